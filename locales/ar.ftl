@@ -0,0 +1,172 @@
+# العربية
+#
+# فهرس Fluent (https://projectfluent.org). تكتب الوسائط بالشكل { $player }؛
+# صيغ الجمع تتبع قواعد اللغة: zero و one و two و few و many و other.
+
+language_name = العربية
+
+## اللعبة
+
+game_title = إكس أو
+mark_x = إكس
+mark_o = أو
+player_label = { $name } ({ $mark })
+player_name_placeholder = اسم اللاعب
+current_player_turn = الدور على: { $player }
+winner = الفائز: { $player }!
+draw = تعادل!
+new_game = لعبة جديدة
+exit = خروج
+side_agent = اللاعب { $player }:
+agent_human = إنسان
+agent_threat = ذكاء اصطناعي: بحث التهديدات
+agent_mcts = ذكاء اصطناعي: مونت كارلو
+language_switch = اللغة
+theme_switch = المظهر
+theme_system = حسب النظام
+theme_dark = داكن
+theme_light = فاتح
+theme_color_blind = مناسب لعمى الألوان
+theme_high_contrast = تباين عالٍ
+reduce_motion = تقليل الحركة
+celebration = الاحتفال بالفوز
+
+## محرك خارجي
+
+external_engine = محرك خارجي:
+load_engine = تحميل كـ O
+engine_load_error = تعذر تشغيل المحرك: { $error }
+
+## اللعب عبر الشبكة
+
+network = الشبكة:
+host_game = إنشاء لعبة
+join_game = انضمام
+connecting = جار الاتصال...
+net_opponent = الخصم: { $name }
+net_you_play = أنت تلعب بـ { $player }
+net_error = خطأ في الشبكة: { $error }
+peer_disconnected = انقطع اتصال الخصم
+peer_resigned = استسلم الخصم
+you_resigned = لقد استسلمت
+resign = استسلام
+rematch = مباراة ثأرية
+rematch_requested = الخصم يطلب مباراة ثأرية
+send = إرسال
+
+## ردهة خادم اللعبة
+
+server = الخادم:
+lobby = الردهة
+no_seeks = لا توجد دعوات مفتوحة
+accept = قبول
+seek = البحث عن خصم
+cancel_seek = إلغاء البحث
+player_name = الاسم:
+time_control = الوقت:
+watch = مشاهدة
+watching = مشاهدة اللعبة { $code }
+spectators = { $count ->
+    [zero] لا مشاهدين
+    [one] مشاهد واحد
+    [two] مشاهدان
+    [few] { $count } مشاهدين
+    [many] { $count } مشاهدًا
+   *[other] { $count } مشاهد
+}
+history = النقلات: { $moves }
+
+## نهاية اللعبة
+
+time_out = انتهى وقت { $player }
+offer_draw = عرض التعادل
+accept_draw = قبول التعادل
+decline_draw = رفض
+abort = إلغاء
+draw_offered = { $player } يعرض التعادل
+draw_declined = رُفض التعادل
+termination_line = اكتمل صف
+termination_full_board = امتلأت اللوحة
+termination_resignation = استسلم { $player }
+termination_agreement = تعادل بالاتفاق
+termination_abandonment = غادر { $player } اللعبة
+termination_aborted = أُلغيت اللعبة
+termination_adjudication = نتيجة بقرار الحكم
+
+## الإحصاءات والمباريات
+
+statistics = الإحصاءات
+stats_total = المجموع: { $score }
+stats_rating = التصنيف: { $rating }
+stats_opponents = الخصوم
+stats_variants = الأنواع
+stats_ai = الذكاء الاصطناعي
+stats_error = لم تُحفظ الإحصاءات: { $error }
+match_format = مباراة (bo3, ft2):
+match_score = نتيجة المباراة: { $score }
+match_winner = { $name } فاز بالمباراة!
+match_drawn = انتهت المباراة بالتعادل!
+
+## الطرفية
+
+console_welcome = مرحبًا بك في إكس أو!
+console_how_to_move = استخدم الأرقام 1-9 للعب:
+console_commands =
+    الأوامر: 'resign' - استسلام، 'draw' - عرض التعادل، 'accept'/'decline' - الرد على العرض،
+    'abort' - إلغاء اللعبة قبل نقلتك الأولى، 'stats' - الإحصاءات.
+console_net_commands =
+    الأوامر: رقم الخانة (1-9)، 'resign' - استسلام، 'draw'/'accept'/'decline' - التعادل،
+    'abort' - إلغاء اللعبة، 'say <نص>' - دردشة، 'stats' - الإحصاءات، 'q' - خروج
+console_gui_hint = لتشغيل الواجهة الرسومية استخدم: cargo run -- --gui
+console_prompt = أدخل خانة (1-9) أو أمرًا:
+console_your_turn = دورك ({ $player }):
+console_peer_turn = دور الخصم...
+console_move = { $name } يلعب: { $position }
+console_no_move = اللاعب { $name } لا يستطيع اللعب.
+console_draw_offered = { $player } يعرض التعادل: 'accept' أو 'decline'.
+console_invalid_move = نقلة غير صالحة! حاول مرة أخرى.
+console_invalid_input = إدخال غير صالح! أدخل رقمًا من 1 إلى 9 أو أمرًا.
+console_not_now = هذا غير ممكن الآن.
+console_draw_accepted = الخصم يقبل التعادل.
+console_draw_rejected = الخصم يرفض التعادل.
+console_plays_x = { $name } يلعب بـ X.
+console_new_match = مباراة جديدة!
+console_play_again = هل تريد اللعب مرة أخرى؟ (نعم/لا):
+console_thanks = شكرًا على اللعب!
+console_quit = جار الخروج من اللعبة...
+console_waiting_peer = في انتظار خصم على { $address }...
+console_connecting_to = جار الاتصال بـ { $address }...
+console_waiting_rematch = في انتظار رد الخصم...
+console_peer_aborted = ألغى الخصم اللعبة.
+console_chat = [دردشة] { $text }
+console_peer_error = الخصم يبلغ عن خطأ: { $error }
+console_message_rejected = رُفضت رسالة من الخصم: { $error }
+console_locales_error = تعذر تحميل الترجمات: { $error }
+console_themes_error = تعذر تحميل السمات: { $error }
+console_default_name = لاعب
+console_tournament_error = خطأ في البطولة: { $error }
+tournament_rules = القواعد: { $rules }
+tournament_elo = إيلو
+console_tournament_start = البطولة: { $games } مباريات لكل زوج...
+console_missing_value = لا توجد قيمة لـ { $option }
+console_invalid_value = قيمة غير صالحة لـ { $option }: { $value }
+console_invalid_rules = قواعد غير صالحة: { $rules }. استخدم classic أو gomoku أو NxN/K حيث N من 2 إلى { $max } و K من 2 إلى N
+console_unknown_option = خيار غير معروف: { $option }
+console_unknown_engine = محرك غير معروف: { $name }. المتاح: threat, mcts
+console_too_few_engines = يلزم محركان على الأقل
+console_engine_usage = الاستخدام: engine [--agent threat|mcts]
+console_protocol_error = خطأ في البروتوكول: { $error }
+console_unknown_agent = وكيل غير معروف لـ { $option }. المتاح: human, threat, mcts
+console_missing_address = حدد عنوانًا لـ { $option }
+console_missing_match = حدد صيغة المباراة، مثل bo3 أو ft2
+console_starting_gui = جارٍ تشغيل النسخة الرسومية...
+console_gui_error = تعذر تشغيل الواجهة الرسومية: { $error }
+console_gui_fallback = جرّب نسخة الطرفية: cargo run
+console_stats_not_saved = لن تُحفظ الإحصاءات: { $error }
+console_locales_dir_error = تعذر تحميل الترجمات من { $dir }: { $error }
+console_catalog_missing = { $locale }: السلسلة { $key } مفقودة
+console_catalog_unused = { $locale }: سلسلة زائدة { $key }
+console_catalogs_complete = الفهارس مكتملة: { $locales }
+# إجابات مفصولة بفواصل
+answer_yes = ن, نعم
+answer_no = ل, لا
@@ -0,0 +1,169 @@
+# Deutsch
+#
+# Fluent-Katalog (https://projectfluent.org). Argumente werden als
+# { $player } geschrieben; Pluralformen folgen den Regeln der Sprache:
+# { $count -> [one] ... *[other] ... }.
+
+language_name = Deutsch
+
+## Spiel
+
+game_title = Tic-Tac-Toe
+mark_x = X
+mark_o = O
+player_label = { $name } ({ $mark })
+player_name_placeholder = Spielername
+current_player_turn = Am Zug: { $player }
+winner = Gewinner: { $player }!
+draw = Unentschieden!
+new_game = Neues Spiel
+exit = Beenden
+side_agent = Spieler { $player }:
+agent_human = Mensch
+agent_threat = KI: Drohungssuche
+agent_mcts = KI: Monte Carlo
+language_switch = Sprache
+theme_switch = Design
+theme_system = Wie im System
+theme_dark = Dunkel
+theme_light = Hell
+theme_color_blind = Farbenblind-sicher
+theme_high_contrast = Hoher Kontrast
+reduce_motion = Bewegung reduzieren
+celebration = Siege feiern
+
+## Externe Engine
+
+external_engine = Externe Engine:
+load_engine = Als O laden
+engine_load_error = Engine konnte nicht gestartet werden: { $error }
+
+## Netzwerkspiel
+
+network = Netzwerk:
+host_game = Spiel eröffnen
+join_game = Beitreten
+connecting = Verbinde...
+net_opponent = Gegner: { $name }
+net_you_play = Du spielst { $player }
+net_error = Netzwerkfehler: { $error }
+peer_disconnected = Gegner hat die Verbindung getrennt
+peer_resigned = Gegner hat aufgegeben
+you_resigned = Du hast aufgegeben
+resign = Aufgeben
+rematch = Revanche
+rematch_requested = Gegner bietet eine Revanche an
+send = Senden
+
+## Lobby des Spielservers
+
+server = Server:
+lobby = Lobby
+no_seeks = Keine offenen Herausforderungen
+accept = Annehmen
+seek = Gegner suchen
+cancel_seek = Suche abbrechen
+player_name = Name:
+time_control = Bedenkzeit:
+watch = Zuschauen
+watching = Partie { $code } wird verfolgt
+spectators = { $count ->
+    [one] { $count } Zuschauer
+   *[other] { $count } Zuschauer
+}
+history = Züge: { $moves }
+
+## Spielende
+
+time_out = { $player } hat die Zeit überschritten
+offer_draw = Remis anbieten
+accept_draw = Remis annehmen
+decline_draw = Ablehnen
+abort = Abbrechen
+draw_offered = { $player } bietet Remis an
+draw_declined = Remis abgelehnt
+termination_line = Reihe vollständig
+termination_full_board = Brett voll
+termination_resignation = { $player } hat aufgegeben
+termination_agreement = Remis nach Vereinbarung
+termination_abandonment = { $player } hat die Partie verlassen
+termination_aborted = Partie abgebrochen
+termination_adjudication = Ergebnis durch Schiedsspruch
+
+## Statistik und Matches
+
+statistics = Statistik
+stats_total = Gesamt: { $score }
+stats_rating = Wertung: { $rating }
+stats_opponents = Gegner
+stats_variants = Varianten
+stats_ai = KI
+stats_error = Statistik nicht gespeichert: { $error }
+match_format = Match (bo3, ft2):
+match_score = Matchstand: { $score }
+match_winner = { $name } gewinnt das Match!
+match_drawn = Das Match endet unentschieden!
+
+## Konsole
+
+console_welcome = Willkommen bei Tic-Tac-Toe!
+console_how_to_move = Ziehe mit den Zahlen 1-9:
+console_commands =
+    Befehle: 'resign' - aufgeben, 'draw' - Remis anbieten, 'accept'/'decline' - auf ein
+    Remisangebot antworten, 'abort' - vor dem ersten eigenen Zug abbrechen, 'stats' - Statistik.
+console_net_commands =
+    Befehle: Feldnummer (1-9), 'resign' - aufgeben, 'draw'/'accept'/'decline' - Remis,
+    'abort' - Partie abbrechen, 'say <Text>' - Chat, 'stats' - Statistik, 'q' - beenden
+console_gui_hint = Grafische Version starten: cargo run -- --gui
+console_prompt = Feld (1-9) oder Befehl eingeben:
+console_your_turn = Dein Zug ({ $player }):
+console_peer_turn = Gegner ist am Zug...
+console_move = { $name } zieht: { $position }
+console_no_move = Spieler { $name } kann keinen Zug machen.
+console_draw_offered = { $player } bietet Remis an: 'accept' oder 'decline'.
+console_invalid_move = Ungültiger Zug! Versuche es noch einmal.
+console_invalid_input = Ungültige Eingabe! Gib eine Zahl von 1 bis 9 oder einen Befehl ein.
+console_not_now = Das ist gerade nicht möglich.
+console_draw_accepted = Der Gegner nimmt das Remis an.
+console_draw_rejected = Der Gegner lehnt das Remis ab.
+console_plays_x = { $name } spielt X.
+console_new_match = Neues Match!
+console_play_again = Noch einmal spielen? (j/n):
+console_thanks = Danke fürs Spielen!
+console_quit = Spiel wird beendet...
+console_waiting_peer = Warte auf einen Gegner auf { $address }...
+console_connecting_to = Verbinde mit { $address }...
+console_waiting_rematch = Warte auf die Antwort des Gegners...
+console_peer_aborted = Der Gegner hat die Partie abgebrochen.
+console_chat = [Chat] { $text }
+console_peer_error = Der Gegner meldet einen Fehler: { $error }
+console_message_rejected = Nachricht des Gegners abgelehnt: { $error }
+console_locales_error = Übersetzungen konnten nicht geladen werden: { $error }
+console_themes_error = Designs konnten nicht geladen werden: { $error }
+console_default_name = Spieler
+console_tournament_error = Turnierfehler: { $error }
+tournament_rules = Regeln: { $rules }
+tournament_elo = Elo
+console_tournament_start = Turnier: { $games } Partien pro Paarung...
+console_missing_value = Kein Wert für { $option }
+console_invalid_value = Ungültiger Wert für { $option }: { $value }
+console_invalid_rules = Ungültige Regeln: { $rules }. Erlaubt sind classic, gomoku oder NxN/K mit N von 2 bis { $max } und K von 2 bis N
+console_unknown_option = Unbekannte Option: { $option }
+console_unknown_engine = Unbekannte Engine: { $name }. Verfügbar: threat, mcts
+console_too_few_engines = Es werden mindestens zwei Engines benötigt
+console_engine_usage = Verwendung: engine [--agent threat|mcts]
+console_protocol_error = Protokollfehler: { $error }
+console_unknown_agent = Unbekannter Agent für { $option }. Verfügbar: human, threat, mcts
+console_missing_address = Geben Sie eine Adresse für { $option } an
+console_missing_match = Geben Sie ein Matchformat an, zum Beispiel bo3 oder ft2
+console_starting_gui = Die grafische Version wird gestartet...
+console_gui_error = GUI konnte nicht gestartet werden: { $error }
+console_gui_fallback = Versuchen Sie die Konsolenversion: cargo run
+console_stats_not_saved = Die Statistik wird nicht gespeichert: { $error }
+console_locales_dir_error = Übersetzungen aus { $dir } konnten nicht geladen werden: { $error }
+console_catalog_missing = { $locale }: Zeichenkette { $key } fehlt
+console_catalog_unused = { $locale }: überflüssige Zeichenkette { $key }
+console_catalogs_complete = Kataloge sind vollständig: { $locales }
+# Antworten durch Kommas getrennt, Groß- und Kleinschreibung egal
+answer_yes = j, ja
+answer_no = n, nein
@@ -0,0 +1,169 @@
+# English
+#
+# Fluent catalog (https://projectfluent.org). Arguments are written as
+# { $player }; plural forms follow the language rules:
+# { $count -> [one] ... *[other] ... }.
+
+language_name = English
+
+## Game
+
+game_title = Tic-Tac-Toe
+mark_x = X
+mark_o = O
+player_label = { $name } ({ $mark })
+player_name_placeholder = Player name
+current_player_turn = Player's turn: { $player }
+winner = Winner: { $player }!
+draw = Draw!
+new_game = New Game
+exit = Exit
+side_agent = Player { $player }:
+agent_human = Human
+agent_threat = AI: threat search
+agent_mcts = AI: Monte Carlo
+language_switch = Language
+theme_switch = Theme
+theme_system = System
+theme_dark = Dark
+theme_light = Light
+theme_color_blind = Color-blind safe
+theme_high_contrast = High contrast
+reduce_motion = Reduce motion
+celebration = Celebrate wins
+
+## External engine
+
+external_engine = External engine:
+load_engine = Load as O
+engine_load_error = Failed to start engine: { $error }
+
+## Network game
+
+network = Network:
+host_game = Host game
+join_game = Join
+connecting = Connecting...
+net_opponent = Opponent: { $name }
+net_you_play = You play { $player }
+net_error = Network error: { $error }
+peer_disconnected = Opponent disconnected
+peer_resigned = Opponent resigned
+you_resigned = You resigned
+resign = Resign
+rematch = Rematch
+rematch_requested = Opponent offers a rematch
+send = Send
+
+## Game server lobby
+
+server = Server:
+lobby = Lobby
+no_seeks = No open seeks
+accept = Accept
+seek = Seek opponent
+cancel_seek = Cancel seek
+player_name = Name:
+time_control = Time control:
+watch = Watch
+watching = Watching game { $code }
+spectators = { $count ->
+    [one] { $count } spectator
+   *[other] { $count } spectators
+}
+history = Moves: { $moves }
+
+## Game end
+
+time_out = { $player } ran out of time
+offer_draw = Offer draw
+accept_draw = Accept draw
+decline_draw = Decline
+abort = Abort
+draw_offered = { $player } offers a draw
+draw_declined = Draw declined
+termination_line = Line completed
+termination_full_board = Board full
+termination_resignation = { $player } resigned
+termination_agreement = Draw by agreement
+termination_abandonment = { $player } abandoned the game
+termination_aborted = Game aborted
+termination_adjudication = Result adjudicated
+
+## Statistics and matches
+
+statistics = Statistics
+stats_total = Total: { $score }
+stats_rating = Rating: { $rating }
+stats_opponents = Opponents
+stats_variants = Variants
+stats_ai = AI
+stats_error = Statistics not saved: { $error }
+match_format = Match (bo3, ft2):
+match_score = Match score: { $score }
+match_winner = { $name } wins the match!
+match_drawn = The match is drawn!
+
+## Console
+
+console_welcome = Welcome to Tic-Tac-Toe!
+console_how_to_move = Use the numbers 1-9 to move:
+console_commands =
+    Commands: 'resign' - resign, 'draw' - offer a draw, 'accept'/'decline' - answer
+    a draw offer, 'abort' - abort the game before your first move, 'stats' - statistics.
+console_net_commands =
+    Commands: cell number (1-9), 'resign' - resign, 'draw'/'accept'/'decline' - draw,
+    'abort' - abort the game, 'say <text>' - chat, 'stats' - statistics, 'q' - quit
+console_gui_hint = To start the graphical version use: cargo run -- --gui
+console_prompt = Enter a position (1-9) or a command:
+console_your_turn = Your move ({ $player }):
+console_peer_turn = Opponent's move...
+console_move = { $name } plays: { $position }
+console_no_move = Player { $name } cannot make a move.
+console_draw_offered = { $player } offers a draw: 'accept' or 'decline'.
+console_invalid_move = Invalid move! Try again.
+console_invalid_input = Invalid input! Enter a number from 1 to 9 or a command.
+console_not_now = That is not possible right now.
+console_draw_accepted = The opponent accepts the draw.
+console_draw_rejected = The opponent declines the draw.
+console_plays_x = { $name } plays X.
+console_new_match = New match!
+console_play_again = Play again? (y/n):
+console_thanks = Thanks for playing!
+console_quit = Exiting the game...
+console_waiting_peer = Waiting for an opponent on { $address }...
+console_connecting_to = Connecting to { $address }...
+console_waiting_rematch = Waiting for the opponent to answer...
+console_peer_aborted = The opponent aborted the game.
+console_chat = [chat] { $text }
+console_peer_error = The opponent reports an error: { $error }
+console_message_rejected = Rejected a message from the opponent: { $error }
+console_locales_error = Could not load translations: { $error }
+console_themes_error = Could not load themes: { $error }
+console_default_name = Player
+console_tournament_error = Tournament error: { $error }
+tournament_rules = Rules: { $rules }
+tournament_elo = Elo
+console_tournament_start = Tournament: { $games } games per pair...
+console_missing_value = No value for { $option }
+console_invalid_value = Invalid value for { $option }: { $value }
+console_invalid_rules = Invalid rules: { $rules }. Use classic, gomoku or NxN/K with N from 2 to { $max } and K from 2 to N
+console_unknown_option = Unknown option: { $option }
+console_unknown_engine = Unknown engine: { $name }. Available: threat, mcts
+console_too_few_engines = At least two engines are needed
+console_engine_usage = Usage: engine [--agent threat|mcts]
+console_protocol_error = Protocol error: { $error }
+console_unknown_agent = Unknown agent for { $option }. Available: human, threat, mcts
+console_missing_address = Specify an address for { $option }
+console_missing_match = Specify a match format, for example bo3 or ft2
+console_starting_gui = Starting the graphical version...
+console_gui_error = Could not start the GUI: { $error }
+console_gui_fallback = Try the console version: cargo run
+console_stats_not_saved = Statistics will not be saved: { $error }
+console_locales_dir_error = Could not load translations from { $dir }: { $error }
+console_catalog_missing = { $locale }: missing string { $key }
+console_catalog_unused = { $locale }: unused string { $key }
+console_catalogs_complete = Catalogs are complete: { $locales }
+# Comma-separated answers, case-insensitive
+answer_yes = y, yes
+answer_no = n, no
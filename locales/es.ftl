@@ -0,0 +1,169 @@
+# Español
+#
+# Catálogo Fluent (https://projectfluent.org). Los argumentos se escriben
+# como { $player }; las formas de plural siguen las reglas del idioma:
+# { $count -> [one] ... *[other] ... }.
+
+language_name = Español
+
+## Juego
+
+game_title = Tres en raya
+mark_x = X
+mark_o = O
+player_label = { $name } ({ $mark })
+player_name_placeholder = Nombre del jugador
+current_player_turn = Turno de: { $player }
+winner = ¡Ganador: { $player }!
+draw = ¡Empate!
+new_game = Nueva partida
+exit = Salir
+side_agent = Jugador { $player }:
+agent_human = Humano
+agent_threat = IA: búsqueda de amenazas
+agent_mcts = IA: Monte Carlo
+language_switch = Idioma
+theme_switch = Tema
+theme_system = Del sistema
+theme_dark = Oscuro
+theme_light = Claro
+theme_color_blind = Apto para daltónicos
+theme_high_contrast = Alto contraste
+reduce_motion = Reducir movimiento
+celebration = Celebrar victorias
+
+## Motor externo
+
+external_engine = Motor externo:
+load_engine = Cargar como O
+engine_load_error = No se pudo iniciar el motor: { $error }
+
+## Partida en red
+
+network = Red:
+host_game = Crear partida
+join_game = Unirse
+connecting = Conectando...
+net_opponent = Rival: { $name }
+net_you_play = Juegas con { $player }
+net_error = Error de red: { $error }
+peer_disconnected = El rival se desconectó
+peer_resigned = El rival se rindió
+you_resigned = Te rendiste
+resign = Rendirse
+rematch = Revancha
+rematch_requested = El rival propone una revancha
+send = Enviar
+
+## Sala del servidor
+
+server = Servidor:
+lobby = Sala
+no_seeks = No hay desafíos abiertos
+accept = Aceptar
+seek = Buscar rival
+cancel_seek = Cancelar búsqueda
+player_name = Nombre:
+time_control = Control de tiempo:
+watch = Ver
+watching = Viendo la partida { $code }
+spectators = { $count ->
+    [one] { $count } espectador
+   *[other] { $count } espectadores
+}
+history = Jugadas: { $moves }
+
+## Fin de la partida
+
+time_out = { $player } se quedó sin tiempo
+offer_draw = Ofrecer tablas
+accept_draw = Aceptar tablas
+decline_draw = Rechazar
+abort = Anular
+draw_offered = { $player } ofrece tablas
+draw_declined = Tablas rechazadas
+termination_line = Línea completa
+termination_full_board = Tablero lleno
+termination_resignation = { $player } se rindió
+termination_agreement = Tablas por acuerdo
+termination_abandonment = { $player } abandonó la partida
+termination_aborted = Partida anulada
+termination_adjudication = Resultado adjudicado
+
+## Estadísticas y encuentros
+
+statistics = Estadísticas
+stats_total = Total: { $score }
+stats_rating = Puntuación: { $rating }
+stats_opponents = Rivales
+stats_variants = Variantes
+stats_ai = IA
+stats_error = Estadísticas no guardadas: { $error }
+match_format = Encuentro (bo3, ft2):
+match_score = Marcador: { $score }
+match_winner = ¡{ $name } gana el encuentro!
+match_drawn = ¡El encuentro termina en empate!
+
+## Consola
+
+console_welcome = ¡Bienvenido al tres en raya!
+console_how_to_move = Usa los números 1-9 para jugar:
+console_commands =
+    Comandos: 'resign' - rendirse, 'draw' - ofrecer tablas, 'accept'/'decline' - responder
+    a una oferta, 'abort' - anular antes de tu primera jugada, 'stats' - estadísticas.
+console_net_commands =
+    Comandos: número de casilla (1-9), 'resign' - rendirse, 'draw'/'accept'/'decline' - tablas,
+    'abort' - anular la partida, 'say <texto>' - chat, 'stats' - estadísticas, 'q' - salir
+console_gui_hint = Para iniciar la versión gráfica usa: cargo run -- --gui
+console_prompt = Introduce una casilla (1-9) o un comando:
+console_your_turn = Tu jugada ({ $player }):
+console_peer_turn = Juega el rival...
+console_move = { $name } juega: { $position }
+console_no_move = El jugador { $name } no puede jugar.
+console_draw_offered = { $player } ofrece tablas: 'accept' o 'decline'.
+console_invalid_move = ¡Jugada no válida! Inténtalo de nuevo.
+console_invalid_input = ¡Entrada no válida! Introduce un número del 1 al 9 o un comando.
+console_not_now = Ahora no es posible.
+console_draw_accepted = El rival acepta las tablas.
+console_draw_rejected = El rival rechaza las tablas.
+console_plays_x = { $name } juega con X.
+console_new_match = ¡Nuevo encuentro!
+console_play_again = ¿Jugar otra vez? (s/n):
+console_thanks = ¡Gracias por jugar!
+console_quit = Saliendo del juego...
+console_waiting_peer = Esperando a un rival en { $address }...
+console_connecting_to = Conectando con { $address }...
+console_waiting_rematch = Esperando la respuesta del rival...
+console_peer_aborted = El rival anuló la partida.
+console_chat = [chat] { $text }
+console_peer_error = El rival informa de un error: { $error }
+console_message_rejected = Mensaje del rival rechazado: { $error }
+console_locales_error = No se pudieron cargar las traducciones: { $error }
+console_themes_error = No se pudieron cargar los temas: { $error }
+console_default_name = Jugador
+console_tournament_error = Error del torneo: { $error }
+tournament_rules = Reglas: { $rules }
+tournament_elo = Elo
+console_tournament_start = Torneo: { $games } partidas por pareja...
+console_missing_value = Falta el valor de { $option }
+console_invalid_value = Valor no válido para { $option }: { $value }
+console_invalid_rules = Reglas no válidas: { $rules }. Use classic, gomoku o NxN/K con N de 2 a { $max } y K de 2 a N
+console_unknown_option = Opción desconocida: { $option }
+console_unknown_engine = Motor desconocido: { $name }. Disponibles: threat, mcts
+console_too_few_engines = Se necesitan al menos dos motores
+console_engine_usage = Uso: engine [--agent threat|mcts]
+console_protocol_error = Error de protocolo: { $error }
+console_unknown_agent = Agente desconocido para { $option }. Disponibles: human, threat, mcts
+console_missing_address = Indique una dirección para { $option }
+console_missing_match = Indique el formato del match, por ejemplo bo3 o ft2
+console_starting_gui = Iniciando la versión gráfica...
+console_gui_error = No se pudo iniciar la interfaz gráfica: { $error }
+console_gui_fallback = Pruebe la versión de consola: cargo run
+console_stats_not_saved = Las estadísticas no se guardarán: { $error }
+console_locales_dir_error = No se pudieron cargar las traducciones de { $dir }: { $error }
+console_catalog_missing = { $locale }: falta la cadena { $key }
+console_catalog_unused = { $locale }: cadena sobrante { $key }
+console_catalogs_complete = Los catálogos están completos: { $locales }
+# Respuestas separadas por comas, sin distinguir mayúsculas
+answer_yes = s, sí, si
+answer_no = n, no
@@ -0,0 +1,167 @@
+# 日本語
+#
+# Fluent カタログ (https://projectfluent.org)。引数は { $player } と書きます。
+# 日本語には複数形がないため *[other] だけを使います。
+
+language_name = 日本語
+
+## ゲーム
+
+game_title = 三目並べ
+mark_x = ×
+mark_o = ○
+player_label = { $name }（{ $mark }）
+player_name_placeholder = プレイヤー名
+current_player_turn = 手番：{ $player }
+winner = 勝者：{ $player }！
+draw = 引き分け！
+new_game = 新しいゲーム
+exit = 終了
+side_agent = プレイヤー { $player }：
+agent_human = 人間
+agent_threat = AI：脅威探索
+agent_mcts = AI：モンテカルロ
+language_switch = 言語
+theme_switch = テーマ
+theme_system = システムに合わせる
+theme_dark = ダーク
+theme_light = ライト
+theme_color_blind = 色覚サポート
+theme_high_contrast = ハイコントラスト
+reduce_motion = 動きを減らす
+celebration = 勝利を祝う
+
+## 外部エンジン
+
+external_engine = 外部エンジン：
+load_engine = O として読み込む
+engine_load_error = エンジンを起動できません：{ $error }
+
+## ネット対戦
+
+network = ネットワーク：
+host_game = ゲームを作成
+join_game = 参加
+connecting = 接続中...
+net_opponent = 対戦相手：{ $name }
+net_you_play = あなたは { $player }
+net_error = ネットワークエラー：{ $error }
+peer_disconnected = 対戦相手が切断しました
+peer_resigned = 対戦相手が投了しました
+you_resigned = 投了しました
+resign = 投了
+rematch = 再戦
+rematch_requested = 対戦相手が再戦を申し込んでいます
+send = 送信
+
+## ゲームサーバーのロビー
+
+server = サーバー：
+lobby = ロビー
+no_seeks = 募集中の対局はありません
+accept = 受ける
+seek = 対戦相手を探す
+cancel_seek = 募集を取り消す
+player_name = 名前：
+time_control = 持ち時間：
+watch = 観戦
+watching = 対局 { $code } を観戦中
+spectators = { $count ->
+   *[other] 観戦者 { $count } 人
+}
+history = 手順：{ $moves }
+
+## 対局終了
+
+time_out = { $player } の時間切れ
+offer_draw = 引き分けを提案
+accept_draw = 引き分けを受ける
+decline_draw = 断る
+abort = 中止
+draw_offered = { $player } が引き分けを提案しています
+draw_declined = 引き分けは断られました
+termination_line = 一列そろいました
+termination_full_board = 盤が埋まりました
+termination_resignation = { $player } が投了しました
+termination_agreement = 合意による引き分け
+termination_abandonment = { $player } が対局を放棄しました
+termination_aborted = 対局は中止されました
+termination_adjudication = 裁定による結果
+
+## 統計と試合
+
+statistics = 統計
+stats_total = 合計：{ $score }
+stats_rating = レーティング：{ $rating }
+stats_opponents = 対戦相手
+stats_variants = ルール
+stats_ai = AI
+stats_error = 統計を保存できません：{ $error }
+match_format = 試合 (bo3, ft2)：
+match_score = 試合のスコア：{ $score }
+match_winner = { $name } が試合に勝ちました！
+match_drawn = 試合は引き分けです！
+
+## コンソール
+
+console_welcome = 三目並べへようこそ！
+console_how_to_move = 1-9 の数字で打ちます：
+console_commands =
+    コマンド：'resign' - 投了、'draw' - 引き分けを提案、'accept'/'decline' - 提案に回答、
+    'abort' - 最初の手の前に中止、'stats' - 統計。
+console_net_commands =
+    コマンド：マス番号 (1-9)、'resign' - 投了、'draw'/'accept'/'decline' - 引き分け、
+    'abort' - 対局を中止、'say <テキスト>' - チャット、'stats' - 統計、'q' - 終了
+console_gui_hint = グラフィカル版の起動：cargo run -- --gui
+console_prompt = 位置 (1-9) またはコマンドを入力：
+console_your_turn = あなたの手番 ({ $player })：
+console_peer_turn = 対戦相手の手番...
+console_move = { $name } の手：{ $position }
+console_no_move = プレイヤー { $name } は打てません。
+console_draw_offered = { $player } が引き分けを提案しています：'accept' または 'decline'。
+console_invalid_move = 無効な手です！もう一度どうぞ。
+console_invalid_input = 無効な入力です！1 から 9 の数字かコマンドを入力してください。
+console_not_now = 今はできません。
+console_draw_accepted = 対戦相手は引き分けを受けました。
+console_draw_rejected = 対戦相手は引き分けを断りました。
+console_plays_x = { $name } が X です。
+console_new_match = 新しい試合！
+console_play_again = もう一度遊びますか？(はい/いいえ)：
+console_thanks = 遊んでくれてありがとう！
+console_quit = ゲームを終了します...
+console_waiting_peer = { $address } で対戦相手を待っています...
+console_connecting_to = { $address } に接続中...
+console_waiting_rematch = 対戦相手の返事を待っています...
+console_peer_aborted = 対戦相手が対局を中止しました。
+console_chat = [チャット] { $text }
+console_peer_error = 対戦相手がエラーを報告しました：{ $error }
+console_message_rejected = 対戦相手のメッセージを拒否しました：{ $error }
+console_locales_error = 翻訳を読み込めません：{ $error }
+console_themes_error = テーマを読み込めませんでした：{ $error }
+console_default_name = プレイヤー
+console_tournament_error = トーナメントのエラー：{ $error }
+tournament_rules = ルール：{ $rules }
+tournament_elo = イロレーティング
+console_tournament_start = トーナメント：各組 { $games } 局...
+console_missing_value = { $option } の値がありません
+console_invalid_value = { $option } の値が正しくありません：{ $value }
+console_invalid_rules = ルールが正しくありません：{ $rules }。classic、gomoku、または NxN/K（N は 2〜{ $max }、K は 2〜N）を指定してください
+console_unknown_option = 不明なオプション：{ $option }
+console_unknown_engine = 不明なエンジン：{ $name }。使用可能：threat, mcts
+console_too_few_engines = エンジンが少なくとも2つ必要です
+console_engine_usage = 使い方：engine [--agent threat|mcts]
+console_protocol_error = プロトコルエラー：{ $error }
+console_unknown_agent = { $option } のエージェントが不明です。使用可能：human, threat, mcts
+console_missing_address = { $option } のアドレスを指定してください
+console_missing_match = 試合形式を指定してください（例：bo3、ft2）
+console_starting_gui = グラフィカル版を起動しています...
+console_gui_error = GUI を起動できません：{ $error }
+console_gui_fallback = コンソール版を試してください：cargo run
+console_stats_not_saved = 統計は保存されません：{ $error }
+console_locales_dir_error = { $dir } から翻訳を読み込めません：{ $error }
+console_catalog_missing = { $locale }：文字列 { $key } がありません
+console_catalog_unused = { $locale }：不要な文字列 { $key }
+console_catalogs_complete = カタログは完全です：{ $locales }
+# カンマ区切りの回答
+answer_yes = はい, うん
+answer_no = いいえ, いや
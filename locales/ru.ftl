@@ -0,0 +1,170 @@
+# Русский язык
+#
+# Каталог в формате Fluent (https://projectfluent.org). Аргументы
+# подставляются как { $player }; формы множественного числа выбираются
+# по правилам языка: { $count -> [one] ... *[many] ... }.
+
+language_name = Русский
+
+## Игра
+
+game_title = Крестики-нолики
+mark_x = крестики
+mark_o = нолики
+player_label = { $name } ({ $mark })
+player_name_placeholder = Имя игрока
+current_player_turn = Ход игрока: { $player }
+winner = Победитель: { $player }!
+draw = Ничья!
+new_game = Новая игра
+exit = Выход
+side_agent = Игрок { $player }:
+agent_human = Человек
+agent_threat = ИИ: поиск угроз
+agent_mcts = ИИ: Монте-Карло
+language_switch = Язык
+theme_switch = Тема
+theme_system = Как в системе
+theme_dark = Темная
+theme_light = Светлая
+theme_color_blind = Для дальтоников
+theme_high_contrast = Высокий контраст
+reduce_motion = Меньше движения
+celebration = Праздновать победу
+
+## Внешний движок
+
+external_engine = Внешний движок:
+load_engine = Загрузить за O
+engine_load_error = Не удалось запустить движок: { $error }
+
+## Игра по сети
+
+network = Сеть:
+host_game = Создать игру
+join_game = Подключиться
+connecting = Подключение...
+net_opponent = Соперник: { $name }
+net_you_play = Вы играете за { $player }
+net_error = Ошибка сети: { $error }
+peer_disconnected = Соперник отключился
+peer_resigned = Соперник сдался
+you_resigned = Вы сдались
+resign = Сдаться
+rematch = Реванш
+rematch_requested = Соперник предлагает реванш
+send = Отправить
+
+## Лобби игрового сервера
+
+server = Сервер:
+lobby = Лобби
+no_seeks = Нет открытых заявок
+accept = Принять
+seek = Найти соперника
+cancel_seek = Отменить заявку
+player_name = Имя:
+time_control = Контроль:
+watch = Смотреть
+watching = Просмотр партии { $code }
+spectators = { $count ->
+    [one] { $count } зритель
+    [few] { $count } зрителя
+   *[many] { $count } зрителей
+}
+history = Ходы: { $moves }
+
+## Окончание партии
+
+time_out = У { $player } истекло время
+offer_draw = Предложить ничью
+accept_draw = Принять ничью
+decline_draw = Отклонить
+abort = Прервать
+draw_offered = { $player } предлагает ничью
+draw_declined = Ничья отклонена
+termination_line = Линия собрана
+termination_full_board = Поле заполнено
+termination_resignation = { $player } сдался
+termination_agreement = Ничья по соглашению
+termination_abandonment = { $player } покинул партию
+termination_aborted = Партия прервана
+termination_adjudication = Результат назначен судьей
+
+## Статистика и матчи
+
+statistics = Статистика
+stats_total = Всего: { $score }
+stats_rating = Рейтинг: { $rating }
+stats_opponents = Соперники
+stats_variants = Варианты
+stats_ai = ИИ
+stats_error = Статистика не сохранена: { $error }
+match_format = Матч (bo3, ft2):
+match_score = Счет матча: { $score }
+match_winner = Матч выиграл { $name }!
+match_drawn = Матч закончился вничью!
+
+## Консоль
+
+console_welcome = Добро пожаловать в Крестики-нолики!
+console_how_to_move = Используйте числа 1-9 для хода:
+console_commands =
+    Команды: 'resign' - сдаться, 'draw' - предложить ничью, 'accept'/'decline' - ответить
+    на предложение, 'abort' - прервать партию до своего первого хода, 'stats' - статистика.
+console_net_commands =
+    Команды: номер клетки (1-9), 'resign' - сдаться, 'draw'/'accept'/'decline' - ничья,
+    'abort' - прервать партию, 'say <текст>' - чат, 'stats' - статистика, 'q' - выход
+console_gui_hint = Для запуска графической версии используйте: cargo run -- --gui
+console_prompt = Введите позицию (1-9) или команду:
+console_your_turn = Ваш ход ({ $player }):
+console_peer_turn = Ход соперника...
+console_move = { $name } ходит: { $position }
+console_no_move = Игрок { $name } не может сделать ход.
+console_draw_offered = { $player } предлагает ничью: 'accept' или 'decline'.
+console_invalid_move = Неверный ход! Попробуйте снова.
+console_invalid_input = Неверный ввод! Введите число от 1 до 9 или команду.
+console_not_now = Сейчас это невозможно.
+console_draw_accepted = Соперник принимает ничью.
+console_draw_rejected = Соперник отклоняет ничью.
+console_plays_x = { $name } играет за X.
+console_new_match = Новый матч!
+console_play_again = Хотите сыграть еще раз? (д/н):
+console_thanks = Спасибо за игру!
+console_quit = Выход из игры...
+console_waiting_peer = Ожидание соперника на { $address }...
+console_connecting_to = Подключение к { $address }...
+console_waiting_rematch = Ждем ответа соперника...
+console_peer_aborted = Соперник прервал партию.
+console_chat = [чат] { $text }
+console_peer_error = Соперник сообщает об ошибке: { $error }
+console_message_rejected = Отклонено сообщение соперника: { $error }
+console_locales_error = Не удалось загрузить переводы: { $error }
+console_themes_error = Не удалось загрузить темы: { $error }
+console_default_name = Игрок
+console_tournament_error = Ошибка турнира: { $error }
+tournament_rules = Правила: { $rules }
+tournament_elo = Эло
+console_tournament_start = Турнир: { $games } партий на пару...
+console_missing_value = Нет значения для { $option }
+console_invalid_value = Неверное значение { $option }: { $value }
+console_invalid_rules = Неверные правила: { $rules }. Укажите classic, gomoku или NxN/K, где N от 2 до { $max }, а K от 2 до N
+console_unknown_option = Неизвестный параметр: { $option }
+console_unknown_engine = Неизвестный движок: { $name }. Доступны: threat, mcts
+console_too_few_engines = Нужно хотя бы два движка
+console_engine_usage = Использование: engine [--agent threat|mcts]
+console_protocol_error = Ошибка протокола: { $error }
+console_unknown_agent = Неизвестный агент для { $option }. Доступны: human, threat, mcts
+console_missing_address = Укажите адрес для { $option }
+console_missing_match = Укажите формат матча, например bo3 или ft2
+console_starting_gui = Запуск графической версии...
+console_gui_error = Ошибка запуска GUI: { $error }
+console_gui_fallback = Попробуйте запустить консольную версию: cargo run
+console_stats_not_saved = Статистика не будет сохранена: { $error }
+console_locales_dir_error = Не удалось загрузить переводы из { $dir }: { $error }
+console_catalog_missing = { $locale }: нет строки { $key }
+console_catalog_unused = { $locale }: лишняя строка { $key }
+console_catalogs_complete = Каталоги полные: { $locales }
+# Варианты ответа через запятую, без учета регистра
+answer_yes = д, да
+answer_no = н, нет
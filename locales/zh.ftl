@@ -0,0 +1,167 @@
+# 简体中文
+#
+# Fluent 目录 (https://projectfluent.org)。参数写作 { $player }；
+# 中文没有复数变化，只使用 *[other] 形式。
+
+language_name = 中文
+
+## 游戏
+
+game_title = 井字棋
+mark_x = 叉
+mark_o = 圈
+player_label = { $name }（{ $mark }）
+player_name_placeholder = 玩家名字
+current_player_turn = 轮到：{ $player }
+winner = 胜者：{ $player }！
+draw = 平局！
+new_game = 新游戏
+exit = 退出
+side_agent = 玩家 { $player }：
+agent_human = 人类
+agent_threat = AI：威胁搜索
+agent_mcts = AI：蒙特卡洛
+language_switch = 语言
+theme_switch = 主题
+theme_system = 跟随系统
+theme_dark = 深色
+theme_light = 浅色
+theme_color_blind = 色盲友好
+theme_high_contrast = 高对比度
+reduce_motion = 减少动态效果
+celebration = 庆祝胜利
+
+## 外部引擎
+
+external_engine = 外部引擎：
+load_engine = 加载为 O
+engine_load_error = 无法启动引擎：{ $error }
+
+## 网络对战
+
+network = 网络：
+host_game = 创建游戏
+join_game = 加入
+connecting = 正在连接...
+net_opponent = 对手：{ $name }
+net_you_play = 你执 { $player }
+net_error = 网络错误：{ $error }
+peer_disconnected = 对手已断开连接
+peer_resigned = 对手认输
+you_resigned = 你认输了
+resign = 认输
+rematch = 再来一局
+rematch_requested = 对手请求再来一局
+send = 发送
+
+## 游戏服务器大厅
+
+server = 服务器：
+lobby = 大厅
+no_seeks = 没有公开的对局邀请
+accept = 接受
+seek = 寻找对手
+cancel_seek = 取消寻找
+player_name = 名字：
+time_control = 用时：
+watch = 观战
+watching = 正在观看对局 { $code }
+spectators = { $count ->
+   *[other] { $count } 名观众
+}
+history = 着法：{ $moves }
+
+## 对局结束
+
+time_out = { $player } 超时
+offer_draw = 提议和棋
+accept_draw = 接受和棋
+decline_draw = 拒绝
+abort = 中止
+draw_offered = { $player } 提议和棋
+draw_declined = 和棋提议被拒绝
+termination_line = 连成一线
+termination_full_board = 棋盘已满
+termination_resignation = { $player } 认输
+termination_agreement = 双方同意和棋
+termination_abandonment = { $player } 离开了对局
+termination_aborted = 对局已中止
+termination_adjudication = 裁判判定结果
+
+## 统计与比赛
+
+statistics = 统计
+stats_total = 总计：{ $score }
+stats_rating = 等级分：{ $rating }
+stats_opponents = 对手
+stats_variants = 变体
+stats_ai = AI
+stats_error = 统计未保存：{ $error }
+match_format = 比赛 (bo3, ft2)：
+match_score = 比分：{ $score }
+match_winner = { $name } 赢得比赛！
+match_drawn = 比赛打平！
+
+## 控制台
+
+console_welcome = 欢迎来到井字棋！
+console_how_to_move = 用数字 1-9 落子：
+console_commands =
+    命令：'resign' - 认输，'draw' - 提议和棋，'accept'/'decline' - 回应和棋提议，
+    'abort' - 在你第一步之前中止对局，'stats' - 统计。
+console_net_commands =
+    命令：格子编号 (1-9)，'resign' - 认输，'draw'/'accept'/'decline' - 和棋，
+    'abort' - 中止对局，'say <文字>' - 聊天，'stats' - 统计，'q' - 退出
+console_gui_hint = 启动图形版本：cargo run -- --gui
+console_prompt = 输入位置 (1-9) 或命令：
+console_your_turn = 轮到你 ({ $player })：
+console_peer_turn = 对手思考中...
+console_move = { $name } 落子：{ $position }
+console_no_move = 玩家 { $name } 无法落子。
+console_draw_offered = { $player } 提议和棋：'accept' 或 'decline'。
+console_invalid_move = 无效的着法！请重试。
+console_invalid_input = 无效的输入！请输入 1 到 9 的数字或命令。
+console_not_now = 现在无法这样做。
+console_draw_accepted = 对手接受和棋。
+console_draw_rejected = 对手拒绝和棋。
+console_plays_x = { $name } 执 X。
+console_new_match = 新的比赛！
+console_play_again = 再玩一局吗？(是/否)：
+console_thanks = 感谢游玩！
+console_quit = 正在退出游戏...
+console_waiting_peer = 正在 { $address } 等待对手...
+console_connecting_to = 正在连接 { $address }...
+console_waiting_rematch = 等待对手回应...
+console_peer_aborted = 对手中止了对局。
+console_chat = [聊天] { $text }
+console_peer_error = 对手报告错误：{ $error }
+console_message_rejected = 已拒绝对手的消息：{ $error }
+console_locales_error = 无法加载翻译：{ $error }
+console_themes_error = 无法加载主题：{ $error }
+console_default_name = 玩家
+console_tournament_error = 锦标赛错误：{ $error }
+tournament_rules = 规则：{ $rules }
+tournament_elo = 等级分
+console_tournament_start = 锦标赛：每对 { $games } 局...
+console_missing_value = { $option } 缺少值
+console_invalid_value = { $option } 的值无效：{ $value }
+console_invalid_rules = 规则无效：{ $rules }。请使用 classic、gomoku 或 NxN/K，其中 N 为 2 到 { $max }，K 为 2 到 N
+console_unknown_option = 未知参数：{ $option }
+console_unknown_engine = 未知引擎：{ $name }。可用：threat, mcts
+console_too_few_engines = 至少需要两个引擎
+console_engine_usage = 用法：engine [--agent threat|mcts]
+console_protocol_error = 协议错误：{ $error }
+console_unknown_agent = { $option } 的代理未知。可用：human, threat, mcts
+console_missing_address = 请为 { $option } 指定地址
+console_missing_match = 请指定比赛格式，例如 bo3 或 ft2
+console_starting_gui = 正在启动图形版本...
+console_gui_error = 无法启动图形界面：{ $error }
+console_gui_fallback = 请尝试控制台版本：cargo run
+console_stats_not_saved = 统计数据将不会保存：{ $error }
+console_locales_dir_error = 无法从 { $dir } 加载翻译：{ $error }
+console_catalog_missing = { $locale }：缺少字符串 { $key }
+console_catalog_unused = { $locale }：多余的字符串 { $key }
+console_catalogs_complete = 目录完整：{ $locales }
+# 以逗号分隔的回答
+answer_yes = 是, 好
+answer_no = 否, 不
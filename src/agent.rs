@@ -0,0 +1,638 @@
+//! # Агенты - участники партии
+//!
+//! Агент отвечает на вопрос "какой ход сделать в этой позиции".
+//! Это может быть человек за клавиатурой или мышью, любой движок ИИ,
+//! заранее записанный список ходов или удаленный соперник.
+//!
+//! Есть два варианта интерфейса:
+//! - [`Agent`] - синхронный, блокирует до получения хода (консоль, турниры)
+//! - [`AsyncAgent`] - опрашиваемый, не блокирует (GUI и веб-версия, которые
+//!   перерисовываются каждый кадр)
+//!
+//! ## Использование
+//! ```rust
+//! use rust_tic_tac_toe::TicTacToe;
+//! use rust_tic_tac_toe::agent::{Agent, ScriptedAgent};
+//!
+//! let mut game = TicTacToe::new();
+//! let mut x = ScriptedAgent::new(vec![4, 0]);
+//! let position = x.choose_move(&game).unwrap();
+//! assert!(game.make_move(position));
+//! ```
+
+use std::collections::VecDeque;
+use std::sync::mpsc::{Receiver, Sender, TryRecvError};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::engine::{self, Engine};
+use crate::mcts::{MctsConfig, MctsEngine};
+use crate::{MessageKey, Player, Rules, Termination, TicTacToe};
+
+/// Время на ход движка в браузере
+///
+/// Ход считается в том же кадре, что и отрисовка, поэтому страница не должна
+/// замирать дольше нескольких кадров.
+pub const WEB_MOVE_TIME: Duration = Duration::from_millis(150);
+
+/// Синхронный агент: возвращает ход, когда он готов
+pub trait Agent {
+    /// Название агента для отображения
+    fn name(&self) -> String;
+
+    /// Выбирает ход в текущей позиции
+    ///
+    /// ## Возвращает
+    /// - `Some(position)` - выбранная клетка
+    /// - `None` - агент не может или не хочет ходить (конец записи, обрыв связи)
+    fn choose_move(&mut self, game: &TicTacToe) -> Option<usize>;
+
+    /// Сообщает агенту о ходе соперника
+    fn observe_move(&mut self, _game: &TicTacToe, _position: usize) {}
+
+    /// Сообщает агенту о начале новой партии
+    fn new_game(&mut self, _game: &TicTacToe) {}
+}
+
+/// Результат опроса асинхронного агента
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MovePoll {
+    /// Ход готов
+    Ready(usize),
+    /// Агент еще думает или ждет ввода
+    Pending,
+    /// Агент больше не будет ходить
+    Unavailable,
+}
+
+/// Асинхронный агент: ход запрашивается и затем опрашивается каждый кадр
+pub trait AsyncAgent {
+    /// Название агента для отображения
+    fn name(&self) -> String;
+
+    /// Просит агента начать выбор хода в текущей позиции
+    fn request_move(&mut self, game: &TicTacToe);
+
+    /// Проверяет, готов ли ход (не блокирует)
+    fn poll_move(&mut self) -> MovePoll;
+
+    /// Ждет ли агент ввода от локального пользователя
+    fn needs_input(&self) -> bool {
+        false
+    }
+
+    /// Передает агенту ввод локального пользователя (клик по клетке)
+    fn submit_input(&mut self, _position: usize) {}
+
+    /// Сообщает агенту о ходе соперника
+    fn observe_move(&mut self, _game: &TicTacToe, _position: usize) {}
+
+    /// Сообщает агенту о начале новой партии
+    fn new_game(&mut self, _game: &TicTacToe) {}
+}
+
+// === ЛОКАЛЬНЫЙ ИГРОК ===
+
+/// Человек, который ходит кликами в интерфейсе
+#[derive(Clone, Debug, Default)]
+pub struct HumanAgent {
+    /// Выбранная, но еще не забранная клетка
+    pending: Option<usize>,
+}
+
+impl HumanAgent {
+    /// Создает агента локального игрока
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl AsyncAgent for HumanAgent {
+    fn name(&self) -> String {
+        "Human".to_string()
+    }
+
+    fn request_move(&mut self, _game: &TicTacToe) {
+        self.pending = None;
+    }
+
+    fn poll_move(&mut self) -> MovePoll {
+        match self.pending.take() {
+            Some(position) => MovePoll::Ready(position),
+            None => MovePoll::Pending,
+        }
+    }
+
+    fn needs_input(&self) -> bool {
+        true
+    }
+
+    fn submit_input(&mut self, position: usize) {
+        self.pending = Some(position);
+    }
+}
+
+// === ДВИЖКИ ИИ ===
+
+/// Агент, который спрашивает ход у движка ИИ
+pub struct EngineAgent {
+    engine: Box<dyn Engine + Send>,
+}
+
+impl EngineAgent {
+    /// Оборачивает движок в агента
+    pub fn new(engine: Box<dyn Engine + Send>) -> Self {
+        Self { engine }
+    }
+}
+
+impl Agent for EngineAgent {
+    fn name(&self) -> String {
+        self.engine.name()
+    }
+
+    fn choose_move(&mut self, game: &TicTacToe) -> Option<usize> {
+        self.engine.choose_move(game)
+    }
+}
+
+/// Решение ИИ по предложению ничьей
+///
+/// ИИ соглашается, только если сам уже не может собрать линию.
+///
+/// ## Возвращает
+/// `true`, если предложение есть и его стоит принять
+pub fn accepts_draw(game: &TicTacToe) -> bool {
+    game.draw_offer().is_some_and(|offerer| !game.can_win(offerer.opponent()))
+}
+
+// === ЗАПИСАННЫЕ ХОДЫ ===
+
+/// Агент, который играет заранее заданный список ходов
+#[derive(Clone, Debug)]
+pub struct ScriptedAgent {
+    moves: VecDeque<usize>,
+}
+
+impl ScriptedAgent {
+    /// Создает агента с указанным списком ходов
+    pub fn new(moves: Vec<usize>) -> Self {
+        Self { moves: moves.into() }
+    }
+}
+
+impl Agent for ScriptedAgent {
+    fn name(&self) -> String {
+        "Scripted".to_string()
+    }
+
+    fn choose_move(&mut self, _game: &TicTacToe) -> Option<usize> {
+        self.moves.pop_front()
+    }
+}
+
+// === УДАЛЕННЫЙ СОПЕРНИК ===
+
+/// Удаленный соперник, связанный с игрой через каналы
+///
+/// Ходы соперника приходят из `incoming`, а ходы локальной стороны
+/// отправляются в `outgoing`. Сетевой модуль сам решает,
+/// как доставлять их по сети.
+pub struct RemoteAgent {
+    name: String,
+    incoming: Receiver<usize>,
+    outgoing: Sender<usize>,
+}
+
+impl RemoteAgent {
+    /// Создает удаленного соперника на паре каналов
+    pub fn new(name: impl Into<String>, incoming: Receiver<usize>, outgoing: Sender<usize>) -> Self {
+        Self { name: name.into(), incoming, outgoing }
+    }
+}
+
+impl Agent for RemoteAgent {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn choose_move(&mut self, _game: &TicTacToe) -> Option<usize> {
+        self.incoming.recv().ok()
+    }
+
+    fn observe_move(&mut self, _game: &TicTacToe, position: usize) {
+        let _ = self.outgoing.send(position);
+    }
+}
+
+impl AsyncAgent for RemoteAgent {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn request_move(&mut self, _game: &TicTacToe) {}
+
+    fn poll_move(&mut self) -> MovePoll {
+        match self.incoming.try_recv() {
+            Ok(position) => MovePoll::Ready(position),
+            Err(TryRecvError::Empty) => MovePoll::Pending,
+            Err(TryRecvError::Disconnected) => MovePoll::Unavailable,
+        }
+    }
+
+    fn observe_move(&mut self, _game: &TicTacToe, position: usize) {
+        let _ = self.outgoing.send(position);
+    }
+}
+
+// === АДАПТЕРЫ ===
+
+/// Асинхронная обертка, которая считает ход сразу при запросе
+///
+/// Подходит для быстрых агентов и для веб-версии, где нет потоков.
+pub struct Blocking<A: Agent> {
+    agent: A,
+    ready: Option<MovePoll>,
+}
+
+impl<A: Agent> Blocking<A> {
+    /// Оборачивает синхронного агента
+    pub fn new(agent: A) -> Self {
+        Self { agent, ready: None }
+    }
+}
+
+impl<A: Agent> AsyncAgent for Blocking<A> {
+    fn name(&self) -> String {
+        self.agent.name()
+    }
+
+    fn request_move(&mut self, game: &TicTacToe) {
+        self.ready = Some(match self.agent.choose_move(game) {
+            Some(position) => MovePoll::Ready(position),
+            None => MovePoll::Unavailable,
+        });
+    }
+
+    fn poll_move(&mut self) -> MovePoll {
+        self.ready.take().unwrap_or(MovePoll::Pending)
+    }
+
+    fn observe_move(&mut self, game: &TicTacToe, position: usize) {
+        self.agent.observe_move(game, position);
+    }
+
+    fn new_game(&mut self, game: &TicTacToe) {
+        self.agent.new_game(game);
+    }
+}
+
+/// Асинхронная обертка, которая считает ход в отдельном потоке
+///
+/// Интерфейс не подвисает, пока движок думает.
+#[cfg(not(target_arch = "wasm32"))]
+pub struct Background<A: Agent + Send + 'static> {
+    name: String,
+    agent: Option<A>,
+    worker: Option<std::thread::JoinHandle<(A, Option<usize>)>>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<A: Agent + Send + 'static> Background<A> {
+    /// Оборачивает синхронного агента
+    pub fn new(agent: A) -> Self {
+        Self { name: agent.name(), agent: Some(agent), worker: None }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<A: Agent + Send + 'static> AsyncAgent for Background<A> {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn request_move(&mut self, game: &TicTacToe) {
+        // Агент занят предыдущим запросом - дождемся его
+        if let Some(worker) = self.worker.take() {
+            if let Ok((agent, _)) = worker.join() {
+                self.agent = Some(agent);
+            }
+        }
+        if let Some(mut agent) = self.agent.take() {
+            let game = game.clone();
+            self.worker = Some(std::thread::spawn(move || {
+                let position = agent.choose_move(&game);
+                (agent, position)
+            }));
+        }
+    }
+
+    fn poll_move(&mut self) -> MovePoll {
+        match &self.worker {
+            Some(worker) if !worker.is_finished() => MovePoll::Pending,
+            Some(_) => match self.worker.take().map(|worker| worker.join()) {
+                Some(Ok((agent, position))) => {
+                    self.agent = Some(agent);
+                    position.map_or(MovePoll::Unavailable, MovePoll::Ready)
+                }
+                _ => MovePoll::Unavailable,
+            },
+            None if self.agent.is_some() => MovePoll::Pending,
+            None => MovePoll::Unavailable,
+        }
+    }
+
+    fn observe_move(&mut self, game: &TicTacToe, position: usize) {
+        if let Some(agent) = &mut self.agent {
+            agent.observe_move(game, position);
+        }
+    }
+
+    fn new_game(&mut self, game: &TicTacToe) {
+        if let Some(agent) = &mut self.agent {
+            agent.new_game(game);
+        }
+    }
+}
+
+// === ВЫБОР АГЕНТА В ИНТЕРФЕЙСЕ ===
+
+/// Виды агентов, которые можно назначить стороне в интерфейсе
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AgentKind {
+    /// Локальный игрок
+    Human,
+    /// Движок с поиском по пространству угроз
+    #[serde(rename = "threat")]
+    ThreatSpace,
+    /// Агент MCTS
+    Mcts,
+}
+
+impl AgentKind {
+    /// Все виды агентов в порядке отображения
+    pub const ALL: [AgentKind; 3] = [AgentKind::Human, AgentKind::ThreatSpace, AgentKind::Mcts];
+
+    /// Ключ локализации для названия агента
+    pub fn text_key(&self) -> MessageKey {
+        match self {
+            AgentKind::Human => MessageKey::AgentHuman,
+            AgentKind::ThreatSpace => MessageKey::AgentThreat,
+            AgentKind::Mcts => MessageKey::AgentMcts,
+        }
+    }
+
+    /// Название агента в командной строке (обратно к [`AgentKind::parse`])
+    pub fn id(&self) -> &'static str {
+        match self {
+            AgentKind::Human => "human",
+            AgentKind::ThreatSpace => "threat",
+            AgentKind::Mcts => "mcts",
+        }
+    }
+
+    /// Разбирает название агента из командной строки
+    pub fn parse(name: &str) -> Option<AgentKind> {
+        match name {
+            "human" => Some(AgentKind::Human),
+            "threat" => Some(AgentKind::ThreatSpace),
+            "mcts" => Some(AgentKind::Mcts),
+            _ => None,
+        }
+    }
+
+    /// Создает синхронный движок ИИ этого вида
+    ///
+    /// Для локального игрока возвращает `None`: его ввод зависит от интерфейса.
+    pub fn engine(&self, rules: Rules) -> Option<Box<dyn Engine + Send>> {
+        self.engine_seeded(rules, MctsConfig::default().seed)
+    }
+
+    /// Создает движок ИИ с указанным зерном для случайных алгоритмов
+    ///
+    /// Поиск угроз настраивается под размер поля (см. [`engine::for_rules`]).
+    pub fn engine_seeded(&self, rules: Rules, seed: u64) -> Option<Box<dyn Engine + Send>> {
+        match self {
+            AgentKind::Human => None,
+            AgentKind::ThreatSpace => Some(engine::for_rules(rules)),
+            AgentKind::Mcts => Some(Box::new(MctsEngine::new(MctsConfig { seed, ..Default::default() }))),
+        }
+    }
+
+    /// Создает асинхронного агента этого вида для GUI и веб-версии
+    ///
+    /// В браузере нет потоков, и движок думает прямо в кадре интерфейса,
+    /// поэтому там его время на ход ограничено [`WEB_MOVE_TIME`].
+    pub fn create(&self, rules: Rules) -> Box<dyn AsyncAgent> {
+        match self.engine(rules) {
+            None => Box::new(HumanAgent::new()),
+            #[cfg(not(target_arch = "wasm32"))]
+            Some(engine) => Box::new(Background::new(EngineAgent::new(engine))),
+            #[cfg(target_arch = "wasm32")]
+            Some(mut engine) => {
+                engine.set_move_time(WEB_MOVE_TIME);
+                Box::new(Blocking::new(EngineAgent::new(engine)))
+            }
+        }
+    }
+}
+
+/// Две стороны партии, каждой из которых назначен свой агент
+///
+/// Каждый кадр интерфейс вызывает [`Seats::poll`], который запрашивает
+/// ход у агента текущей стороны и применяет его к игре.
+pub struct Seats {
+    /// Агенты сторон X и O
+    agents: [Box<dyn AsyncAgent>; 2],
+    /// Запрошен ли уже ход у текущего агента
+    requested: bool,
+}
+
+impl Seats {
+    /// Создает стороны с указанными агентами
+    pub fn new(x: Box<dyn AsyncAgent>, o: Box<dyn AsyncAgent>) -> Self {
+        Self { agents: [x, o], requested: false }
+    }
+
+    /// Возвращает агента стороны
+    pub fn agent(&self, player: Player) -> &dyn AsyncAgent {
+        self.agents[Self::index(player)].as_ref()
+    }
+
+    /// Заменяет агента стороны
+    pub fn set_agent(&mut self, player: Player, agent: Box<dyn AsyncAgent>) {
+        self.agents[Self::index(player)] = agent;
+        self.requested = false;
+    }
+
+    /// Меняет агентов сторон местами (в матче участники чередуют цвет)
+    pub fn swap(&mut self) {
+        self.agents.swap(0, 1);
+        self.requested = false;
+    }
+
+    /// Ждет ли текущая сторона ввода от локального пользователя
+    pub fn awaiting_input(&self, game: &TicTacToe) -> bool {
+        !game.is_game_over() && self.agent(game.current_player()).needs_input()
+    }
+
+    /// Передает клик локального пользователя агенту текущей стороны
+    ///
+    /// Клик по занятой клетке или вне поля не передается: иначе он
+    /// засчитался бы как недопустимый ход.
+    pub fn submit_input(&mut self, game: &TicTacToe, position: usize) {
+        if self.awaiting_input(game) && position < game.get_board().len() && game.cell(position).is_none() {
+            self.agents[Self::index(game.current_player())].submit_input(position);
+        }
+    }
+
+    /// Сообщает агентам о новой партии
+    pub fn new_game(&mut self, game: &TicTacToe) {
+        self.requested = false;
+        for agent in &mut self.agents {
+            agent.new_game(game);
+        }
+    }
+
+    /// Опрашивает агента текущей стороны и делает его ход
+    ///
+    /// Агент, приславший недопустимый ход, проигрывает по решению судьи,
+    /// а агент, который больше не будет ходить, считается покинувшим партию:
+    /// иначе его ход запрашивался бы бесконечно.
+    ///
+    /// ## Возвращает
+    /// - `Some(position)` - если ход был сделан
+    /// - `None` - если ход еще не готов или игра закончена
+    pub fn poll(&mut self, game: &mut TicTacToe) -> Option<usize> {
+        if game.is_game_over() {
+            return None;
+        }
+
+        let current = Self::index(game.current_player());
+        if !self.requested {
+            self.agents[current].request_move(game);
+            self.requested = true;
+        }
+
+        match self.agents[current].poll_move() {
+            MovePoll::Ready(position) => {
+                self.requested = false;
+                if !game.make_move(position) {
+                    game.adjudicate(Some(game.current_player().opponent()));
+                    return None;
+                }
+                self.agents[1 - current].observe_move(game, position);
+                Some(position)
+            }
+            MovePoll::Pending => None,
+            MovePoll::Unavailable => {
+                game.terminate(Termination::Abandonment(game.current_player()));
+                None
+            }
+        }
+    }
+
+    fn index(player: Player) -> usize {
+        match player {
+            Player::X => 0,
+            Player::O => 1,
+        }
+    }
+}
+
+// === ТЕСТЫ ===
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc::channel;
+
+    /// Тест: записанные ходы и удаленный соперник разыгрывают партию
+    #[test]
+    fn test_seats_play_scripted_against_remote() {
+        let (to_remote, from_local) = channel();
+        let (to_local, from_remote) = channel();
+        let mut seats = Seats::new(
+            Box::new(Blocking::new(ScriptedAgent::new(vec![0, 1, 2]))),
+            Box::new(RemoteAgent::new("peer", from_remote, to_remote)),
+        );
+        let mut game = TicTacToe::new();
+
+        assert_eq!(seats.poll(&mut game), Some(0));
+        assert_eq!(from_local.try_recv(), Ok(0));
+
+        // Удаленный соперник еще не ответил
+        assert_eq!(seats.poll(&mut game), None);
+        to_local.send(3).unwrap();
+        assert_eq!(seats.poll(&mut game), Some(3));
+
+        to_local.send(4).unwrap();
+        while !game.is_game_over() {
+            seats.poll(&mut game);
+        }
+        assert_eq!(game.get_winner(), Some(Player::X));
+    }
+
+    /// Тест: локальный игрок ходит только после клика
+    #[test]
+    fn test_human_waits_for_input() {
+        let mut seats = Seats::new(Box::new(HumanAgent::new()), Box::new(HumanAgent::new()));
+        let mut game = TicTacToe::new();
+
+        assert!(seats.awaiting_input(&game));
+        assert_eq!(seats.poll(&mut game), None);
+        seats.submit_input(&game, 4);
+        assert_eq!(seats.poll(&mut game), Some(4));
+        assert_eq!(game.current_player(), Player::O);
+    }
+
+    /// Тест: недопустимый ход или пропавший агент заканчивают партию, а не зацикливают ее
+    #[test]
+    fn test_seats_end_game_without_move() {
+        let mut seats = Seats::new(
+            Box::new(Blocking::new(ScriptedAgent::new(vec![4]))),
+            Box::new(Blocking::new(ScriptedAgent::new(vec![4]))),
+        );
+        let mut game = TicTacToe::new();
+        assert_eq!(seats.poll(&mut game), Some(4));
+        assert_eq!(seats.poll(&mut game), None);
+        assert_eq!(game.termination(), Some(Termination::Adjudication(Some(Player::X))));
+
+        let (to_remote, _from_local) = channel();
+        let (to_local, from_remote) = channel::<usize>();
+        drop(to_local);
+        let mut seats = Seats::new(
+            Box::new(RemoteAgent::new("peer", from_remote, to_remote)),
+            Box::new(HumanAgent::new()),
+        );
+        let mut game = TicTacToe::new();
+        assert_eq!(seats.poll(&mut game), None);
+        assert_eq!(game.termination(), Some(Termination::Abandonment(Player::X)));
+
+        // Клик по занятой клетке просто не засчитывается
+        let mut seats = Seats::new(Box::new(HumanAgent::new()), Box::new(HumanAgent::new()));
+        let mut game = TicTacToe::new();
+        seats.poll(&mut game);
+        seats.submit_input(&game, 4);
+        assert_eq!(seats.poll(&mut game), Some(4));
+        seats.submit_input(&game, 4);
+        assert_eq!(seats.poll(&mut game), None);
+        assert!(!game.is_game_over());
+    }
+
+    /// Тест: движок в отдельном потоке возвращает ход
+    #[test]
+    fn test_background_engine_agent() {
+        let mut seats = Seats::new(AgentKind::ThreatSpace.create(Rules::classic()), Box::new(HumanAgent::new()));
+        let mut game = TicTacToe::new();
+
+        let mut made = None;
+        while made.is_none() {
+            made = seats.poll(&mut game);
+        }
+        assert_eq!(game.moves().len(), 1);
+        assert!(seats.awaiting_input(&game));
+    }
+}
@@ -0,0 +1,957 @@
+//! # Общее приложение egui
+//!
+//! Настольная и веб-версия показывают одно и то же приложение [`App`]:
+//! поле, статус партии, часы, счет матча, выбор сторон и имен игроков,
+//! языка, контроля времени и формата матча. То, что зависит от платформы, -
+//! сетевая игра, игровой сервер, статистика, шрифты, заголовок окна, -
+//! подключается через оболочку [`Shell`].
+//!
+//! Состояние, общее для всех платформ, хранит [`Session`]. Оболочка
+//! получает его в каждом вызове и может, например, показывать в нем партию
+//! удаленного соперника. Поле рисует виджет [`crate::board::BoardWidget`],
+//! который можно встроить и в другие приложения egui.
+//!
+//! ## Использование
+//! ```rust,no_run
+//! use rust_tic_tac_toe::app::{App, Session, Shell};
+//! use rust_tic_tac_toe::{Language, Localization};
+//!
+//! /// Игра за одним устройством: без сети и статистики
+//! struct Offline;
+//!
+//! impl Shell for Offline {}
+//!
+//! let app = App::new(Session::new(Localization::new(Language::English)), Offline);
+//! eframe::run_native("Tic-Tac-Toe", eframe::NativeOptions::default(), Box::new(|_| Box::new(app)))?;
+//! # Ok::<(), eframe::Error>(())
+//! ```
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use eframe::egui;
+
+use crate::agent::{self, AgentKind, AsyncAgent, Seats};
+use crate::board::{BoardStyle, BoardWidget, Motion, MOTION_STORAGE_KEY};
+use crate::clock::{self, Clock, RealTime, TimeControl};
+use crate::i18n::LANGUAGE_STORAGE_KEY;
+use crate::online::ClientMessage;
+use crate::series::{Match, MatchFormat};
+use crate::theme::{GameColors, Themes, THEME_STORAGE_KEY};
+use crate::{bidi, Localization, MessageKey, Player, PlayerNames, Termination, TicTacToe};
+
+// === ОБОЛОЧКА ПЛАТФОРМЫ ===
+
+/// Удаленная партия глазами интерфейса
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Remote {
+    /// Имена сторон
+    pub names: PlayerNames,
+    /// Ждет ли партия хода локального игрока
+    pub local_turn: bool,
+    /// Остаток времени сторон X и O (`None` - партия без часов)
+    pub remaining: Option<[Duration; 2]>,
+    /// Сторона, чьи часы идут
+    pub running: Option<Player>,
+}
+
+/// Часть приложения, которая зависит от платформы
+///
+/// Все методы необязательны: оболочка без них дает игру за одним
+/// устройством с агентами и часами.
+pub trait Shell {
+    /// Обрабатывает события платформы в начале кадра: сеть, сервер
+    fn poll(&mut self, _session: &mut Session, _ctx: &egui::Context) {}
+
+    /// Удаленная партия, если она идет (своя или просматриваемая)
+    ///
+    /// Пока она есть, партию в [`Session`] ведет оболочка: агенты сторон и
+    /// часы локальной партии стоят.
+    fn remote(&self, _session: &Session) -> Option<Remote> {
+        None
+    }
+
+    /// Отправляет ход локального игрока в удаленную партию
+    fn send_move(&mut self, _session: &mut Session, _position: usize) {}
+
+    /// Отрисовывает панели платформы под настройками партии
+    fn draw_panels(&mut self, _ui: &mut egui::Ui, _session: &mut Session) {}
+
+    /// Отрисовывает элементы под кнопкой новой игры
+    fn draw_footer(&mut self, _ui: &mut egui::Ui, _session: &mut Session) {}
+
+    /// Партия закончилась; вызывается один раз на партию
+    fn game_finished(&mut self, _session: &Session) {}
+
+    /// Пользователь выбрал язык интерфейса
+    fn language_selected(&mut self, _locale: &str) {}
+
+    /// Показывает заголовок окна на выбранном языке
+    fn set_title(&mut self, ctx: &egui::Context, title: &str) {
+        ctx.send_viewport_cmd(egui::ViewportCommand::Title(title.to_string()));
+    }
+}
+
+// === СОСТОЯНИЕ ===
+
+/// Состояние интерфейса, общее для всех платформ
+pub struct Session {
+    /// Игровая логика
+    game: TicTacToe,
+    /// Размер одной клетки игрового поля в пикселях
+    cell_size: f32,
+    /// Темы оформления и выбранная тема
+    themes: Themes,
+    /// Настройки анимации поля
+    motion: Motion,
+    /// Локализация
+    localization: Localization,
+    /// Заголовок окна, который сейчас показан
+    window_title: String,
+    /// Кто играет за стороны X и O
+    sides: [AgentKind; 2],
+    /// Имена агентов, которых стороне назначила оболочка (внешние движки)
+    external: [Option<String>; 2],
+    /// Имена игроков сторон X и O
+    names: PlayerNames,
+    /// Агенты сторон
+    seats: Seats,
+    /// Имя игрока в лобби, онлайн-комнатах и статистике
+    player_name: String,
+    /// Контроль времени для новой партии (пусто - без часов)
+    time_control: String,
+    /// Часы локальной партии
+    clock: Option<Clock>,
+    /// Ошибка в контроле времени
+    clock_error: Option<String>,
+    /// Матч локальных участников
+    series: Match,
+    /// Формат матча (`bo3`, `ft2`, пусто - открытый матч)
+    match_format: String,
+    /// Ошибка в формате матча
+    match_error: Option<String>,
+    /// Участник матча, который сейчас играет за X
+    series_x: usize,
+    /// Засчитана ли текущая партия
+    game_counted: bool,
+}
+
+impl Session {
+    /// Создает состояние с новой партией, в которой обе стороны играют люди
+    ///
+    /// ## Параметры
+    /// - `localization`: язык интерфейса; строки языков справа налево
+    ///   выводятся в порядке отображения
+    pub fn new(mut localization: Localization) -> Self {
+        localization.set_visual_order(true);
+        let game = TicTacToe::new();
+        let sides = [AgentKind::Human, AgentKind::Human];
+        let player_name = localization.get_logical_text(MessageKey::ConsoleDefaultName);
+        let mut session = Self {
+            seats: Seats::new(sides[0].create(game.rules()), sides[1].create(game.rules())),
+            game,
+            cell_size: 80.0,
+            themes: Themes::new(),
+            motion: Motion::default(),
+            localization,
+            window_title: String::new(),
+            sides,
+            external: [None, None],
+            names: PlayerNames::default(),
+            player_name,
+            time_control: String::new(),
+            clock: None,
+            clock_error: None,
+            series: Match::new(MatchFormat::Open, "", ""),
+            match_format: String::new(),
+            match_error: None,
+            series_x: 0,
+            game_counted: false,
+        };
+        session.new_match(MatchFormat::Open);
+        session
+    }
+
+    /// Текущая партия
+    pub fn game(&self) -> &TicTacToe {
+        &self.game
+    }
+
+    /// Показывает партию, которую ведет оболочка (например, сетевую)
+    pub fn set_game(&mut self, game: TicTacToe) {
+        self.game = game;
+    }
+
+    /// Начинает локальную партию заново, не трогая матч (после выхода из удаленной)
+    pub fn reset_game(&mut self) {
+        self.game.reset();
+        self.seats.new_game(&self.game);
+    }
+
+    /// Локализация интерфейса
+    pub fn localization(&self) -> &Localization {
+        &self.localization
+    }
+
+    /// Локализация для выбора языка
+    pub fn localization_mut(&mut self) -> &mut Localization {
+        &mut self.localization
+    }
+
+    /// Восстанавливает язык, выбранный в прошлый раз
+    pub fn restore_language(&mut self, storage: Option<&dyn eframe::Storage>) {
+        if let Some(locale) = storage.and_then(|storage| storage.get_string(LANGUAGE_STORAGE_KEY)) {
+            self.localization.select_preferred([locale.as_str()]);
+        }
+    }
+
+    /// Восстанавливает тему и настройки анимации, выбранные в прошлый раз
+    pub fn restore_appearance(&mut self, storage: Option<&dyn eframe::Storage>) {
+        let Some(storage) = storage else { return };
+        if let Some(name) = storage.get_string(THEME_STORAGE_KEY) {
+            self.themes.select(&name);
+        }
+        if let Some(motion) = eframe::get_value(storage, MOTION_STORAGE_KEY) {
+            self.motion = motion;
+        }
+    }
+
+    /// Цветовая схема выбранной темы
+    pub fn colors(&self) -> &GameColors {
+        &self.themes.current().colors
+    }
+
+    /// Темы оформления
+    pub fn themes(&self) -> &Themes {
+        &self.themes
+    }
+
+    /// Темы для загрузки своих тем и выбора
+    pub fn themes_mut(&mut self) -> &mut Themes {
+        &mut self.themes
+    }
+
+    /// Настройки анимации поля
+    pub fn motion(&self) -> Motion {
+        self.motion
+    }
+
+    /// Настройки анимации для изменения
+    pub fn motion_mut(&mut self) -> &mut Motion {
+        &mut self.motion
+    }
+
+    /// Имена игроков локальной партии
+    pub fn names(&self) -> &PlayerNames {
+        &self.names
+    }
+
+    /// Имя игрока в лобби, онлайн-комнатах и статистике
+    pub fn player_name(&self) -> &str {
+        &self.player_name
+    }
+
+    /// Имя игрока для поля ввода
+    pub fn player_name_mut(&mut self) -> &mut String {
+        &mut self.player_name
+    }
+
+    /// Контроль времени из поля ввода (`None` - без часов)
+    pub fn time_control(&self) -> Result<Option<TimeControl>, String> {
+        match self.time_control.trim() {
+            "" => Ok(None),
+            text => text.parse().map(Some),
+        }
+    }
+
+    /// Кто играет за сторону
+    pub fn side(&self, player: Player) -> AgentKind {
+        self.sides[player.index()]
+    }
+
+    /// Имя агента, которого стороне назначила оболочка
+    pub fn external(&self, player: Player) -> Option<&str> {
+        self.external[player.index()].as_deref()
+    }
+
+    /// Назначает агентов для сторон X и O
+    pub fn set_sides(&mut self, x: AgentKind, o: AgentKind) {
+        self.sides = [x, o];
+        self.external = [None, None];
+        self.seats = Seats::new(x.create(self.game.rules()), o.create(self.game.rules()));
+        self.new_match(self.series.format());
+    }
+
+    /// Назначает стороне агента оболочки (например, внешний движок)
+    ///
+    /// ## Параметры
+    /// - `name`: имя агента в выборе сторон и в матче
+    pub fn set_external_agent(&mut self, player: Player, name: String, agent: Box<dyn AsyncAgent>) {
+        self.external[player.index()] = Some(name);
+        self.seats.set_agent(player, agent);
+        self.new_match(self.series.format());
+    }
+
+    /// Начинает новую локальную партию с часами по выбранному контролю времени
+    pub fn new_game(&mut self) {
+        self.match_error = None;
+        match self.match_format.parse() {
+            Ok(format) if format != self.series.format() || self.series.is_over() => self.new_match(format),
+            Ok(_) => {}
+            Err(e) => self.match_error = Some(e),
+        }
+        // Участники матча по очереди играют за X
+        if self.series.x_index() != self.series_x {
+            self.sides.swap(0, 1);
+            self.external.swap(0, 1);
+            self.seats.swap();
+            self.names.swap();
+            self.series_x = self.series.x_index();
+        }
+        self.reset_game();
+        self.clock = None;
+        self.clock_error = None;
+        match self.time_control() {
+            Ok(control) => self.clock = control.map(|control| Clock::new(control, Arc::new(RealTime::default()))),
+            Err(e) => self.clock_error = Some(e),
+        }
+    }
+
+    /// Начинает матч между агентами, которые сейчас играют за X и O
+    fn new_match(&mut self, format: MatchFormat) {
+        let mut names = [Player::X, Player::O].map(|player| {
+            match (self.external(player), self.side(player), self.names.name(player).trim()) {
+                (Some(name), _, _) => name.to_string(),
+                (None, AgentKind::Human, "") => self.player_name.clone(),
+                (None, AgentKind::Human, name) => name.to_string(),
+                (None, kind, _) => self.localization.get_text(kind.text_key()),
+            }
+        });
+        if names[0] == names[1] {
+            names = [format!("{} (X)", names[0]), format!("{} (O)", names[1])];
+        }
+        self.series = Match::new(format, &names[0], &names[1]);
+        self.series_x = 0;
+    }
+
+    /// Применяет действие к локальной партии; ИИ сразу отвечает на предложение ничьей
+    fn apply_local_action(&mut self, action: GameAction) {
+        let player = self.game.current_player();
+        action.apply(&mut self.game, player);
+        let opponent = player.opponent();
+        if self.game.draw_offer() == Some(player) && !self.seats.agent(opponent).needs_input() {
+            if agent::accepts_draw(&self.game) {
+                self.game.accept_draw(opponent);
+            } else {
+                self.game.decline_draw(opponent);
+            }
+        }
+    }
+
+    /// Имена сторон текущей партии: удаленной или локальной
+    fn display_names<'a>(&'a self, remote: Option<&'a Remote>) -> &'a PlayerNames {
+        remote.map_or(&self.names, |remote| &remote.names)
+    }
+
+    /// Отрисовывает статус игры
+    ///
+    /// Показывает текущего игрока или результат игры:
+    /// - "Ход игрока: Алиса (крестики)" - во время игры
+    /// - "Победитель: Алиса (крестики)!" - при победе
+    /// - "Ничья!" - при ничьей
+    fn draw_status(&self, ui: &mut egui::Ui, remote: Option<&Remote>) {
+        let names = self.display_names(remote);
+        ui.add_space(20.0);
+
+        self.draw_clocks(ui, remote);
+        self.draw_scoreboard(ui, remote.is_some());
+
+        if self.game.is_game_over() {
+            // Игра закончена - показываем причину и результат
+            if let Some(termination) = self.game.termination() {
+                ui.label(egui::RichText::new(termination.describe(&self.localization, names))
+                    .color(self.colors().text)
+                    .size(16.0));
+            }
+            match self.game.get_winner() {
+                _ if self.game.termination() == Some(Termination::Aborted) => {}
+                Some(player) => {
+                    let text = self.localization.format(MessageKey::Winner, &[("player", names.label(player, &self.localization).into())]);
+                    ui.heading(egui::RichText::new(text)
+                        .color(self.colors().highlight)
+                        .size(24.0));
+                }
+                None => {
+                    ui.heading(egui::RichText::new(self.localization.get_text(MessageKey::Draw))
+                        .color(self.colors().highlight)
+                        .size(24.0));
+                }
+            }
+        } else {
+            // Игра продолжается - показываем текущего игрока
+            let text = self.localization.format(MessageKey::CurrentPlayerTurn, &[("player", names.label(self.game.current_player(), &self.localization).into())]);
+            ui.heading(egui::RichText::new(text)
+                .color(self.colors().text)
+                .size(20.0));
+        }
+    }
+
+    /// Отрисовывает часы обеих сторон; идущие часы выделены
+    fn draw_clocks(&self, ui: &mut egui::Ui, remote: Option<&Remote>) {
+        let rtl = self.localization.is_rtl();
+        let (remaining, running) = match remote {
+            Some(remote) => (remote.remaining, remote.running),
+            None => (
+                self.clock.as_ref().map(|clock| [Player::X, Player::O].map(|player| clock.remaining(player))),
+                self.clock.as_ref().and_then(Clock::running),
+            ),
+        };
+        let Some(remaining) = remaining else { return };
+        let names = self.display_names(remote);
+        bidi::horizontal(ui, rtl, |ui| {
+            for (player, remaining) in [Player::X, Player::O].into_iter().zip(remaining) {
+                let text = format!("{} ⏱ {}", names.label(player, &self.localization), clock::format_remaining(remaining));
+                let color = if running == Some(player) { self.colors().highlight } else { self.colors().text };
+                ui.label(egui::RichText::new(text).color(color).size(18.0).monospace());
+            }
+        });
+    }
+
+    /// Отрисовывает счет матча и его итог
+    fn draw_scoreboard(&self, ui: &mut egui::Ui, remote: bool) {
+        if remote || (self.series.format() == MatchFormat::Open && self.series.games() == 0) {
+            return;
+        }
+        let score = self.localization.format(MessageKey::MatchScore, &[("score", self.series.to_string().into())]);
+        ui.label(egui::RichText::new(score).color(self.colors().text).size(16.0));
+        if self.series.is_over() {
+            let result = match self.series.winner() {
+                Some(index) => self.localization.format(MessageKey::MatchWinner, &[("name", self.series.players()[index].as_str().into())]),
+                None => self.localization.get_text(MessageKey::MatchDrawn),
+            };
+            ui.label(egui::RichText::new(result).color(self.colors().highlight).size(18.0));
+        }
+    }
+
+    /// Отрисовывает выбор агента и имени игрока для стороны
+    fn draw_side_selector(&mut self, ui: &mut egui::Ui, player: Player) {
+        let rtl = self.localization.is_rtl();
+        let index = player.index();
+        let mut kind = self.sides[index];
+        let mut changed = false;
+        let mut renamed = false;
+
+        bidi::horizontal(ui, rtl, |ui| {
+            ui.label(egui::RichText::new(self.localization.format(MessageKey::SideAgent, &[("player", player.mark(&self.localization).into())]))
+                .color(self.colors().text)
+                .size(16.0));
+
+            if kind == AgentKind::Human && self.external[index].is_none() {
+                let hint = self.localization.get_text(MessageKey::PlayerNamePlaceholder);
+                let edit = egui::TextEdit::singleline(self.names.name_mut(player)).hint_text(hint).desired_width(100.0);
+                renamed = ui.add(edit).lost_focus();
+            }
+
+            let selected = match &self.external[index] {
+                Some(name) => name.clone(),
+                None => self.localization.get_text(kind.text_key()),
+            };
+            egui::ComboBox::from_id_source(("side_agent", index))
+                .selected_text(selected)
+                .show_ui(ui, |ui| {
+                    for option in AgentKind::ALL {
+                        changed |= ui.selectable_value(&mut kind, option, self.localization.get_text(option.text_key())).clicked();
+                    }
+                });
+        });
+
+        if kind != self.sides[index] || (changed && self.external[index].is_some()) {
+            self.sides[index] = kind;
+            self.external[index] = None;
+            self.seats.set_agent(player, kind.create(self.game.rules()));
+            self.new_match(self.series.format());
+        } else if renamed && self.series.games() == 0 {
+            // Матч, в котором уже есть партии, сохраняет прежние имена
+            self.new_match(self.series.format());
+        }
+    }
+
+    /// Отрисовывает выбор темы и анимации; новая тема применяется со следующего кадра
+    fn draw_appearance(&mut self, ui: &mut egui::Ui) {
+        let rtl = self.localization.is_rtl();
+        bidi::horizontal(ui, rtl, |ui| {
+            ui.label(egui::RichText::new(self.localization.get_text(MessageKey::ThemeSwitch))
+                .color(self.colors().text)
+                .size(16.0));
+
+            let selected = self.themes.selected().to_string();
+            let names: Vec<String> = self.themes.names().map(str::to_string).collect();
+            egui::ComboBox::from_id_source("theme")
+                .selected_text(Themes::label(&selected, &self.localization))
+                .show_ui(ui, |ui| {
+                    for name in names {
+                        if ui.selectable_label(name == selected, Themes::label(&name, &self.localization)).clicked() {
+                            self.themes.select(&name);
+                        }
+                    }
+                });
+        });
+
+        bidi::horizontal(ui, rtl, |ui| {
+            ui.checkbox(&mut self.motion.reduce, self.localization.get_text(MessageKey::ReduceMotion));
+            ui.add_enabled(
+                !self.motion.reduce,
+                egui::Checkbox::new(&mut self.motion.celebration, self.localization.get_text(MessageKey::Celebration)),
+            );
+        });
+    }
+
+    /// Отрисовывает поля контроля времени и формата матча
+    fn draw_settings(&mut self, ui: &mut egui::Ui) {
+        let rtl = self.localization.is_rtl();
+
+        // Контроль времени для новой партии
+        bidi::horizontal(ui, rtl, |ui| {
+            ui.label(egui::RichText::new(self.localization.get_text(MessageKey::TimeControl))
+                .color(self.colors().text)
+                .size(16.0));
+            ui.add(egui::TextEdit::singleline(&mut self.time_control).desired_width(50.0));
+        });
+        if let Some(error) = &self.clock_error {
+            ui.label(egui::RichText::new(error).color(self.colors().x_color));
+        }
+
+        // Формат матча: применяется со следующей новой игры
+        bidi::horizontal(ui, rtl, |ui| {
+            ui.label(egui::RichText::new(self.localization.get_text(MessageKey::MatchFormat))
+                .color(self.colors().text)
+                .size(16.0));
+            ui.add(egui::TextEdit::singleline(&mut self.match_format).desired_width(50.0));
+        });
+        if let Some(error) = &self.match_error {
+            ui.label(egui::RichText::new(error).color(self.colors().x_color));
+        }
+    }
+}
+
+// === ПРИЛОЖЕНИЕ ===
+
+/// Приложение egui: общее состояние и оболочка платформы
+pub struct App<S: Shell> {
+    /// Состояние, общее для всех платформ
+    session: Session,
+    /// Оболочка платформы
+    shell: S,
+}
+
+impl<S: Shell> App<S> {
+    /// Создает приложение из состояния и оболочки
+    pub fn new(session: Session, shell: S) -> Self {
+        Self { session, shell }
+    }
+
+    /// Состояние, общее для всех платформ
+    pub fn session(&self) -> &Session {
+        &self.session
+    }
+
+    /// Состояние для изменения
+    pub fn session_mut(&mut self) -> &mut Session {
+        &mut self.session
+    }
+
+    /// Оболочка платформы
+    pub fn shell(&self) -> &S {
+        &self.shell
+    }
+
+    /// Оболочка для изменения
+    pub fn shell_mut(&mut self) -> &mut S {
+        &mut self.shell
+    }
+
+    /// Ведет партию между кадрами: ходы агентов, часы и счет матча
+    fn advance(&mut self, ctx: &egui::Context, remote: Option<&Remote>) {
+        let session = &mut self.session;
+        match remote {
+            None => {
+                // Спрашиваем ход у агента текущей стороны
+                session.seats.poll(&mut session.game);
+                // Часы локальной партии: переключаем после хода и проверяем время
+                if let Some(clock) = &mut session.clock {
+                    clock.tick(&mut session.game);
+                    if clock.running().is_some() {
+                        ctx.request_repaint_after(Duration::from_millis(100));
+                    }
+                }
+                if !session.game.is_game_over() && !session.seats.awaiting_input(&session.game) {
+                    // ИИ думает - перерисуем, когда ход может быть готов
+                    ctx.request_repaint_after(Duration::from_millis(50));
+                }
+            }
+            // Идущие часы перерисовываются, даже когда никто не ходит
+            Some(remote) if remote.running.is_some() => ctx.request_repaint_after(Duration::from_millis(100)),
+            Some(_) => {}
+        }
+
+        // Законченная партия засчитывается один раз
+        if session.game.moves().is_empty() && !session.game.is_game_over() {
+            session.game_counted = false;
+        }
+        if session.game.is_game_over() && !session.game_counted {
+            session.game_counted = true;
+            if remote.is_none() {
+                session.series.record(&session.game);
+            }
+            self.shell.game_finished(session);
+        }
+    }
+
+    /// Отрисовывает игровое поле
+    ///
+    /// Поле рисует [`BoardWidget`]; клетки нажимаются, только если сейчас
+    /// ходит локальный игрок. Ход передается агенту текущей стороны или
+    /// оболочке удаленной партии.
+    fn draw_board(&mut self, ui: &mut egui::Ui, remote: Option<&Remote>) {
+        let session = &mut self.session;
+        // Проверяем, ждет ли текущая сторона ввода
+        let awaiting = match remote {
+            Some(remote) => remote.local_turn,
+            None => session.seats.awaiting_input(&session.game),
+        };
+        let theme = session.themes.current();
+        let style = BoardStyle { cell_size: session.cell_size, colors: theme.colors, patterns: theme.patterns, ..Default::default() };
+        let board = BoardWidget::new(&session.game).style(style).motion(session.motion).read_only(!awaiting).show(ui);
+
+        if let Some(position) = board.clicked {
+            match remote {
+                Some(_) => self.shell.send_move(session, position),
+                None => session.seats.submit_input(&session.game, position),
+            }
+            ui.ctx().request_repaint();
+        }
+    }
+
+    /// Отрисовывает элементы управления
+    ///
+    /// Язык, тема и анимация, стороны и имена игроков, контроль времени и формат матча,
+    /// панели платформы, сдачу и ничью локальной партии и кнопку новой игры.
+    fn draw_controls(&mut self, ui: &mut egui::Ui, remote: Option<&Remote>) {
+        let rtl = self.session.localization.is_rtl();
+        ui.add_space(20.0);
+
+        // Переключатель языка: по кнопке на каждый загруженный каталог
+        let session = &mut self.session;
+        bidi::horizontal(ui, rtl, |ui| {
+            ui.label(egui::RichText::new(session.localization.get_text(MessageKey::LanguageSwitch))
+                .color(session.colors().text)
+                .size(16.0));
+
+            let locales: Vec<String> = session.localization.locales().map(str::to_string).collect();
+            for locale in locales {
+                let selected = session.localization.locale() == locale;
+                if ui.button(egui::RichText::new(session.localization.language_name(&locale))
+                    .color(if selected { session.colors().highlight } else { session.colors().text })
+                    .size(14.0))
+                    .clicked() {
+                    session.localization.set_locale(&locale);
+                    self.shell.language_selected(&locale);
+                }
+            }
+        });
+
+        self.session.draw_appearance(ui);
+
+        // Выбор агентов и имен для сторон локальной партии
+        if remote.is_none() {
+            for player in [Player::X, Player::O] {
+                self.session.draw_side_selector(ui, player);
+            }
+        }
+
+        self.session.draw_settings(ui);
+
+        // Панели платформы: сетевая игра, сервер, внешние движки
+        self.shell.draw_panels(ui, &mut self.session);
+
+        // Сдача, ничья и прерывание локальной партии от имени игрока, чей ход
+        if remote.is_none() {
+            let session = &mut self.session;
+            let player = session.seats.awaiting_input(&session.game).then(|| session.game.current_player());
+            if let Some(action) = game_action_buttons(ui, &session.localization, session.colors(), &session.names, &session.game, player) {
+                session.apply_local_action(action);
+            }
+        }
+
+        ui.add_space(10.0);
+
+        // Кнопка "Новая игра" (в удаленной партии новая партия - это реванш)
+        if ui.add_enabled(remote.is_none(), egui::Button::new(egui::RichText::new(self.session.localization.get_text(MessageKey::NewGame))
+            .color(self.session.colors().text)
+            .size(16.0)))
+            .clicked() {
+            self.session.new_game();
+        }
+
+        self.shell.draw_footer(ui, &mut self.session);
+    }
+}
+
+/// Главный цикл приложения
+impl<S: Shell> eframe::App for App<S> {
+    /// Сохраняет выбранные язык, тему и анимацию в хранилище eframe
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        storage.set_string(LANGUAGE_STORAGE_KEY, self.session.localization.locale().to_string());
+        storage.set_string(THEME_STORAGE_KEY, self.session.themes.selected().to_string());
+        eframe::set_value(storage, MOTION_STORAGE_KEY, &self.session.motion);
+    }
+
+    /// Основной метод обновления интерфейса
+    ///
+    /// Вызывается каждый кадр: опрашивает оболочку, ведет партию и рисует
+    /// центральную панель с игровым полем и элементами управления.
+    fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
+        // Тема "как в системе" следует за светлой или темной темой системы
+        if let Some(system) = frame.info().system_theme {
+            self.session.themes.set_system_dark(system == eframe::Theme::Dark);
+        }
+        // eframe сам переключает оформление вслед за системой - возвращаем выбранное
+        let visuals = self.session.themes.current().visuals();
+        if ctx.style().visuals != visuals {
+            ctx.set_visuals(visuals);
+        }
+        // Меньше движения - и у виджетов egui: панели и кнопки без плавных переходов
+        let animation_time = if self.session.motion.reduce { 0.0 } else { egui::Style::default().animation_time };
+        if ctx.style().animation_time != animation_time {
+            ctx.style_mut(|style| style.animation_time = animation_time);
+        }
+
+        self.shell.poll(&mut self.session, ctx);
+        let remote = self.shell.remote(&self.session);
+        self.advance(ctx, remote.as_ref());
+
+        // Заголовок окна следует за выбранным языком
+        let title = self.session.localization.get_logical_text(MessageKey::GameTitle);
+        if title != self.session.window_title {
+            self.shell.set_title(ctx, &title);
+            self.session.window_title = title;
+        }
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            // Центрируем все элементы интерфейса
+            ui.vertical_centered(|ui| {
+                // Заголовок игры
+                ui.heading(egui::RichText::new(self.session.localization.get_text(MessageKey::GameTitle))
+                    .color(self.session.colors().highlight)
+                    .size(28.0));
+
+                ui.add_space(20.0);
+
+                self.draw_board(ui, remote.as_ref());
+                self.session.draw_status(ui, remote.as_ref());
+                self.draw_controls(ui, remote.as_ref());
+            });
+        });
+    }
+}
+
+// === СДАЧА И НИЧЬЯ ===
+
+/// Действие игрока над партией, кроме хода
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum GameAction {
+    /// Сдаться
+    Resign,
+    /// Предложить ничью
+    OfferDraw,
+    /// Принять ничью
+    AcceptDraw,
+    /// Отклонить ничью
+    DeclineDraw,
+    /// Прервать партию
+    Abort,
+}
+
+impl GameAction {
+    /// Применяет действие к партии от имени игрока
+    pub fn apply(self, game: &mut TicTacToe, player: Player) -> bool {
+        match self {
+            GameAction::Resign => game.resign(player),
+            GameAction::OfferDraw => game.offer_draw(player),
+            GameAction::AcceptDraw => game.accept_draw(player),
+            GameAction::DeclineDraw => game.decline_draw(player),
+            GameAction::Abort => game.abort(player),
+        }
+    }
+
+    /// Сообщение игровому серверу
+    pub fn message(self) -> ClientMessage {
+        match self {
+            GameAction::Resign => ClientMessage::Resign,
+            GameAction::OfferDraw => ClientMessage::OfferDraw,
+            GameAction::AcceptDraw => ClientMessage::AcceptDraw,
+            GameAction::DeclineDraw => ClientMessage::DeclineDraw,
+            GameAction::Abort => ClientMessage::Abort,
+        }
+    }
+}
+
+/// Отрисовывает кнопки сдачи, ничьей и прерывания партии
+///
+/// ## Параметры
+/// - `names`: имена сторон для сообщения о предложении ничьей
+/// - `player`: от чьего имени действовать (`None` - кнопки не показываются)
+///
+/// ## Возвращает
+/// Нажатое действие
+pub fn game_action_buttons(
+    ui: &mut egui::Ui,
+    localization: &Localization,
+    colors: &GameColors,
+    names: &PlayerNames,
+    game: &TicTacToe,
+    player: Option<Player>,
+) -> Option<GameAction> {
+    let rtl = localization.is_rtl();
+    let player = player.filter(|_| !game.is_game_over())?;
+    let mut action = None;
+    if let Some(offerer) = game.draw_offer().filter(|&offerer| offerer != player) {
+        ui.label(egui::RichText::new(localization.format(MessageKey::DrawOffered, &[("player", names.label(offerer, localization).into())]))
+            .color(colors.highlight));
+    }
+    bidi::horizontal(ui, rtl, |ui| {
+        let mut button = |ui: &mut egui::Ui, enabled: bool, key: MessageKey, clicked: GameAction| {
+            if ui.add_enabled(enabled, egui::Button::new(localization.get_text(key))).clicked() {
+                action = Some(clicked);
+            }
+        };
+        if game.draw_offer() == Some(player.opponent()) {
+            button(ui, true, MessageKey::AcceptDraw, GameAction::AcceptDraw);
+            button(ui, true, MessageKey::DeclineDraw, GameAction::DeclineDraw);
+        } else {
+            button(ui, game.draw_offer().is_none(), MessageKey::OfferDraw, GameAction::OfferDraw);
+        }
+        button(ui, true, MessageKey::Resign, GameAction::Resign);
+        if game.can_abort(player) {
+            button(ui, true, MessageKey::Abort, GameAction::Abort);
+        }
+    });
+    action
+}
+
+// === ТЕСТЫ ===
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Language;
+
+    /// Оболочка, которая считает законченные партии
+    #[derive(Default)]
+    struct Counting {
+        finished: usize,
+        remote: Option<Remote>,
+    }
+
+    impl Shell for Counting {
+        fn remote(&self, _session: &Session) -> Option<Remote> {
+            self.remote.clone()
+        }
+
+        fn game_finished(&mut self, _session: &Session) {
+            self.finished += 1;
+        }
+    }
+
+    /// Хранилище eframe в памяти
+    #[derive(Default)]
+    struct MemoryStorage(std::collections::HashMap<String, String>);
+
+    impl eframe::Storage for MemoryStorage {
+        fn get_string(&self, key: &str) -> Option<String> {
+            self.0.get(key).cloned()
+        }
+
+        fn set_string(&mut self, key: &str, value: String) {
+            self.0.insert(key.to_string(), value);
+        }
+
+        fn flush(&mut self) {}
+    }
+
+    /// Играет за X три клетки верхнего ряда
+    fn win_for_x(session: &mut Session) {
+        for position in [0, 3, 1, 4, 2] {
+            session.game.make_move(position);
+        }
+    }
+
+    /// Тест: законченная локальная партия засчитывается в матч и оболочке один раз
+    #[test]
+    fn test_game_counted_once() {
+        let mut app = App::new(Session::new(Localization::new(Language::English)), Counting::default());
+        let ctx = egui::Context::default();
+        win_for_x(app.session_mut());
+        app.advance(&ctx, None);
+        app.advance(&ctx, None);
+        assert_eq!(app.shell().finished, 1);
+        assert_eq!(app.session().series.games(), 1);
+
+        // Удаленная партия не идет в счет локального матча
+        app.session_mut().reset_game();
+        app.advance(&ctx, None);
+        win_for_x(app.session_mut());
+        let remote = Remote::default();
+        app.advance(&ctx, Some(&remote));
+        assert_eq!(app.shell().finished, 2);
+        assert_eq!(app.session().series.games(), 1);
+    }
+
+    /// Тест: в матче участники вместе с именами меняются цветом
+    #[test]
+    fn test_new_game_swaps_sides() {
+        let mut session = Session::new(Localization::new(Language::English));
+        session.names.set(Player::X, "Alice");
+        session.set_sides(AgentKind::Human, AgentKind::Mcts);
+        assert_eq!(session.series.players()[0], "Alice");
+        assert_eq!(session.series.players()[1], "AI: Monte Carlo");
+
+        win_for_x(&mut session);
+        session.series.record(&session.game);
+        session.new_game();
+        assert_eq!(session.side(Player::X), AgentKind::Mcts);
+        assert_eq!(session.names().name(Player::O), "Alice");
+        assert!(session.game().moves().is_empty());
+    }
+
+    /// Тест: имена удаленной партии заменяют имена локальной
+    #[test]
+    fn test_display_names() {
+        let mut session = Session::new(Localization::new(Language::English));
+        session.names.set(Player::X, "Alice");
+        let remote = Remote { names: PlayerNames::new("Bob", "Carol"), ..Remote::default() };
+        assert_eq!(session.display_names(None).label(Player::X, &session.localization), "Alice (X)");
+        assert_eq!(session.display_names(Some(&remote)).label(Player::O, &session.localization), "Carol (O)");
+        assert_eq!(session.player_name(), "Player");
+        assert_eq!(Session::new(Localization::new(Language::Russian)).player_name(), "Игрок");
+        session.time_control = "5+3".to_string();
+        assert!(session.time_control().unwrap().is_some());
+        session.time_control = "abc".to_string();
+        assert!(session.time_control().is_err());
+    }
+
+    /// Тест: тема и настройки анимации переживают перезапуск
+    #[test]
+    fn test_appearance_saved_and_restored() {
+        let mut app = App::new(Session::new(Localization::new(Language::English)), Counting::default());
+        assert!(app.session_mut().themes_mut().select("high-contrast"));
+        app.session_mut().motion = Motion { reduce: true, celebration: false };
+        let mut storage = MemoryStorage::default();
+        eframe::App::save(&mut app, &mut storage);
+
+        let mut session = Session::new(Localization::new(Language::English));
+        session.restore_appearance(Some(&storage));
+        assert_eq!(session.themes().selected(), "high-contrast");
+        assert_eq!(session.motion, Motion { reduce: true, celebration: false });
+    }
+}
@@ -0,0 +1,231 @@
+//! # Журнал партии
+//!
+//! Сервер ведет для каждой комнаты журнал событий: кто и когда сел за партию,
+//! отключился или вернулся, какие ходы приняты и какие сообщения отклонены
+//! (с причиной), а также предложения ничьей, сдачу и другие причины окончания.
+//! Журнал рейтинговой партии сохраняется вместе с ее записью, поэтому спорный
+//! результат можно проверить позже.
+//!
+//! [`AuditLog::verify`] сверяет журнал с записью партии и повторно проигрывает
+//! ходы через [`GameRecord::verify`].
+//!
+//! ## Использование
+//! ```rust
+//! use rust_tic_tac_toe::{Player, Rules, TicTacToe};
+//! use rust_tic_tac_toe::audit::{AuditEvent, AuditLog};
+//! use rust_tic_tac_toe::record::GameRecord;
+//!
+//! let mut log = AuditLog::new("ABCDE");
+//! log.push(Some(1), AuditEvent::Move { player: Player::X, seq: 0, position: 4 });
+//!
+//! let mut game = TicTacToe::with_rules(Rules::classic());
+//! game.make_move(4);
+//! assert!(log.verify(&GameRecord::from_game(&game, "Alice", "Bob")).is_empty());
+//! ```
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::online::TimeControl;
+use crate::record::{GameRecord, Inconsistency};
+use crate::server::ConnectionId;
+use crate::{Player, Rules, Termination};
+
+/// Событие в комнате
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum AuditEvent {
+    /// Комната создана
+    Created {
+        /// Правила партии
+        rules: Rules,
+        /// Контроль времени
+        time_control: Option<TimeControl>,
+        /// Рейтинговая ли партия
+        rated: bool,
+    },
+    /// Игрок занял место
+    Seated {
+        /// Сторона
+        player: Player,
+        /// Имя игрока
+        name: String,
+    },
+    /// Игрок вернулся в партию по ключу
+    Rejoined {
+        /// Сторона
+        player: Player,
+    },
+    /// Игрок отключился
+    Disconnected {
+        /// Сторона
+        player: Player,
+    },
+    /// Ход принят
+    Move {
+        /// Сторона
+        player: Player,
+        /// Номер хода в партии
+        seq: usize,
+        /// Клетка
+        position: usize,
+    },
+    /// Ход отклонен
+    Rejected {
+        /// Сторона, от имени которой пришел ход
+        player: Player,
+        /// Номер хода из сообщения
+        seq: usize,
+        /// Клетка из сообщения
+        position: usize,
+        /// Причина
+        reason: String,
+    },
+    /// Игрок предложил ничью
+    DrawOffered {
+        /// Сторона
+        player: Player,
+    },
+    /// Игрок отклонил предложение ничьей
+    DrawDeclined {
+        /// Сторона
+        player: Player,
+    },
+    /// Партия завершена не ходом: сдача, время, соглашение, уход или прерывание
+    Terminated {
+        /// Причина
+        termination: Termination,
+    },
+    /// Партия закончена
+    Finished {
+        /// Победитель (`None` - ничья)
+        winner: Option<Player>,
+    },
+}
+
+/// Запись журнала
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Время события в миллисекундах от начала эпохи Unix
+    pub time_ms: u64,
+    /// Подключение, от которого пришло сообщение (`None` - событие сервера)
+    pub connection: Option<ConnectionId>,
+    /// Событие
+    pub event: AuditEvent,
+}
+
+/// Журнал событий одной комнаты
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AuditLog {
+    /// Код комнаты
+    pub code: String,
+    /// События в порядке их появления
+    pub entries: Vec<AuditEntry>,
+}
+
+impl AuditLog {
+    /// Создает пустой журнал комнаты
+    pub fn new(code: &str) -> Self {
+        Self { code: code.to_string(), entries: Vec::new() }
+    }
+
+    /// Добавляет событие с текущим временем
+    pub fn push(&mut self, connection: Option<ConnectionId>, event: AuditEvent) {
+        let time_ms = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |time| time.as_millis() as u64);
+        self.entries.push(AuditEntry { time_ms, connection, event });
+    }
+
+    /// Принятые ходы в порядке их совершения
+    pub fn moves(&self) -> Vec<usize> {
+        self.entries
+            .iter()
+            .filter_map(|entry| match entry.event {
+                AuditEvent::Move { position, .. } => Some(position),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Число отклоненных ходов
+    pub fn rejected_count(&self) -> usize {
+        self.entries.iter().filter(|entry| matches!(entry.event, AuditEvent::Rejected { .. })).count()
+    }
+
+    /// Сверяет журнал с записью партии
+    ///
+    /// Ходы журнала должны идти подряд по номерам, чередовать стороны и
+    /// совпадать с ходами записи; сама запись проверяется повторным проигрыванием.
+    ///
+    /// ## Возвращает
+    /// Список несоответствий (пустой - журнал и запись согласованы)
+    pub fn verify(&self, record: &GameRecord) -> Vec<Inconsistency> {
+        let mut problems = record.verify();
+        let logged: Vec<(Player, usize, usize)> = self
+            .entries
+            .iter()
+            .filter_map(|entry| match entry.event {
+                AuditEvent::Move { player, seq, position } => Some((player, seq, position)),
+                _ => None,
+            })
+            .collect();
+
+        for index in 0..logged.len().max(record.moves.len()) {
+            let entry = logged.get(index);
+            let recorded = record.moves.get(index).copied();
+            let expected_player = if index.is_multiple_of(2) { Player::X } else { Player::O };
+            let consistent = entry.is_some_and(|&(player, seq, position)| {
+                player == expected_player && seq == index && Some(position) == recorded
+            });
+            if !consistent {
+                problems.push(Inconsistency::LogMismatch { index, logged: entry.map(|entry| entry.2), recorded });
+            }
+        }
+        problems
+    }
+}
+
+// === ТЕСТЫ ===
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log_of(moves: &[usize]) -> AuditLog {
+        let mut log = AuditLog::new("ABCDE");
+        for (seq, &position) in moves.iter().enumerate() {
+            let player = if seq.is_multiple_of(2) { Player::X } else { Player::O };
+            log.push(Some(1), AuditEvent::Move { player, seq, position });
+        }
+        log
+    }
+
+    fn record(moves: Vec<usize>, winner: Option<Player>) -> GameRecord {
+        GameRecord { rules: Rules::classic(), x: "A".into(), o: "B".into(), moves, winner, termination: None }
+    }
+
+    /// Тест: журнал согласован с записью партии
+    #[test]
+    fn test_log_matches_record() {
+        let mut log = log_of(&[0, 3, 1, 4, 2]);
+        log.push(Some(2), AuditEvent::Rejected { player: Player::O, seq: 4, position: 5, reason: "партия закончена".into() });
+        log.push(None, AuditEvent::Finished { winner: Some(Player::X) });
+        assert_eq!(log.moves(), vec![0, 3, 1, 4, 2]);
+        assert_eq!(log.rejected_count(), 1);
+        assert!(log.verify(&record(vec![0, 3, 1, 4, 2], Some(Player::X))).is_empty());
+    }
+
+    /// Тест: подмена хода в записи и лишний ход в журнале
+    #[test]
+    fn test_log_mismatch() {
+        let log = log_of(&[0, 3, 1]);
+        assert_eq!(
+            log.verify(&record(vec![0, 4, 1], None)),
+            vec![Inconsistency::LogMismatch { index: 1, logged: Some(3), recorded: Some(4) }]
+        );
+        assert_eq!(
+            log.verify(&record(vec![0, 3], None)),
+            vec![Inconsistency::LogMismatch { index: 2, logged: Some(1), recorded: None }]
+        );
+    }
+}
@@ -0,0 +1,254 @@
+//! # Текст справа налево
+//!
+//! egui рисует строку слева направо символ за символом: он не переставляет
+//! арабские и еврейские слова и не соединяет арабские буквы. Модуль готовит
+//! такие строки к выводу:
+//! - [`shape_arabic`] заменяет арабские буквы их формами в слове (отдельной,
+//!   начальной, срединной, конечной) из блока Arabic Presentation Forms-B;
+//! - [`visual_order`] переставляет символы в порядок отображения по
+//!   алгоритму Unicode Bidirectional (UAX #9) и зеркалит скобки.
+//!
+//! Для языков справа налево ([`is_rtl_locale`]) ряды элементов управления
+//! выстраиваются от правого края ([`horizontal`]).
+//!
+//! ## Использование
+//! ```rust
+//! use rust_tic_tac_toe::bidi;
+//!
+//! assert!(bidi::is_rtl_locale("ar-EG"));
+//! assert!(!bidi::is_rtl_locale("en"));
+//! // Лам и алиф соединяются в лигатуру
+//! assert_eq!(bidi::shape_arabic("لا"), "\u{FEFB}");
+//! // В строке справа налево латиница остается словом, а слова идут справа
+//! assert_eq!(bidi::visual_order("X (\u{FEFB})"), "(\u{FEFB}) X");
+//! ```
+
+use unicode_bidi::{BidiInfo, Level};
+
+use eframe::egui;
+
+/// Языки, которые пишутся справа налево
+const RTL_LANGUAGES: [&str; 6] = ["ar", "fa", "he", "ps", "ur", "yi"];
+
+/// Пишется ли язык справа налево
+///
+/// ## Параметры
+/// - `locale`: код языка, возможно с регионом (`ar`, `ar-EG`, `he_IL`)
+pub fn is_rtl_locale(locale: &str) -> bool {
+    let language = locale.split(['-', '_']).next().unwrap_or_default();
+    RTL_LANGUAGES.contains(&language)
+}
+
+// === АРАБСКИЕ БУКВЫ ===
+
+/// Арабская буква, ее первая форма в Presentation Forms-B и число форм:
+/// 4 - соединяется с обеих сторон, 2 - только с предыдущей буквой, 1 - ни с чем
+const ARABIC_FORMS: [(char, u32, u32); 36] = [
+    ('\u{0621}', 0xFE80, 1), // ء
+    ('\u{0622}', 0xFE81, 2), // آ
+    ('\u{0623}', 0xFE83, 2), // أ
+    ('\u{0624}', 0xFE85, 2), // ؤ
+    ('\u{0625}', 0xFE87, 2), // إ
+    ('\u{0626}', 0xFE89, 4), // ئ
+    ('\u{0627}', 0xFE8D, 2), // ا
+    ('\u{0628}', 0xFE8F, 4), // ب
+    ('\u{0629}', 0xFE93, 2), // ة
+    ('\u{062A}', 0xFE95, 4), // ت
+    ('\u{062B}', 0xFE99, 4), // ث
+    ('\u{062C}', 0xFE9D, 4), // ج
+    ('\u{062D}', 0xFEA1, 4), // ح
+    ('\u{062E}', 0xFEA5, 4), // خ
+    ('\u{062F}', 0xFEA9, 2), // د
+    ('\u{0630}', 0xFEAB, 2), // ذ
+    ('\u{0631}', 0xFEAD, 2), // ر
+    ('\u{0632}', 0xFEAF, 2), // ز
+    ('\u{0633}', 0xFEB1, 4), // س
+    ('\u{0634}', 0xFEB5, 4), // ش
+    ('\u{0635}', 0xFEB9, 4), // ص
+    ('\u{0636}', 0xFEBD, 4), // ض
+    ('\u{0637}', 0xFEC1, 4), // ط
+    ('\u{0638}', 0xFEC5, 4), // ظ
+    ('\u{0639}', 0xFEC9, 4), // ع
+    ('\u{063A}', 0xFECD, 4), // غ
+    ('\u{0641}', 0xFED1, 4), // ف
+    ('\u{0642}', 0xFED5, 4), // ق
+    ('\u{0643}', 0xFED9, 4), // ك
+    ('\u{0644}', 0xFEDD, 4), // ل
+    ('\u{0645}', 0xFEE1, 4), // م
+    ('\u{0646}', 0xFEE5, 4), // ن
+    ('\u{0647}', 0xFEE9, 4), // ه
+    ('\u{0648}', 0xFEED, 2), // و
+    ('\u{0649}', 0xFEEF, 2), // ى
+    ('\u{064A}', 0xFEF1, 4), // ي
+];
+
+/// Лигатуры лам-алиф: алиф и отдельная форма лигатуры (конечная - следующая)
+const LAM_ALEF: [(char, u32); 4] = [('\u{0622}', 0xFEF5), ('\u{0623}', 0xFEF7), ('\u{0625}', 0xFEF9), ('\u{0627}', 0xFEFB)];
+
+/// Лам
+const LAM: char = '\u{0644}';
+
+/// Татвиль: соединительная черта, соединяется с обеих сторон
+const TATWEEL: char = '\u{0640}';
+
+/// Первая форма и число форм буквы (`None` - не арабская буква)
+fn arabic_forms(c: char) -> Option<(u32, u32)> {
+    ARABIC_FORMS.iter().find(|(letter, _, _)| *letter == c).map(|&(_, first, count)| (first, count))
+}
+
+/// Огласовки не влияют на соединение букв
+fn is_transparent(c: char) -> bool {
+    matches!(c, '\u{064B}'..='\u{065F}' | '\u{0670}')
+}
+
+/// Соединяется ли буква со следующей
+fn joins_next(c: char) -> bool {
+    c == TATWEEL || arabic_forms(c).is_some_and(|(_, count)| count == 4)
+}
+
+/// Соединяется ли буква с предыдущей
+fn joins_previous(c: char) -> bool {
+    c == TATWEEL || arabic_forms(c).is_some_and(|(_, count)| count > 1)
+}
+
+/// Заменяет арабские буквы их формами в слове
+///
+/// Остальные символы не меняются. Результат - по-прежнему логический
+/// порядок; для вывода его нужно переставить через [`visual_order`].
+pub fn shape_arabic(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut shaped = String::with_capacity(text.len());
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let previous = chars[..i].iter().rev().copied().find(|&c| !is_transparent(c));
+        let next = chars[i + 1..].iter().copied().find(|&c| !is_transparent(c));
+        let joined_before = previous.is_some_and(joins_next);
+        if c == LAM {
+            if let Some(&(_, ligature)) = LAM_ALEF.iter().find(|(alef, _)| chars.get(i + 1) == Some(alef)) {
+                shaped.push(char::from_u32(ligature + u32::from(joined_before)).unwrap_or(c));
+                i += 2;
+                continue;
+            }
+        }
+        match arabic_forms(c) {
+            Some((first, count)) => {
+                let joined_after = count == 4 && next.is_some_and(joins_previous);
+                let form = match (joined_before && count > 1, joined_after) {
+                    (false, false) => 0,
+                    (true, false) => 1,
+                    (false, true) => 2,
+                    (true, true) => 3,
+                };
+                shaped.push(char::from_u32(first + form).unwrap_or(c));
+            }
+            None => shaped.push(c),
+        }
+        i += 1;
+    }
+    shaped
+}
+
+// === ПОРЯДОК ОТОБРАЖЕНИЯ ===
+
+/// Готовит строку интерфейса справа налево к выводу слева направо
+///
+/// Каждая строка текста считается абзацем с направлением справа налево:
+/// арабские буквы соединяются, отрезки справа налево переворачиваются, а
+/// скобки в них зеркалятся. Вставки слева направо (числа, латиница, `X`)
+/// читаются как обычно.
+///
+/// ## Пример
+/// ```rust
+/// use rust_tic_tac_toe::bidi::visual_order;
+///
+/// assert_eq!(visual_order("Draw!"), "!Draw");
+/// ```
+pub fn visual_order(text: &str) -> String {
+    shape_arabic(text).split('\n').map(reorder_line).collect::<Vec<_>>().join("\n")
+}
+
+/// Переставляет одну строку текста в порядок отображения
+fn reorder_line(line: &str) -> String {
+    let info = BidiInfo::new(line, Some(Level::rtl()));
+    let Some(paragraph) = info.paragraphs.first() else { return line.to_string() };
+    let (levels, runs) = info.visual_runs(paragraph, paragraph.range.clone());
+    runs.into_iter()
+        .map(|run| {
+            let text = &line[run.clone()];
+            if levels[run.start].is_rtl() {
+                text.chars().rev().map(mirror).collect()
+            } else {
+                text.to_string()
+            }
+        })
+        .collect()
+}
+
+/// Парный символ для отрезка справа налево
+fn mirror(c: char) -> char {
+    match c {
+        '(' => ')',
+        ')' => '(',
+        '[' => ']',
+        ']' => '[',
+        '{' => '}',
+        '}' => '{',
+        '<' => '>',
+        '>' => '<',
+        '«' => '»',
+        '»' => '«',
+        c => c,
+    }
+}
+
+// === РАСКЛАДКА ===
+
+/// Ряд элементов: слева направо или, для языков справа налево, от правого края
+///
+/// ## Параметры
+/// - `rtl`: выстраивать элементы справа налево
+/// - `add_contents`: добавляет элементы ряда в порядке чтения
+pub fn horizontal<R>(
+    ui: &mut egui::Ui,
+    rtl: bool,
+    add_contents: impl FnOnce(&mut egui::Ui) -> R,
+) -> egui::InnerResponse<R> {
+    if rtl {
+        let size = egui::vec2(ui.available_size_before_wrap().x, ui.spacing().interact_size.y);
+        ui.allocate_ui_with_layout(size, egui::Layout::right_to_left(egui::Align::Center), add_contents)
+    } else {
+        ui.horizontal(add_contents)
+    }
+}
+
+// === ТЕСТЫ ===
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Тест: формы букв зависят от соседей
+    #[test]
+    fn test_shape_arabic() {
+        // با: бэ в начальной форме, алиф в конечной
+        assert_eq!(shape_arabic("با"), "\u{FE91}\u{FE8E}");
+        // دب: дал не соединяется со следующей буквой, поэтому бэ отдельная
+        assert_eq!(shape_arabic("دب"), "\u{FEA9}\u{FE8F}");
+        // بلا: лигатура лам-алиф в конечной форме после бэ
+        assert_eq!(shape_arabic("بلا"), "\u{FE91}\u{FEFC}");
+        // Огласовка между буквами не разрывает соединение
+        assert_eq!(shape_arabic("بَب"), "\u{FE91}\u{064E}\u{FE90}");
+        assert_eq!(shape_arabic("Tic-Tac-Toe"), "Tic-Tac-Toe");
+    }
+
+    /// Тест: слова справа налево, числа и латиница внутри них
+    #[test]
+    fn test_visual_order() {
+        let hebrew = "\u{05D0}\u{05D1} 12 X";
+        assert_eq!(visual_order(hebrew), "X 12 \u{05D1}\u{05D0}");
+        assert_eq!(visual_order("\u{05D0} (\u{05D1})"), "(\u{05D1}) \u{05D0}");
+        assert_eq!(visual_order("\u{05D0}\n\u{05D1}\u{05D2}"), "\u{05D0}\n\u{05D2}\u{05D1}");
+        assert_eq!(visual_order(""), "");
+    }
+}
@@ -0,0 +1,53 @@
+//! # Игровой сервер "Крестики-нолики"
+//! 
+//! Принимает WebSocket-подключения браузерных клиентов и проводит партии
+//! в комнатах с кодами (см. модуль `server`).
+//! 
+//! ## Запуск
+//! ```bash
+//! cargo run --release --bin server -- --addr 0.0.0.0:9001 --store lobby.json
+//! ```
+//! 
+//! С `--store` рейтинги и партии из лобби сохраняются в JSON-файл,
+//! без него - только в памяти.
+
+use std::env;
+use std::net::TcpListener;
+use rust_tic_tac_toe::lobby::JsonFileStore;
+use rust_tic_tac_toe::online::DEFAULT_PORT;
+use rust_tic_tac_toe::server::{self, GameServer};
+
+/// Запускает сервер на адресе из `--addr` (по умолчанию на всех интерфейсах)
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let mut addr = format!("0.0.0.0:{}", DEFAULT_PORT);
+    let mut store = None;
+    
+    let mut iter = args.iter().skip(1);
+    while let Some(arg) = iter.next() {
+        match (arg.as_str(), iter.next()) {
+            ("--addr", Some(value)) => addr = value.clone(),
+            ("--store", Some(value)) => store = Some(value.clone()),
+            _ => {
+                eprintln!("Использование: server [--addr host:port] [--store файл.json]");
+                return;
+            }
+        }
+    }
+    
+    let mut game_server = GameServer::new();
+    if let Some(path) = store {
+        match JsonFileStore::open(&path) {
+            Ok(store) => game_server = game_server.with_store(Box::new(store)),
+            Err(e) => {
+                eprintln!("Не удалось открыть хранилище {}: {}", path, e);
+                return;
+            }
+        }
+    }
+    
+    println!("Игровой сервер слушает {}", addr);
+    if let Err(e) = TcpListener::bind(&addr).and_then(|listener| server::serve(listener, game_server)) {
+        eprintln!("Ошибка сервера: {}", e);
+    }
+}
@@ -0,0 +1,575 @@
+//! # Виджет игрового поля
+//!
+//! [`BoardWidget`] рисует поле любой партии [`TicTacToe`] в чужом интерфейсе
+//! egui: панели анализа, дашборды, просмотр записей. Виджет ничего не меняет
+//! в партии - он только сообщает, какую клетку нажали или над какой держат
+//! курсор, а ход делает вызывающий код.
+//!
+//! Кроме символов, виджет умеет показывать:
+//! - подсветку клеток (последний ход, выигрышная линия, лучший ход);
+//! - стрелки между клетками (план, угроза);
+//! - подписи в углу клетки (оценка, номер хода).
+//!
+//! Новые символы дорисовываются за долю секунды, последний ход коротко
+//! пульсирует, а выигрышная линия зачеркивается; по желанию победу
+//! отмечает салют. Время анимации берется из ввода egui, настройки - в
+//! [`Motion`]: с `reduce` все рисуется сразу и без движения.
+//!
+//! ## Пример
+//! ```rust,no_run
+//! use eframe::egui;
+//! use rust_tic_tac_toe::TicTacToe;
+//! use rust_tic_tac_toe::board::BoardWidget;
+//!
+//! fn analysis_panel(ui: &mut egui::Ui, game: &mut TicTacToe) {
+//!     let board = BoardWidget::new(game)
+//!         .highlight(4, egui::Color32::from_rgba_unmultiplied(255, 165, 0, 60))
+//!         .arrow(0, 8, egui::Color32::LIGHT_BLUE)
+//!         .annotate(2, "+1")
+//!         .show(ui);
+//!     if let Some(position) = board.clicked {
+//!         game.make_move(position);
+//!     }
+//! }
+//! ```
+
+use std::f32::consts::TAU;
+
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+
+use crate::theme::GameColors;
+use crate::{Player, TicTacToe};
+
+// === СТИЛЬ ===
+
+/// Размеры и цвета игрового поля
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct BoardStyle {
+    /// Наибольший размер клетки в пикселях; на узкой панели клетки меньше
+    pub cell_size: f32,
+    /// Толщина линий сетки
+    pub grid_width: f32,
+    /// Толщина линий символов X и O
+    pub mark_width: f32,
+    /// Цвета поля и символов
+    pub colors: GameColors,
+    /// Узоры на клетках: штриховка под X и точка внутри O, чтобы символы
+    /// различались и без цвета
+    pub patterns: bool,
+}
+
+impl Default for BoardStyle {
+    fn default() -> Self {
+        Self {
+            cell_size: 80.0,
+            grid_width: 3.0,
+            mark_width: 4.0,
+            colors: GameColors::default(),
+            patterns: false,
+        }
+    }
+}
+
+// === АНИМАЦИЯ ===
+
+/// Ключ, под которым графические версии хранят настройки анимации
+pub const MOTION_STORAGE_KEY: &str = "tic_tac_toe_motion";
+
+/// Сколько секунд дорисовывается новый символ
+const MARK_DURATION: f64 = 0.25;
+/// Сколько секунд зачеркивается выигрышная линия
+const LINE_DURATION: f64 = 0.4;
+/// Сколько секунд пульсирует последний ход
+const PULSE_DURATION: f64 = 1.2;
+/// Сколько секунд летит салют
+const CELEBRATION_DURATION: f64 = 1.6;
+/// Сколько частиц в салюте
+const CELEBRATION_PARTICLES: usize = 28;
+
+/// Настройки анимации поля
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug, Serialize, Deserialize)]
+pub struct Motion {
+    /// Меньше движения: символы и линия рисуются сразу, без пульса и салюта
+    pub reduce: bool,
+    /// Салют при победе
+    pub celebration: bool,
+}
+
+/// Когда на поле появились символы и закончилась партия
+///
+/// Хранится в памяти egui между кадрами под идентификатором поля.
+#[derive(Clone, Debug, Default)]
+struct Timeline {
+    /// Поле в прошлом кадре
+    board: Vec<Option<Player>>,
+    /// Время появления символа в каждой клетке
+    placed: Vec<f64>,
+    /// Время, когда собрана выигрышная линия
+    won: Option<f64>,
+}
+
+impl Timeline {
+    /// Начинает отсчет с уже стоящими символами: их не нужно анимировать
+    fn new(game: &TicTacToe) -> Self {
+        Self {
+            board: game.get_board().to_vec(),
+            placed: vec![f64::NEG_INFINITY; game.get_board().len()],
+            won: game.winning_line().map(|_| f64::NEG_INFINITY),
+        }
+    }
+
+    /// Сравнивает поле с прошлым кадром и запоминает время новых символов
+    fn update(&mut self, game: &TicTacToe, now: f64) {
+        let board = game.get_board();
+        if board.len() != self.board.len() {
+            *self = Self::new(game);
+            return;
+        }
+        for (position, cell) in board.iter().enumerate() {
+            if cell.is_some() && self.board[position] != *cell {
+                self.placed[position] = now;
+            }
+        }
+        self.board = board.to_vec();
+        self.won = match (game.winning_line(), self.won) {
+            (None, _) => None,
+            (Some(_), None) => Some(now),
+            (Some(_), won) => won,
+        };
+    }
+}
+
+/// Доля пройденной анимации от 0 до 1; без движения анимация сразу закончена
+fn progress(motion: Motion, start: f64, now: f64, duration: f64) -> f32 {
+    if motion.reduce {
+        return 1.0;
+    }
+    ((now - start) / duration).clamp(0.0, 1.0) as f32
+}
+
+// === ВИДЖЕТ ===
+
+/// Стрелка от одной клетки к другой
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Arrow {
+    /// Клетка, из которой выходит стрелка
+    pub from: usize,
+    /// Клетка, на которую указывает стрелка
+    pub to: usize,
+    /// Цвет стрелки
+    pub color: egui::Color32,
+}
+
+/// Поле партии как виджет egui
+///
+/// Собирается цепочкой вызовов и показывается через [`BoardWidget::show`]
+/// (с номерами нажатой и наведенной клеток) или через `ui.add(...)`
+/// (только [`egui::Response`]; клетку тогда дает [`cell_at`]).
+#[must_use = "виджет нужно показать через `show` или `ui.add`"]
+pub struct BoardWidget<'a> {
+    /// Партия, поле которой рисуется
+    game: &'a TicTacToe,
+    /// Размеры и цвета
+    style: BoardStyle,
+    /// Поле только для просмотра: нажатия не принимаются
+    read_only: bool,
+    /// Подсвеченные клетки и цвет подсветки
+    highlights: Vec<(usize, egui::Color32)>,
+    /// Стрелки поверх символов
+    arrows: Vec<Arrow>,
+    /// Подписи в углу клеток
+    annotations: Vec<(usize, String)>,
+    /// Настройки анимации
+    motion: Motion,
+}
+
+/// Что произошло с полем за кадр
+#[derive(Clone, Debug)]
+pub struct BoardResponse {
+    /// Ответ egui для всей области поля
+    pub response: egui::Response,
+    /// Нажатая клетка (`None` - нажатия не было или поле только для просмотра)
+    pub clicked: Option<usize>,
+    /// Клетка под курсором
+    pub hovered: Option<usize>,
+}
+
+impl<'a> BoardWidget<'a> {
+    /// Создает виджет поля партии со стилем по умолчанию
+    pub fn new(game: &'a TicTacToe) -> Self {
+        Self {
+            game,
+            style: BoardStyle::default(),
+            read_only: false,
+            highlights: Vec::new(),
+            arrows: Vec::new(),
+            annotations: Vec::new(),
+            motion: Motion::default(),
+        }
+    }
+
+    /// Задает размеры и цвета поля
+    pub fn style(mut self, style: BoardStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Делает поле только для просмотра: клетки не нажимаются
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Подсвечивает клетку; полупрозрачный цвет не закрывает сетку
+    pub fn highlight(mut self, position: usize, color: egui::Color32) -> Self {
+        self.highlights.push((position, color));
+        self
+    }
+
+    /// Рисует стрелку от клетки `from` к клетке `to`
+    pub fn arrow(mut self, from: usize, to: usize, color: egui::Color32) -> Self {
+        self.arrows.push(Arrow { from, to, color });
+        self
+    }
+
+    /// Подписывает клетку в левом верхнем углу
+    pub fn annotate(mut self, position: usize, text: impl Into<String>) -> Self {
+        self.annotations.push((position, text.into()));
+        self
+    }
+
+    /// Задает настройки анимации
+    pub fn motion(mut self, motion: Motion) -> Self {
+        self.motion = motion;
+        self
+    }
+
+    /// Показывает поле и сообщает, какую клетку нажали и над какой курсор
+    ///
+    /// ## Параметры
+    /// - `ui`: область, в которую добавляется поле
+    ///
+    /// ## Возвращает
+    /// Ответ egui и номера нажатой и наведенной клеток
+    pub fn show(self, ui: &mut egui::Ui) -> BoardResponse {
+        let size = self.game.rules().size;
+        // Поле не шире доступного места, но клетки не больше заданных
+        let cell_size = self.style.cell_size.min(ui.available_width() / size as f32).max(1.0);
+        let board_size = cell_size * size as f32;
+        let sense = if self.read_only { egui::Sense::hover() } else { egui::Sense::click() };
+        let (response, painter) = ui.allocate_painter(egui::vec2(board_size, board_size), sense);
+        let rect = response.rect;
+
+        let now = ui.input(|input| input.time);
+        let mut timeline = ui.data(|data| data.get_temp::<Timeline>(response.id)).unwrap_or_else(|| Timeline::new(self.game));
+        timeline.update(self.game, now);
+        if ui.is_rect_visible(rect) && self.paint(&painter, rect, cell_size, &timeline, now) {
+            // Анимация не закончена - следующий кадр нужен сразу
+            ui.ctx().request_repaint();
+        }
+        ui.data_mut(|data| data.insert_temp(response.id, timeline));
+
+        let hovered = response.hover_pos().and_then(|pos| cell_at(size, rect, pos));
+        let clicked = if response.clicked() {
+            response.interact_pointer_pos().and_then(|pos| cell_at(size, rect, pos))
+        } else {
+            None
+        };
+        BoardResponse { response, clicked, hovered }
+    }
+
+    /// Рисует фон, подсветку, сетку, символы, выигрышную линию, стрелки и подписи
+    ///
+    /// ## Возвращает
+    /// `true`, если какая-то анимация еще идет
+    fn paint(&self, painter: &egui::Painter, rect: egui::Rect, cell_size: f32, timeline: &Timeline, now: f64) -> bool {
+        let size = self.game.rules().size;
+        let colors = &self.style.colors;
+        let mut animating = false;
+
+        // Фон поля без скругления углов
+        painter.rect_filled(rect, 0.0, colors.background);
+
+        for &(position, color) in self.highlights.iter().filter(|(position, _)| *position < size * size) {
+            painter.rect_filled(cell_rect(size, rect, position), 0.0, color);
+        }
+
+        // Последний ход дважды вспыхивает и гаснет; без движения - бледная подсветка
+        if let Some(&last) = self.game.moves().last().filter(|&&last| last < size * size) {
+            let phase = progress(self.motion, timeline.placed[last], now, PULSE_DURATION);
+            let strength = if self.motion.reduce { 0.3 } else { (1.0 - phase) * (0.5 - 0.5 * (phase * 2.0 * TAU).cos()) };
+            painter.rect_filled(cell_rect(size, rect, last), 0.0, colors.highlight.gamma_multiply(0.35 * strength));
+            animating |= phase < 1.0;
+        }
+
+        // Сетка: по size - 1 вертикальных и горизонтальных линий
+        let stroke = egui::Stroke::new(self.style.grid_width, colors.grid);
+        for i in 1..size {
+            let offset = i as f32 * cell_size;
+            painter.line_segment([egui::pos2(rect.min.x + offset, rect.min.y), egui::pos2(rect.min.x + offset, rect.max.y)], stroke);
+            painter.line_segment([egui::pos2(rect.min.x, rect.min.y + offset), egui::pos2(rect.max.x, rect.min.y + offset)], stroke);
+        }
+
+        for (position, cell) in self.game.get_board().iter().enumerate() {
+            let cell_rect = cell_rect(size, rect, position);
+            let drawn = progress(self.motion, timeline.placed[position], now, MARK_DURATION);
+            match cell {
+                Some(Player::X) => {
+                    if self.style.patterns {
+                        self.paint_hatching(painter, cell_rect);
+                    }
+                    self.paint_x(painter, cell_rect.center(), cell_size, drawn);
+                }
+                Some(Player::O) => {
+                    if self.style.patterns {
+                        painter.circle_filled(cell_rect.center(), cell_size * 0.08, self.style.colors.o_color);
+                    }
+                    self.paint_o(painter, cell_rect.center(), cell_size, drawn);
+                }
+                None => continue,
+            }
+            animating |= drawn < 1.0;
+        }
+
+        // Выигрышная линия зачеркивается от первой клетки к последней
+        if let (Some((first, last)), Some(won)) = (self.game.winning_line(), timeline.won) {
+            let from = cell_rect(size, rect, first).center();
+            let to = cell_rect(size, rect, last).center();
+            let drawn = progress(self.motion, won, now, LINE_DURATION);
+            painter.line_segment([from, from + (to - from) * drawn], egui::Stroke::new(self.style.mark_width * 1.5, colors.highlight));
+            animating |= drawn < 1.0;
+            if self.motion.celebration && !self.motion.reduce {
+                animating |= self.paint_celebration(painter, from + (to - from) * 0.5, cell_size, (now - won) as f32);
+            }
+        }
+
+        for arrow in self.arrows.iter().filter(|arrow| arrow.from < size * size && arrow.to < size * size) {
+            let from = cell_rect(size, rect, arrow.from).center();
+            let to = cell_rect(size, rect, arrow.to).center();
+            // Острие не доходит до центра, чтобы не закрывать символ целиком
+            painter.arrow(from, (to - from) * 0.85, egui::Stroke::new(self.style.mark_width, arrow.color));
+        }
+
+        let font = egui::FontId::proportional(cell_size * 0.2);
+        for (position, text) in self.annotations.iter().filter(|(position, _)| *position < size * size) {
+            let corner = cell_rect(size, rect, *position).left_top() + egui::vec2(cell_size * 0.05, cell_size * 0.03);
+            painter.text(corner, egui::Align2::LEFT_TOP, text, font.clone(), colors.text);
+        }
+        animating
+    }
+
+    /// Рисует крест из двух диагоналей; размер - 30% клетки от центра
+    ///
+    /// `drawn` - доля нарисованного: первая половина - первая диагональ,
+    /// вторая половина - вторая.
+    fn paint_x(&self, painter: &egui::Painter, center: egui::Pos2, cell_size: f32, drawn: f32) {
+        let size = cell_size * 0.3;
+        let stroke = egui::Stroke::new(self.style.mark_width, self.style.colors.x_color);
+        let diagonals = [(egui::vec2(-size, -size), egui::vec2(size, size)), (egui::vec2(size, -size), egui::vec2(-size, size))];
+        for (index, (from, to)) in diagonals.into_iter().enumerate() {
+            let part = (drawn * 2.0 - index as f32).clamp(0.0, 1.0);
+            if part > 0.0 {
+                painter.line_segment([center + from, center + from + (to - from) * part], stroke);
+            }
+        }
+    }
+
+    /// Рисует бледную диагональную штриховку клетки с X
+    fn paint_hatching(&self, painter: &egui::Painter, cell: egui::Rect) {
+        let stroke = egui::Stroke::new(1.0, self.style.colors.x_color.gamma_multiply(0.35));
+        let step = cell.width() / 6.0;
+        // Линии под 45 градусов, обрезанные по краям клетки
+        for i in 1..12 {
+            let offset = i as f32 * step;
+            let start = cell.left_top() + if offset < cell.width() { egui::vec2(offset, 0.0) } else { egui::vec2(cell.width(), offset - cell.width()) };
+            let end = cell.left_top() + if offset < cell.height() { egui::vec2(0.0, offset) } else { egui::vec2(offset - cell.height(), cell.height()) };
+            painter.line_segment([start, end], stroke);
+        }
+    }
+
+    /// Рисует окружность радиусом 25% клетки; `drawn` - доля нарисованной дуги
+    fn paint_o(&self, painter: &egui::Painter, center: egui::Pos2, cell_size: f32, drawn: f32) {
+        let radius = cell_size * 0.25;
+        let stroke = egui::Stroke::new(self.style.mark_width, self.style.colors.o_color);
+        if drawn >= 1.0 {
+            painter.circle_stroke(center, radius, stroke);
+            return;
+        }
+        // Дуга по часовой стрелке от верхней точки
+        let points = (0..=32)
+            .map(|step| {
+                let angle = drawn * TAU * step as f32 / 32.0 - TAU / 4.0;
+                center + radius * egui::vec2(angle.cos(), angle.sin())
+            })
+            .collect();
+        painter.add(egui::Shape::line(points, stroke));
+    }
+
+    /// Рисует салют из центра выигрышной линии
+    ///
+    /// ## Параметры
+    /// - `origin`: откуда разлетаются частицы
+    /// - `elapsed`: секунды с победы
+    ///
+    /// ## Возвращает
+    /// `true`, пока салют еще виден
+    fn paint_celebration(&self, painter: &egui::Painter, origin: egui::Pos2, cell_size: f32, elapsed: f32) -> bool {
+        let phase = elapsed / CELEBRATION_DURATION as f32;
+        if !(0.0..1.0).contains(&phase) {
+            return false;
+        }
+        let colors = &self.style.colors;
+        let palette = [colors.x_color, colors.o_color, colors.highlight];
+        for index in 0..CELEBRATION_PARTICLES {
+            // Золотой угол раскладывает частицы по кругу без видимого узора
+            let angle = index as f32 * 2.399_963;
+            let speed = cell_size * (1.5 + (index % 5) as f32 * 0.4);
+            let gravity = egui::vec2(0.0, cell_size * 2.0 * elapsed * elapsed);
+            let position = origin + egui::vec2(angle.cos(), angle.sin()) * speed * elapsed + gravity;
+            painter.circle_filled(position, cell_size * 0.05, palette[index % palette.len()].gamma_multiply(1.0 - phase));
+        }
+        true
+    }
+}
+
+impl egui::Widget for BoardWidget<'_> {
+    fn ui(self, ui: &mut egui::Ui) -> egui::Response {
+        self.show(ui).response
+    }
+}
+
+// === ГЕОМЕТРИЯ ===
+
+/// Клетка поля под точкой
+///
+/// ## Параметры
+/// - `size`: размер стороны поля в клетках
+/// - `rect`: область, которую занимает поле
+/// - `pos`: точка на экране
+///
+/// ## Возвращает
+/// Номер клетки или `None`, если точка вне поля
+///
+/// ## Пример
+/// ```rust
+/// use eframe::egui;
+/// use rust_tic_tac_toe::board::cell_at;
+///
+/// let rect = egui::Rect::from_min_size(egui::pos2(10.0, 10.0), egui::vec2(240.0, 240.0));
+/// assert_eq!(cell_at(3, rect, egui::pos2(130.0, 130.0)), Some(4));
+/// assert_eq!(cell_at(3, rect, egui::pos2(5.0, 130.0)), None);
+/// ```
+pub fn cell_at(size: usize, rect: egui::Rect, pos: egui::Pos2) -> Option<usize> {
+    if size == 0 || !rect.contains(pos) {
+        return None;
+    }
+    let offset = pos - rect.min;
+    let col = ((offset.x / rect.width() * size as f32) as usize).min(size - 1);
+    let row = ((offset.y / rect.height() * size as f32) as usize).min(size - 1);
+    Some(row * size + col)
+}
+
+/// Область клетки на экране
+fn cell_rect(size: usize, rect: egui::Rect, position: usize) -> egui::Rect {
+    let cell = rect.width() / size as f32;
+    let (row, col) = (position / size, position % size);
+    egui::Rect::from_min_size(rect.min + egui::vec2(col as f32 * cell, row as f32 * cell), egui::vec2(cell, cell))
+}
+
+// === ТЕСТЫ ===
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Rules;
+
+    /// Показывает поле в пустом кадре и возвращает ответ виджета
+    fn show(ctx: &egui::Context, input: egui::RawInput, board: impl FnOnce(&mut egui::Ui) -> BoardResponse) -> BoardResponse {
+        let mut result = None;
+        let _ = ctx.run(input, |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| result = Some(board(ui)));
+        });
+        result.unwrap()
+    }
+
+    /// Ввод кадра с окном 400x500 и указанными событиями
+    fn input(events: Vec<egui::Event>) -> egui::RawInput {
+        egui::RawInput {
+            screen_rect: Some(egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(400.0, 500.0))),
+            events,
+            ..Default::default()
+        }
+    }
+
+    /// Нажатие или отпускание левой кнопки мыши в точке
+    fn button(pos: egui::Pos2, pressed: bool) -> egui::Event {
+        egui::Event::PointerButton { pos, button: egui::PointerButton::Primary, pressed, modifiers: Default::default() }
+    }
+
+    /// Тест: точка переводится в номер клетки для полей разного размера
+    #[test]
+    fn test_cell_at() {
+        let rect = egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(150.0, 150.0));
+        assert_eq!(cell_at(3, rect, egui::pos2(1.0, 1.0)), Some(0));
+        assert_eq!(cell_at(3, rect, egui::pos2(149.0, 149.0)), Some(8));
+        assert_eq!(cell_at(15, rect, egui::pos2(15.0, 25.0)), Some(31));
+        assert_eq!(cell_at(3, rect, egui::pos2(151.0, 10.0)), None);
+    }
+
+    /// Тест: поле занимает size клеток и сужается под узкую панель
+    #[test]
+    fn test_board_size() {
+        let ctx = egui::Context::default();
+        let game = TicTacToe::new();
+        let board = show(&ctx, input(Vec::new()), |ui| BoardWidget::new(&game).show(ui));
+        assert_eq!(board.response.rect.size(), egui::vec2(240.0, 240.0));
+
+        let gomoku = TicTacToe::with_rules(Rules::gomoku());
+        let board = show(&ctx, input(Vec::new()), |ui| BoardWidget::new(&gomoku).show(ui));
+        assert!(board.response.rect.width() <= 400.0);
+        assert_eq!(board.response.rect.width(), board.response.rect.height());
+    }
+
+    /// Тест: нажатие сообщает клетку, а поле только для просмотра его не принимает
+    #[test]
+    fn test_click_reports_cell() {
+        let game = TicTacToe::new();
+        for read_only in [false, true] {
+            let ctx = egui::Context::default();
+            let board = show(&ctx, input(Vec::new()), |ui| BoardWidget::new(&game).read_only(read_only).show(ui));
+            let center = cell_rect(3, board.response.rect, 4).center();
+
+            let press = vec![egui::Event::PointerMoved(center), button(center, true)];
+            show(&ctx, input(press), |ui| BoardWidget::new(&game).read_only(read_only).show(ui));
+            let board = show(&ctx, input(vec![button(center, false)]), |ui| BoardWidget::new(&game).read_only(read_only).show(ui));
+
+            assert_eq!(board.hovered, Some(4));
+            assert_eq!(board.clicked, if read_only { None } else { Some(4) });
+        }
+    }
+
+    /// Тест: анимируются только новые символы, а линия - с момента победы
+    #[test]
+    fn test_timeline() {
+        let mut game = TicTacToe::new();
+        game.make_move(0);
+        let mut timeline = Timeline::new(&game);
+        assert_eq!(timeline.placed[0], f64::NEG_INFINITY, "символ уже стоял, когда поле показали");
+
+        for (time, position) in [(1.0, 3), (2.0, 1), (3.0, 4), (4.0, 2)] {
+            game.make_move(position);
+            timeline.update(&game, time);
+        }
+        assert_eq!(timeline.placed[2], 4.0);
+        assert_eq!(timeline.won, Some(4.0));
+        timeline.update(&game, 5.0);
+        assert_eq!((timeline.placed[2], timeline.won), (4.0, Some(4.0)), "повторный кадр ничего не сдвигает");
+
+        game.reset();
+        timeline.update(&game, 6.0);
+        assert_eq!(timeline.won, None);
+        assert_eq!(progress(Motion { reduce: true, celebration: true }, 6.0, 6.0, MARK_DURATION), 1.0);
+        assert_eq!(progress(Motion::default(), 6.0, 6.0 + MARK_DURATION / 2.0, MARK_DURATION), 0.5);
+    }
+}
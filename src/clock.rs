@@ -0,0 +1,464 @@
+//! # Шахматные часы
+//!
+//! Часы отсчитывают время каждого игрока по контролю времени [`TimeControl`]:
+//! - без добавки (`5`) - основное время дается на всю партию;
+//! - Фишер (`5+3`) - после каждого хода добавляется несколько секунд;
+//! - Бронштейн (`5d3`) - после хода возвращается потраченное время,
+//!   но не больше задержки;
+//! - лимит на ход (`/10s`) - на каждый ход отдельно дается одно и то же время.
+//!
+//! Время часы берут из источника [`TimeSource`]: в игре это [`RealTime`],
+//! в тестах - [`ManualTime`], который продвигается вручную.
+//!
+//! Часы следят за партией через [`Clock::tick`]: новый ход переключает часы,
+//! а когда время игрока истекает, партия заканчивается вызовом
+//! [`TicTacToe::time_out`] - поражение или ничья, если соперник уже не может
+//! собрать линию.
+//!
+//! ## Использование
+//! ```rust
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//! use rust_tic_tac_toe::{Player, TicTacToe};
+//! use rust_tic_tac_toe::clock::{Clock, ManualTime};
+//!
+//! let time = ManualTime::default();
+//! let mut clock = Clock::new("1+2".parse().unwrap(), Arc::new(time.clone()));
+//! let mut game = TicTacToe::new();
+//! clock.tick(&mut game); // пошли часы X
+//!
+//! time.advance(Duration::from_secs(10));
+//! game.make_move(4);
+//! clock.tick(&mut game); // X потратил 10 секунд и получил 2
+//! assert_eq!(clock.remaining(Player::X), Duration::from_secs(52));
+//! assert_eq!(clock.running(), Some(Player::O));
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use web_time::Instant;
+
+use crate::{Player, TicTacToe};
+
+// === КОНТРОЛЬ ВРЕМЕНИ ===
+
+/// Способ отсчета времени
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClockMode {
+    /// Только основное время
+    SuddenDeath,
+    /// Добавка после каждого хода
+    #[default]
+    Fischer,
+    /// Возврат потраченного на ход времени, не больше задержки
+    Bronstein,
+    /// Отдельный лимит на каждый ход
+    PerMove,
+}
+
+/// Контроль времени
+///
+/// Записывается как `5` (пять минут на партию), `5+3` (Фишер, три секунды
+/// за ход), `5d3` (Бронштейн, задержка три секунды) или `/10s` (десять секунд
+/// на каждый ход). Время в секундах помечается суффиксом `s`: `30s+1`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TimeControl {
+    /// Основное время в секундах (для лимита на ход - время на каждый ход)
+    pub base_secs: u64,
+    /// Добавка Фишера или задержка Бронштейна в секундах
+    pub increment_secs: u64,
+    /// Способ отсчета
+    #[serde(default)]
+    pub mode: ClockMode,
+}
+
+impl TimeControl {
+    /// Основное время без добавки
+    pub const fn sudden_death(base_secs: u64) -> Self {
+        Self { base_secs, increment_secs: 0, mode: ClockMode::SuddenDeath }
+    }
+
+    /// Основное время с добавкой Фишера
+    pub const fn fischer(base_secs: u64, increment_secs: u64) -> Self {
+        Self { base_secs, increment_secs, mode: ClockMode::Fischer }
+    }
+
+    /// Основное время с задержкой Бронштейна
+    pub const fn bronstein(base_secs: u64, delay_secs: u64) -> Self {
+        Self { base_secs, increment_secs: delay_secs, mode: ClockMode::Bronstein }
+    }
+
+    /// Одинаковый лимит на каждый ход
+    pub const fn per_move(limit_secs: u64) -> Self {
+        Self { base_secs: limit_secs, increment_secs: 0, mode: ClockMode::PerMove }
+    }
+
+    /// Время на часах игрока в начале партии
+    pub fn initial(&self) -> Duration {
+        Duration::from_secs(self.base_secs)
+    }
+}
+
+impl fmt::Display for TimeControl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let base = if self.base_secs.is_multiple_of(60) {
+            (self.base_secs / 60).to_string()
+        } else {
+            format!("{}s", self.base_secs)
+        };
+        match self.mode {
+            ClockMode::SuddenDeath => write!(f, "{}", base),
+            ClockMode::Fischer => write!(f, "{}+{}", base, self.increment_secs),
+            ClockMode::Bronstein => write!(f, "{}d{}", base, self.increment_secs),
+            ClockMode::PerMove => write!(f, "/{}", base),
+        }
+    }
+}
+
+impl FromStr for TimeControl {
+    type Err = String;
+
+    /// Разбирает `5`, `5+3`, `5d3` или `/10s`; минуты можно заменить секундами (`30s+1`)
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let text = text.trim();
+        let error = || format!("неверный контроль времени: {}", text);
+        let base = |base: &str| -> Result<u64, String> {
+            match base.strip_suffix('s') {
+                Some(secs) => secs.parse().map_err(|_| error()),
+                None => base.parse::<u64>().map(|minutes| minutes * 60).map_err(|_| error()),
+            }
+        };
+        let increment = |increment: &str| increment.parse::<u64>().map_err(|_| error());
+
+        let control = if let Some(limit) = text.strip_prefix('/') {
+            Self::per_move(base(limit)?)
+        } else if let Some((main, extra)) = text.split_once('+') {
+            Self::fischer(base(main)?, increment(extra)?)
+        } else if let Some((main, delay)) = text.split_once('d') {
+            Self::bronstein(base(main)?, increment(delay)?)
+        } else {
+            Self::sudden_death(base(text)?)
+        };
+        if control.base_secs == 0 {
+            return Err(error());
+        }
+        Ok(control)
+    }
+}
+
+// === ИСТОЧНИКИ ВРЕМЕНИ ===
+
+/// Источник монотонного времени для часов
+pub trait TimeSource: Send + Sync {
+    /// Время от произвольной начальной точки; никогда не уменьшается
+    fn now(&self) -> Duration;
+}
+
+/// Настоящее время
+#[derive(Clone, Copy, Debug)]
+pub struct RealTime {
+    start: Instant,
+}
+
+impl Default for RealTime {
+    fn default() -> Self {
+        Self { start: Instant::now() }
+    }
+}
+
+impl TimeSource for RealTime {
+    fn now(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
+
+/// Время, которое продвигается вручную (для тестов)
+///
+/// Копии указывают на одни и те же часы: тест оставляет себе одну копию,
+/// а другую отдает [`Clock`].
+#[derive(Clone, Debug, Default)]
+pub struct ManualTime {
+    millis: Arc<AtomicU64>,
+}
+
+impl ManualTime {
+    /// Продвигает время вперед
+    pub fn advance(&self, duration: Duration) {
+        self.millis.fetch_add(duration.as_millis() as u64, Ordering::SeqCst);
+    }
+}
+
+impl TimeSource for ManualTime {
+    fn now(&self) -> Duration {
+        Duration::from_millis(self.millis.load(Ordering::SeqCst))
+    }
+}
+
+// === ЧАСЫ ===
+
+/// Показания часов для передачи по сети
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClockState {
+    /// Оставшееся время X и O в миллисекундах на момент отправки
+    pub remaining_ms: [u64; 2],
+    /// Чьи часы идут
+    pub running: Option<Player>,
+}
+
+impl ClockState {
+    /// Оставшееся время игрока спустя `elapsed` после отправки показаний
+    pub fn remaining(&self, player: Player, elapsed: Duration) -> Duration {
+        let remaining = Duration::from_millis(self.remaining_ms[index(player)]);
+        if self.running == Some(player) {
+            remaining.saturating_sub(elapsed)
+        } else {
+            remaining
+        }
+    }
+}
+
+/// Шахматные часы для одной партии
+#[derive(Clone)]
+pub struct Clock {
+    control: TimeControl,
+    source: Arc<dyn TimeSource>,
+    /// Время X и O на начало их текущего хода
+    remaining: [Duration; 2],
+    /// Чьи часы идут и когда начался ход
+    running: Option<(Player, Duration)>,
+    /// Сколько ходов партии часы уже учли
+    moves_seen: usize,
+    /// Игрок, у которого истекло время
+    flagged: Option<Player>,
+}
+
+impl Clock {
+    /// Создает остановленные часы
+    ///
+    /// ## Параметры
+    /// - `control`: контроль времени
+    /// - `source`: источник времени
+    pub fn new(control: TimeControl, source: Arc<dyn TimeSource>) -> Self {
+        Self {
+            control,
+            source,
+            remaining: [control.initial(); 2],
+            running: None,
+            moves_seen: 0,
+            flagged: None,
+        }
+    }
+
+    /// Контроль времени
+    pub fn control(&self) -> TimeControl {
+        self.control
+    }
+
+    /// Оставшееся время игрока с учетом идущего хода
+    pub fn remaining(&self, player: Player) -> Duration {
+        let remaining = self.remaining[index(player)];
+        match self.running {
+            Some((running, started)) if running == player => {
+                remaining.saturating_sub(self.source.now().saturating_sub(started))
+            }
+            _ => remaining,
+        }
+    }
+
+    /// Чьи часы идут
+    pub fn running(&self) -> Option<Player> {
+        self.running.map(|(player, _)| player)
+    }
+
+    /// Игрок, у которого истекло время
+    pub fn flagged(&self) -> Option<Player> {
+        self.flagged
+    }
+
+    /// Показания часов для передачи по сети
+    pub fn state(&self) -> ClockState {
+        ClockState {
+            remaining_ms: [Player::X, Player::O].map(|player| self.remaining(player).as_millis() as u64),
+            running: self.running(),
+        }
+    }
+
+    /// Сверяет часы с партией
+    ///
+    /// Если в партии появился новый ход, время сделавшего его игрока
+    /// пересчитывается по контролю времени и запускаются часы соперника.
+    /// Если время текущего игрока истекло, партия заканчивается.
+    /// Вызывается после каждого хода и периодически между ходами.
+    ///
+    /// ## Возвращает
+    /// `true`, если именно сейчас у игрока истекло время
+    pub fn tick(&mut self, game: &mut TicTacToe) -> bool {
+        if self.flagged.is_some() {
+            return false;
+        }
+        let now = self.source.now();
+
+        let moves = game.moves().len();
+        if moves != self.moves_seen {
+            if let (true, Some((player, started))) = (moves > self.moves_seen, self.running) {
+                self.finish_turn(player, now.saturating_sub(started));
+            }
+            self.moves_seen = moves;
+            self.running = None;
+        }
+        if game.is_game_over() {
+            self.running = None;
+            return false;
+        }
+
+        match self.running {
+            None => self.running = Some((game.current_player(), now)),
+            Some((player, started)) if now.saturating_sub(started) >= self.remaining[index(player)] => {
+                self.remaining[index(player)] = Duration::ZERO;
+                self.running = None;
+                self.flagged = Some(player);
+                game.time_out(player);
+                return true;
+            }
+            Some(_) => {}
+        }
+        false
+    }
+
+    /// Пересчитывает время игрока после его хода
+    fn finish_turn(&mut self, player: Player, spent: Duration) {
+        let increment = Duration::from_secs(self.control.increment_secs);
+        let remaining = &mut self.remaining[index(player)];
+        *remaining = match self.control.mode {
+            ClockMode::SuddenDeath => remaining.saturating_sub(spent),
+            ClockMode::Fischer => remaining.saturating_sub(spent) + increment,
+            ClockMode::Bronstein => remaining.saturating_sub(spent) + spent.min(increment),
+            ClockMode::PerMove => self.control.initial(),
+        };
+    }
+}
+
+impl fmt::Debug for Clock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Clock")
+            .field("control", &self.control)
+            .field("remaining", &self.remaining)
+            .field("running", &self.running)
+            .field("flagged", &self.flagged)
+            .finish()
+    }
+}
+
+/// Записывает время для табло: `4:05`, а меньше десяти секунд - `9.4`
+pub fn format_remaining(remaining: Duration) -> String {
+    let secs = remaining.as_secs();
+    if secs < 10 {
+        format!("{}.{}", secs, remaining.subsec_millis() / 100)
+    } else if secs < 3600 {
+        format!("{}:{:02}", secs / 60, secs % 60)
+    } else {
+        format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+    }
+}
+
+/// Индекс игрока в массивах часов
+fn index(player: Player) -> usize {
+    match player {
+        Player::X => 0,
+        Player::O => 1,
+    }
+}
+
+// === ТЕСТЫ ===
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Termination;
+
+    const SECOND: Duration = Duration::from_secs(1);
+
+    /// Играет ход через `spent` секунд после начала хода
+    fn play(clock: &mut Clock, time: &ManualTime, game: &mut TicTacToe, position: usize, spent: u64) {
+        time.advance(SECOND * spent as u32);
+        assert!(!clock.tick(game), "время не должно истечь");
+        game.make_move(position);
+        clock.tick(game);
+    }
+
+    /// Тест: запись и разбор всех контролей времени
+    #[test]
+    fn test_time_control_notation() {
+        for (text, control) in [
+            ("5", TimeControl::sudden_death(300)),
+            ("5+3", TimeControl::fischer(300, 3)),
+            ("30s+1", TimeControl::fischer(30, 1)),
+            ("5d3", TimeControl::bronstein(300, 3)),
+            ("/10s", TimeControl::per_move(10)),
+        ] {
+            assert_eq!(text.parse::<TimeControl>(), Ok(control));
+            assert_eq!(control.to_string(), text);
+        }
+        assert!("0+3".parse::<TimeControl>().is_err());
+        assert!("5+x".parse::<TimeControl>().is_err());
+    }
+
+    /// Тест: добавка Фишера, возврат Бронштейна и лимит на ход
+    #[test]
+    fn test_clock_modes() {
+        let cases = [
+            (TimeControl::sudden_death(60), 50),
+            (TimeControl::fischer(60, 5), 55),
+            (TimeControl::bronstein(60, 5), 55),
+            (TimeControl::bronstein(60, 15), 60),
+            (TimeControl::per_move(20), 20),
+        ];
+        for (control, expected) in cases {
+            let time = ManualTime::default();
+            let mut clock = Clock::new(control, Arc::new(time.clone()));
+            let mut game = TicTacToe::new();
+            clock.tick(&mut game);
+            play(&mut clock, &time, &mut game, 4, 10);
+            assert_eq!(clock.remaining(Player::X), SECOND * expected, "{}", control);
+            assert_eq!(clock.running(), Some(Player::O));
+
+            time.advance(SECOND * 3);
+            assert_eq!(clock.remaining(Player::O), control.initial() - SECOND * 3);
+            assert_eq!(clock.state().remaining_ms[1], control.initial().as_millis() as u64 - 3000);
+        }
+    }
+
+    /// Тест: истечение времени - поражение, а если соперник не может победить - ничья
+    #[test]
+    fn test_flag_fall() {
+        let time = ManualTime::default();
+        let mut clock = Clock::new(TimeControl::per_move(5), Arc::new(time.clone()));
+        let mut game = TicTacToe::new();
+        clock.tick(&mut game);
+        play(&mut clock, &time, &mut game, 4, 4);
+        time.advance(SECOND * 5);
+        assert!(clock.tick(&mut game));
+        assert_eq!(clock.flagged(), Some(Player::O));
+        assert!(game.is_game_over());
+        assert_eq!(game.termination(), Some(Termination::Timeout(Player::O)));
+        assert_eq!(game.get_winner(), Some(Player::X));
+
+        // O не может собрать линию через последнюю свободную клетку
+        let mut game = TicTacToe::new();
+        for position in [0, 1, 2, 4, 3, 5, 7, 6] {
+            game.make_move(position);
+        }
+        let mut clock = Clock::new(TimeControl::sudden_death(1), Arc::new(time.clone()));
+        clock.tick(&mut game);
+        time.advance(SECOND * 2);
+        assert!(clock.tick(&mut game));
+        assert_eq!(clock.flagged(), Some(Player::X));
+        assert_eq!(game.get_winner(), None);
+    }
+}
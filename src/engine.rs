@@ -0,0 +1,71 @@
+//! # Движки искусственного интеллекта
+//!
+//! Общий интерфейс "спросить у компьютера ход", которым пользуются
+//! консольная и графическая версии игры. Конкретные алгоритмы поиска
+//! находятся в отдельных модулях.
+//!
+//! ## Использование
+//! ```rust
+//! use rust_tic_tac_toe::TicTacToe;
+//! use rust_tic_tac_toe::engine::{self, Engine};
+//!
+//! let mut game = TicTacToe::new();
+//! let mut ai = engine::for_rules(game.rules());
+//! if let Some(position) = ai.choose_move(&game) {
+//!     game.make_move(position);
+//! }
+//! ```
+
+use std::time::Duration;
+
+use crate::threat::{ThreatSpaceConfig, ThreatSpaceEngine};
+use crate::{Rules, TicTacToe};
+
+/// Сведения о последнем поиске движка
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EngineInfo {
+    /// Достигнутая глубина
+    pub depth: usize,
+    /// Количество просмотренных узлов (итераций)
+    pub nodes: u64,
+    /// Оценка выбранного хода с точки зрения ходящего
+    pub score: i64,
+}
+
+/// Движок, который умеет выбирать ход в заданной позиции
+pub trait Engine {
+    /// Название движка для отображения в интерфейсе
+    fn name(&self) -> String;
+
+    /// Выбирает ход для текущего игрока
+    ///
+    /// ## Возвращает
+    /// - `Some(position)` - выбранная клетка
+    /// - `None` - если игра закончена и ходить некуда
+    fn choose_move(&mut self, game: &TicTacToe) -> Option<usize>;
+
+    /// Устанавливает время на обдумывание одного хода
+    fn set_move_time(&mut self, _time: Duration) {}
+
+    /// Сведения о последнем поиске, если движок их собирает
+    fn info(&self) -> Option<EngineInfo> {
+        None
+    }
+}
+
+/// Наибольшее поле (в клетках), на котором движок перебирает все ходы
+const EXACT_SEARCH_CELLS: usize = 16;
+
+/// Создает движок, подходящий для указанных правил
+///
+/// На маленьких полях (3x3, 4x4) альфа-бета перебор смотрит все свободные
+/// клетки на всю глубину партии и играет точно. На больших полях полный
+/// перебор невозможен, поэтому работает поиск угроз с отбором кандидатов.
+pub fn for_rules(rules: Rules) -> Box<dyn Engine + Send> {
+    let cells = rules.cell_count();
+    if cells <= EXACT_SEARCH_CELLS {
+        let config = ThreatSpaceConfig { max_depth: cells, candidate_limit: cells, ..Default::default() };
+        return Box::new(ThreatSpaceEngine::new(config));
+    }
+    Box::new(ThreatSpaceEngine::default())
+}
@@ -0,0 +1,147 @@
+//! # Запасные шрифты
+//!
+//! Встроенные шрифты egui покрывают латиницу, кириллицу и греческий, но не
+//! китайский, японский, корейский, арабский и иврит: такие буквы выводятся
+//! квадратами. Модуль добавляет запасные шрифты в конец семейств
+//! [`egui::FontDefinitions`], и egui берет из них символы, которых нет в
+//! основных шрифтах.
+//!
+//! Настольная версия ищет шрифты в каталоге из [`FONTS_ENV`] и среди
+//! известных системных шрифтов ([`load_system_fonts`]). Веб-версия скачивает
+//! файлы [`WEB_FONTS`], которые лежат рядом со страницей.
+//!
+//! ## Использование
+//! ```rust,no_run
+//! use rust_tic_tac_toe::fonts;
+//!
+//! # let ctx = eframe::egui::Context::default();
+//! ctx.set_fonts(fonts::definitions(fonts::load_system_fonts()));
+//! ```
+
+use eframe::egui;
+
+/// Переменная окружения с каталогом дополнительных шрифтов (`*.ttf`, `*.otf`, `*.ttc`)
+pub const FONTS_ENV: &str = "TIC_TAC_TOE_FONTS";
+
+/// Шрифты веб-версии: пути относительно страницы
+pub const WEB_FONTS: [&str; 3] = [
+    "fonts/NotoSansSC-Regular.otf",
+    "fonts/NotoSansJP-Regular.otf",
+    "fonts/NotoSansArabic-Regular.ttf",
+];
+
+/// Известные системные шрифты с китайскими, японскими, корейскими, арабскими
+/// и еврейскими буквами; загружаются те, что есть
+#[cfg(not(target_arch = "wasm32"))]
+const SYSTEM_FONTS: [&str; 14] = [
+    // Linux
+    "/usr/share/fonts/opentype/noto/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/noto-cjk/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/google-noto-cjk/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/truetype/wqy/wqy-microhei.ttc",
+    "/usr/share/fonts/truetype/noto/NotoSansArabic-Regular.ttf",
+    "/usr/share/fonts/noto/NotoSansArabic-Regular.ttf",
+    "/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf",
+    // Windows
+    "C:\\Windows\\Fonts\\msyh.ttc",
+    "C:\\Windows\\Fonts\\YuGothR.ttc",
+    "C:\\Windows\\Fonts\\malgun.ttf",
+    "C:\\Windows\\Fonts\\arial.ttf",
+    // macOS
+    "/System/Library/Fonts/PingFang.ttc",
+    "/System/Library/Fonts/Hiragino Sans GB.ttc",
+    "/System/Library/Fonts/Supplemental/Arial Unicode.ttf",
+];
+
+/// Шрифты egui по умолчанию и запасные шрифты после них
+///
+/// ## Параметры
+/// - `fonts`: имена и содержимое файлов шрифтов; файлы, которые не
+///   разбираются как шрифт, пропускаются
+pub fn definitions(fonts: Vec<(String, Vec<u8>)>) -> egui::FontDefinitions {
+    let mut definitions = egui::FontDefinitions::default();
+    for (name, data) in fonts {
+        if ab_glyph::FontRef::try_from_slice(&data).is_err() || definitions.font_data.contains_key(&name) {
+            continue;
+        }
+        definitions.font_data.insert(name.clone(), egui::FontData::from_owned(data));
+        for family in [egui::FontFamily::Proportional, egui::FontFamily::Monospace] {
+            definitions.families.entry(family).or_default().push(name.clone());
+        }
+    }
+    definitions
+}
+
+/// Загружает шрифты каталога; имя шрифта - имя файла
+///
+/// ## Возвращает
+/// Имена и содержимое файлов `*.ttf`, `*.otf` и `*.ttc` в порядке имен
+#[cfg(not(target_arch = "wasm32"))]
+pub fn load_dir(dir: impl AsRef<std::path::Path>) -> std::io::Result<Vec<(String, Vec<u8>)>> {
+    let mut fonts = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        let is_font = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .is_some_and(|extension| ["ttf", "otf", "ttc"].contains(&extension.to_lowercase().as_str()));
+        if let (true, Some(name)) = (is_font, path.file_name().and_then(|name| name.to_str())) {
+            fonts.push((name.to_string(), std::fs::read(&path)?));
+        }
+    }
+    fonts.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(fonts)
+}
+
+/// Шрифты из каталога [`FONTS_ENV`] и найденные системные шрифты
+#[cfg(not(target_arch = "wasm32"))]
+pub fn load_system_fonts() -> Vec<(String, Vec<u8>)> {
+    let mut fonts = match std::env::var(FONTS_ENV) {
+        Ok(dir) => load_dir(&dir).unwrap_or_else(|e| {
+            eprintln!("Не удалось загрузить шрифты из {}: {}", dir, e);
+            Vec::new()
+        }),
+        Err(_) => Vec::new(),
+    };
+    for path in SYSTEM_FONTS {
+        if let Ok(data) = std::fs::read(path) {
+            fonts.push((path.to_string(), data));
+        }
+    }
+    fonts
+}
+
+// === ТЕСТЫ ===
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Тест: запасные шрифты идут после шрифтов egui, негодные файлы пропускаются
+    #[test]
+    fn test_definitions() {
+        let default = egui::FontDefinitions::default();
+        let definitions = definitions(vec![("broken.ttf".to_string(), b"not a font".to_vec())]);
+        assert_eq!(definitions.font_data.len(), default.font_data.len());
+        assert_eq!(definitions.families, default.families);
+
+        let Some((name, data)) = load_system_fonts().into_iter().next() else { return };
+        let definitions = super::definitions(vec![(name.clone(), data)]);
+        assert_eq!(definitions.families[&egui::FontFamily::Proportional].last(), Some(&name));
+        assert_eq!(definitions.families[&egui::FontFamily::Monospace].last(), Some(&name));
+    }
+
+    /// Тест: из каталога берутся только файлы шрифтов
+    #[test]
+    fn test_load_dir() {
+        let dir = std::env::temp_dir().join(format!("ttt_fonts_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("b.TTF"), b"b").unwrap();
+        std::fs::write(dir.join("a.otf"), b"a").unwrap();
+        std::fs::write(dir.join("readme.txt"), b"not a font").unwrap();
+
+        let names: Vec<String> = load_dir(&dir).unwrap().into_iter().map(|(name, _)| name).collect();
+        assert_eq!(names, vec!["a.otf".to_string(), "b.TTF".to_string()]);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
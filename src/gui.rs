@@ -1,412 +1,693 @@
 //! # Графический интерфейс для игры "Крестики-нолики"
-//! 
-//! Этот модуль содержит GUI версию игры с использованием egui.
-//! Предоставляет современный интерфейс с красивыми цветами и анимациями.
-//! 
+//!
+//! Этот модуль содержит настольную оболочку общего приложения
+//! [`rust_tic_tac_toe::app::App`]: поле, статус, матч, выбор сторон и языка
+//! те же, что в веб-версии. Оболочка добавляет то, что есть только на
+//! компьютере: игру по сети, лобби игрового сервера, внешние движки и
+//! статистику сыгранных партий.
+//!
 //! ## Использование
 //! ```rust
 //! use rust_tic_tac_toe::gui::TicTacToeGUI;
-//! 
+//!
 //! let mut gui = TicTacToeGUI::new();
 //! gui.run()?;
 //! ```
-//! 
+//!
 //! ## Особенности
 //! - Современный дизайн с темной темой
 //! - Адаптивный интерфейс
 //! - Управление мышью
 //! - Красивые цвета для X и O
+//! - Статистика сыгранных партий
+//! - Матчи из нескольких партий со счетом
+//! - Языки справа налево и запасные шрифты для китайского, японского и арабского
+//! - Язык системы при первом запуске; выбранный язык запоминается между запусками
+//! - Заголовок окна на выбранном языке и имена игроков в сообщениях
+//! - Светлая, темная, высококонтрастная тема и тема для дальтоников; свои темы
+//!   из файлов `*.toml` каталога `TIC_TAC_TOE_THEMES`
+//! - Анимация ходов, выигрышной линии и победы; настройка "меньше движения"
 
 use eframe::egui;
-use crate::TicTacToe;
-use rust_tic_tac_toe::{Localization, Language};
+use rust_tic_tac_toe::{Localization, Language, MessageKey, Player, PlayerNames};
+use rust_tic_tac_toe::{bidi, fonts, i18n};
+use rust_tic_tac_toe::agent::{AgentKind, Background, EngineAgent};
+use rust_tic_tac_toe::engine::Engine;
+use rust_tic_tac_toe::app::{self, App, GameAction, Remote, Session, Shell};
+use rust_tic_tac_toe::net::{self, NetEvent, NetGame, NetHost};
+use rust_tic_tac_toe::online::{ClientMessage, Connection, OnlineState, DEFAULT_PORT};
+use rust_tic_tac_toe::protocol::ExternalEngine;
+use rust_tic_tac_toe::rating::{RatingSystem, Ratings};
+use rust_tic_tac_toe::stats::{self, JsonStatsStore, MemoryStatsStore, Participant, Stats};
+use std::sync::mpsc::{self, Receiver};
+use std::time::Duration;
 
-/// Основная структура графического интерфейса игры
-/// 
-/// Содержит игровую логику, настройки отображения и цветовую схему.
-/// Обрабатывает пользовательский ввод и отрисовку игрового поля.
+/// Настольная версия игры
+///
+/// Содержит состояние, общее с веб-версией, и настольную оболочку.
+/// Настраивается до запуска окна через [`TicTacToeGUI::run`].
 pub struct TicTacToeGUI {
-    /// Игровая логика
-    game: TicTacToe,
-    /// Размер одной клетки игрового поля в пикселях
-    cell_size: f32,
-    /// Цветовая схема интерфейса
-    colors: GameColors,
-    /// Локализация
-    localization: Localization,
+    /// Состояние, общее с веб-версией
+    session: Session,
+    /// Сеть, сервер, внешние движки и статистика
+    shell: NativeShell,
 }
 
-/// Цветовая схема для графического интерфейса
-/// 
-/// Использует темную тему в стиле популярных редакторов кода
-/// с яркими акцентами для игровых элементов.
-struct GameColors {
-    /// Цвет фона игрового поля
-    background: egui::Color32,
-    /// Цвет сетки игрового поля
-    grid: egui::Color32,
-    /// Цвет символа X (красный)
-    x_color: egui::Color32,
-    /// Цвет символа O (зеленый)
-    o_color: egui::Color32,
-    /// Цвет выделения и заголовков (оранжевый)
-    highlight: egui::Color32,
-    /// Цвет основного текста (белый)
-    text: egui::Color32,
-}
-
-impl Default for GameColors {
-    /// Создает цветовую схему по умолчанию
-    /// 
-    /// Использует темную тему с яркими акцентами:
-    /// - Темно-серый фон
-    /// - Светло-серые линии сетки
-    /// - Красный для X
-    /// - Зеленый для O
-    /// - Оранжевый для выделения
-    fn default() -> Self {
-        Self {
-            background: egui::Color32::from_rgb(40, 44, 52),    // Темно-серый
-            grid: egui::Color32::from_rgb(68, 71, 90),          // Светло-серый
-            x_color: egui::Color32::from_rgb(255, 85, 85),      // Красный
-            o_color: egui::Color32::from_rgb(80, 250, 123),     // Зеленый
-            highlight: egui::Color32::from_rgb(255, 184, 108),  // Оранжевый
-            text: egui::Color32::from_rgb(248, 248, 242),       // Белый
-        }
-    }
+/// Настольная оболочка: то, чего нет в веб-версии
+struct NativeShell {
+    /// Язык задан явно (`--lang`), сохраненный выбор его не заменяет
+    explicit_locale: bool,
+    /// Путь к исполняемому файлу внешнего движка
+    engine_path: String,
+    /// Ошибка запуска внешнего движка
+    engine_error: Option<String>,
+    /// Внешний движок, который запускается в фоне
+    engine_loading: Option<Receiver<std::io::Result<ExternalEngine>>>,
+    /// Сетевая игра (пока она есть, ходы идут через нее, а не через агентов)
+    network: Option<NetGame>,
+    /// Подключение, которое устанавливается в фоне
+    connecting: Option<Receiver<std::io::Result<NetGame>>>,
+    /// Адрес для создания игры или подключения
+    net_address: String,
+    /// Последнее сообщение о состоянии сети
+    net_status: Option<String>,
+    /// Вводимое сообщение чата
+    chat_input: String,
+    /// Последние сообщения чата
+    chat_log: Vec<String>,
+    /// Подключение к игровому серверу с лобби
+    server: Option<Connection>,
+    /// Состояние лобби и партии на сервере
+    online: OnlineState,
+    /// Адрес игрового сервера
+    server_url: String,
+    /// Контроль времени для новой заявки (`5+3`, пусто - без часов)
+    seek_time_control: String,
+    /// Код комнаты для просмотра
+    watch_code: String,
+    /// Статистика сыгранных партий
+    stats: Stats,
+    /// Рейтинги по сохраненным партиям
+    ratings: Ratings,
+    /// Ошибка открытия или записи статистики
+    stats_error: Option<String>,
+    /// Время начала текущей партии
+    game_started_ms: u64,
 }
 
 impl TicTacToeGUI {
     /// Создает новый графический интерфейс
-    /// 
+    ///
     /// Инициализирует игру с настройками по умолчанию:
-    /// - Размер клетки: 80 пикселей
+    /// - Язык системы, если он поддерживается, иначе английский
     /// - Стандартная цветовая схема
     /// - Новая игра
+    /// - Обе стороны играют люди
     pub fn new() -> Self {
-        Self {
-            game: TicTacToe::new(),
-            cell_size: 80.0,
-            colors: GameColors::default(),
-            localization: Localization::new(Language::English), // По умолчанию английский
+        let mut localization = Localization::new(Language::English); // Если язык системы не поддерживается
+        let locales_error = localization.load_env_dir().err();
+        if let Some(locale) = i18n::env_locale() {
+            localization.select_preferred([locale.as_str()]);
+        }
+        // Ошибку загрузки переводов сообщаем уже на языке системы
+        if let Some(e) = locales_error {
+            eprintln!("{}", localization.format(MessageKey::ConsoleLocalesError, &[("error", e.to_string().into())]));
         }
+        let mut session = Session::new(localization);
+        if let Err(e) = session.themes_mut().load_env_dir() {
+            let message = session.localization().format(MessageKey::ConsoleThemesError, &[("error", e.to_string().into())]);
+            eprintln!("{}", message);
+        }
+        Self { session, shell: NativeShell::new() }
+    }
+
+    /// Назначает агентов для сторон X и O
+    pub fn set_sides(&mut self, x: AgentKind, o: AgentKind) {
+        self.session.set_sides(x, o);
+    }
+
+    /// Выбирает язык интерфейса по коду; он важнее сохраненного выбора
+    pub fn set_locale(&mut self, locale: &str) {
+        let localization = self.session.localization_mut();
+        if !localization.select_preferred([locale]) {
+            localization.set_locale(locale);
+        }
+        self.shell.explicit_locale = true;
+    }
+
+    /// Создает сетевую игру или подключается к ней в фоновом потоке
+    ///
+    /// ## Параметры
+    /// - `host`: `true` - ждать соперника на адресе, `false` - подключиться к адресу
+    /// - `addr`: адрес вида `host:port`
+    /// - `name`: имя локального игрока
+    pub fn connect(&mut self, host: bool, addr: &str, name: &str) {
+        self.shell.connect(&self.session, host, addr, name);
     }
 
     /// Запускает графический интерфейс
-    /// 
+    ///
     /// Создает окно с заданными параметрами и запускает главный цикл.
-    /// 
+    ///
     /// ## Параметры окна
     /// - Начальный размер: 400x500 пикселей
     /// - Минимальный размер: 350x450 пикселей
-    /// - Заголовок: "Крестики-нолики"
-    pub fn run(&mut self) -> Result<(), eframe::Error> {
+    /// - Заголовок: название игры на выбранном языке
+    pub fn run(self) -> Result<(), eframe::Error> {
         let options = eframe::NativeOptions {
             viewport: egui::ViewportBuilder::default()
                 .with_inner_size([400.0, 500.0])
                 .with_min_inner_size([350.0, 450.0])
-                .with_title("Крестики-нолики"),
+                .with_title(self.session.localization().get_logical_text(MessageKey::GameTitle)),
+            // Тема "как в системе" узнает о светлой или темной теме системы
+            follow_system_theme: true,
             ..Default::default()
         };
-        
-        let app = self.clone();
+
+        // Сетевое подключение нельзя скопировать, поэтому передаем само состояние
+        let TicTacToeGUI { mut session, shell } = self;
         eframe::run_native(
+            // Имя приложения задает каталог сохраненных настроек и не переводится
             "Крестики-нолики",
             options,
-            Box::new(|_cc| Box::new(app)),
+            Box::new(move |cc| {
+                // Запасные шрифты для китайского, японского, арабского и других языков
+                cc.egui_ctx.set_fonts(fonts::definitions(fonts::load_system_fonts()));
+                if !shell.explicit_locale {
+                    session.restore_language(cc.storage);
+                }
+                session.restore_appearance(cc.storage);
+                Box::new(App::new(session, shell))
+            }),
         )
     }
+}
+
+impl NativeShell {
+    /// Создает оболочку без подключений; статистика открывается из файла
+    fn new() -> Self {
+        let (stats, stats_error) = open_stats();
+        Self {
+            explicit_locale: false,
+            engine_path: String::new(),
+            engine_error: None,
+            engine_loading: None,
+            network: None,
+            connecting: None,
+            net_address: "127.0.0.1:7878".to_string(),
+            net_status: None,
+            chat_input: String::new(),
+            chat_log: Vec::new(),
+            server: None,
+            online: OnlineState::default(),
+            server_url: format!("ws://127.0.0.1:{}", DEFAULT_PORT),
+            seek_time_control: String::new(),
+            watch_code: String::new(),
+            ratings: stats.ratings(RatingSystem::default()),
+            stats,
+            stats_error,
+            game_started_ms: stats::unix_time_ms(),
+        }
+    }
+
+    /// Запускает внешний движок в фоновом потоке
+    ///
+    /// Рукопожатие может длиться до [`protocol::HANDSHAKE_TIMEOUT`](rust_tic_tac_toe::protocol::HANDSHAKE_TIMEOUT), поэтому окно
+    /// не ждет его, а забирает движок в [`NativeShell::poll_engine`].
+    fn load_external_engine(&mut self) {
+        let (sender, receiver) = mpsc::channel();
+        let path = self.engine_path.trim().to_string();
+        std::thread::spawn(move || {
+            let _ = sender.send(ExternalEngine::spawn(&path, &[]));
+        });
+        self.engine_loading = Some(receiver);
+        self.engine_error = None;
+    }
 
-    /// Отрисовывает игровое поле
-    /// 
-    /// Создает интерактивную область для игрового поля размером 3x3 клетки.
-    /// Обрабатывает клики мыши и отрисовывает все элементы поля.
-    fn draw_board(&mut self, ui: &mut egui::Ui) {
-        let board_size = self.cell_size * 3.0;
-        
-        // Создаем область для отрисовки с поддержкой кликов
-        let (response, painter) = ui.allocate_painter(
-            egui::vec2(board_size, board_size),
-            egui::Sense::click()
-        );
-
-        // Рисуем фон игрового поля
-        painter.rect_filled(
-            egui::Rect::from_min_size(
-                response.rect.min,
-                egui::vec2(board_size, board_size)
-            ),
-            0.0, // Без скругления углов
-            self.colors.background,
-        );
-
-        // Рисуем сетку игрового поля
-        self.draw_grid(&painter, board_size, response.rect);
-
-        // Рисуем символы X и O
-        self.draw_symbols(&painter, response.rect);
-
-        // Обрабатываем клики мыши
-        if response.clicked() {
-            self.handle_click(&response);
+    /// Назначает запущенный внешний движок стороне O
+    fn poll_engine(&mut self, session: &mut Session) {
+        let Some(receiver) = &self.engine_loading else { return };
+        let Ok(result) = receiver.try_recv() else { return };
+        self.engine_loading = None;
+        match result {
+            Ok(engine) => {
+                let name = engine.name();
+                let agent = EngineAgent::new(Box::new(engine));
+                session.set_external_agent(Player::O, name, Box::new(Background::new(agent)));
+            }
+            Err(e) => {
+                self.engine_error = Some(session.localization().format(MessageKey::EngineLoadError, &[("error", e.to_string().into())]));
+            }
         }
     }
 
-    /// Отрисовывает сетку игрового поля
-    /// 
-    /// Рисует 4 линии, разделяющие поле на 9 клеток:
-    /// - 2 вертикальные линии
-    /// - 2 горизонтальные линии
-    fn draw_grid(&self, painter: &egui::Painter, board_size: f32, rect: egui::Rect) {
-        let stroke = egui::Stroke::new(3.0, self.colors.grid);
-        
-        // Вертикальные линии (разделяют столбцы)
-        for i in 1..3 {
-            let x = rect.min.x + i as f32 * self.cell_size;
-            painter.line_segment(
-                [egui::pos2(x, rect.min.y), egui::pos2(x, rect.min.y + board_size)],
-                stroke,
-            );
+    /// Создает сетевую игру или подключается к ней в фоновом потоке
+    fn connect(&mut self, session: &Session, host: bool, addr: &str, name: &str) {
+        self.net_address = addr.to_string();
+        let (sender, receiver) = mpsc::channel();
+        let (addr, name, rules) = (addr.to_string(), name.to_string(), session.game().rules());
+        std::thread::spawn(move || {
+            let result = if host {
+                NetHost::bind(addr.as_str(), &name, rules).and_then(NetHost::accept)
+            } else {
+                net::join(addr.as_str(), &name)
+            };
+            let _ = sender.send(result);
+        });
+        self.network = None;
+        self.connecting = Some(receiver);
+        self.net_status = Some(session.localization().get_text(MessageKey::Connecting));
+        self.chat_log.clear();
+    }
+
+    /// Обрабатывает подключение и события сетевой игры
+    fn poll_network(&mut self, session: &mut Session) {
+        let localization = session.localization();
+        if let Some(receiver) = &self.connecting {
+            if let Ok(result) = receiver.try_recv() {
+                self.connecting = None;
+                match result {
+                    Ok(net) => {
+                        self.net_status = None;
+                        self.network = Some(net);
+                    }
+                    Err(e) => {
+                        self.net_status = Some(localization.format(MessageKey::NetError, &[("error", e.to_string().into())]));
+                    }
+                }
+            }
         }
-        
-        // Горизонтальные линии (разделяют строки)
-        for i in 1..3 {
-            let y = rect.min.y + i as f32 * self.cell_size;
-            painter.line_segment(
-                [egui::pos2(rect.min.x, y), egui::pos2(rect.min.x + board_size, y)],
-                stroke,
-            );
+
+        let Some(net) = &mut self.network else { return };
+        for event in net.poll() {
+            match event {
+                NetEvent::Chat(text) => self.chat_log.push(format!("{}: {}", net.peer_name(), text)),
+                NetEvent::PeerResigned => self.net_status = Some(localization.get_text(MessageKey::PeerResigned)),
+                NetEvent::DrawDeclined => self.net_status = Some(localization.get_text(MessageKey::DrawDeclined)),
+                NetEvent::DrawOffered | NetEvent::DrawAgreed | NetEvent::PeerAborted => self.net_status = None,
+                NetEvent::RematchRequested => self.net_status = Some(localization.get_text(MessageKey::RematchRequested)),
+                NetEvent::RematchStarted | NetEvent::PeerMove(_) => self.net_status = None,
+                NetEvent::Disconnected => self.net_status = Some(localization.get_text(MessageKey::PeerDisconnected)),
+                NetEvent::PeerError(reason) | NetEvent::IllegalMessage(reason) => {
+                    self.net_status = Some(localization.format(MessageKey::NetError, &[("error", reason.as_str().into())]));
+                }
+            }
         }
+        session.set_game(net.game().clone());
     }
 
-    /// Отрисовывает символы X и O на игровом поле
-    /// 
-    /// Проходит по всем клеткам доски и рисует символы игроков
-    /// в центре соответствующих клеток.
-    fn draw_symbols(&self, painter: &egui::Painter, rect: egui::Rect) {
-        for (i, cell) in self.game.get_board().iter().enumerate() {
-            if let Some(player) = cell {
-                // Вычисляем позицию клетки (строка и столбец)
-                let (row, col) = (i / 3, i % 3);
-                
-                // Вычисляем центр клетки для размещения символа
-                let center = egui::pos2(
-                    rect.min.x + col as f32 * self.cell_size + self.cell_size / 2.0,
-                    rect.min.y + row as f32 * self.cell_size + self.cell_size / 2.0,
-                );
-                
-                // Рисуем соответствующий символ
-                match player {
-                    rust_tic_tac_toe::Player::X => self.draw_x(painter, center),
-                    rust_tic_tac_toe::Player::O => self.draw_o(painter, center),
+    /// Отрисовывает панель сетевой игры
+    fn draw_network(&mut self, ui: &mut egui::Ui, session: &mut Session) {
+        let rtl = session.localization().is_rtl();
+        let colors = *session.colors();
+        bidi::horizontal(ui, rtl, |ui| {
+            ui.label(egui::RichText::new(session.localization().get_text(MessageKey::Network))
+                .color(colors.text)
+                .size(16.0));
+            ui.add(egui::TextEdit::singleline(&mut self.net_address).desired_width(120.0));
+            if ui.button(session.localization().get_text(MessageKey::HostGame)).clicked() {
+                let addr = self.net_address.clone();
+                let name = session.player_name().to_string();
+                self.connect(session, true, &addr, &name);
+            }
+            if ui.button(session.localization().get_text(MessageKey::JoinGame)).clicked() {
+                let addr = self.net_address.clone();
+                let name = session.player_name().to_string();
+                self.connect(session, false, &addr, &name);
+            }
+        });
+
+        let names = self.remote(session).map(|remote| remote.names).unwrap_or_default();
+        if let Some(net) = &mut self.network {
+            let localization = session.localization();
+            let opponent = localization.format(MessageKey::NetOpponent, &[("name", net.peer_name().into())]);
+            let side = localization.format(MessageKey::NetYouPlay, &[("player", net.local_player().mark(localization).into())]);
+            ui.label(egui::RichText::new(format!("{} · {}", opponent, side)).color(colors.text));
+
+            let action = app::game_action_buttons(ui, localization, &colors, &names, net.game(), Some(net.local_player()));
+            let result = match action {
+                Some(GameAction::Resign) => net.resign().map_err(net::NetError::from),
+                Some(GameAction::OfferDraw) => net.offer_draw(),
+                Some(GameAction::AcceptDraw) => net.accept_draw(),
+                Some(GameAction::DeclineDraw) => net.decline_draw(),
+                Some(GameAction::Abort) => net.abort(),
+                None => Ok(()),
+            };
+            match result {
+                Ok(()) if action == Some(GameAction::Resign) => {
+                    self.net_status = Some(localization.get_text(MessageKey::YouResigned));
                 }
+                Ok(()) => {}
+                Err(e) => self.net_status = Some(localization.format(MessageKey::NetError, &[("error", e.to_string().into())])),
+            }
+            if ui.add_enabled(net.is_finished(), egui::Button::new(localization.get_text(MessageKey::Rematch))).clicked()
+                && net.request_rematch().unwrap_or(false)
+            {
+                self.net_status = None;
             }
+
+            for line in self.chat_log.iter().rev().take(3).rev() {
+                ui.label(egui::RichText::new(line).color(colors.text).size(12.0));
+            }
+            bidi::horizontal(ui, rtl, |ui| {
+                ui.add(egui::TextEdit::singleline(&mut self.chat_input).desired_width(180.0));
+                if ui.button(localization.get_text(MessageKey::Send)).clicked() && !self.chat_input.is_empty() {
+                    if net.chat(&self.chat_input).is_ok() {
+                        self.chat_log.push(format!("> {}", self.chat_input));
+                    }
+                    self.chat_input.clear();
+                }
+            });
+            session.set_game(net.game().clone());
+        }
+
+        if let Some(status) = &self.net_status {
+            ui.label(egui::RichText::new(status).color(colors.highlight));
         }
     }
 
-    /// Отрисовывает символ X
-    /// 
-    /// Рисует две пересекающиеся линии, образующие крест.
-    /// Размер символа составляет 30% от размера клетки.
-    fn draw_x(&self, painter: &egui::Painter, center: egui::Pos2) {
-        let size = self.cell_size * 0.3;
-        let stroke = egui::Stroke::new(4.0, self.colors.x_color);
-        
-        // Первая диагональ (слева сверху вправо вниз)
-        painter.line_segment(
-            [egui::pos2(center.x - size, center.y - size), egui::pos2(center.x + size, center.y + size)],
-            stroke,
-        );
-        
-        // Вторая диагональ (справа сверху влево вниз)
-        painter.line_segment(
-            [egui::pos2(center.x + size, center.y - size), egui::pos2(center.x - size, center.y + size)],
-            stroke,
-        );
+    /// Обрабатывает сообщения игрового сервера
+    fn poll_server(&mut self, session: &mut Session) {
+        let Some(server) = &self.server else { return };
+        for message in server.poll() {
+            self.online.apply(message);
+        }
+        if let Some(game) = self.online.game() {
+            session.set_game(game);
+        }
     }
 
-    /// Отрисовывает символ O
-    /// 
-    /// Рисует окружность с толщиной линии 4 пикселя.
-    /// Радиус составляет 25% от размера клетки.
-    fn draw_o(&self, painter: &egui::Painter, center: egui::Pos2) {
-        let radius = self.cell_size * 0.25;
-        let stroke = egui::Stroke::new(4.0, self.colors.o_color);
-        
-        // Рисуем окружность
-        painter.circle_stroke(center, radius, stroke);
+    /// Отрисовывает панель лобби игрового сервера
+    fn draw_lobby(&mut self, ui: &mut egui::Ui, session: &mut Session) {
+        let rtl = session.localization().is_rtl();
+        let colors = *session.colors();
+        let mut leave = false;
+        bidi::horizontal(ui, rtl, |ui| {
+            ui.label(egui::RichText::new(session.localization().get_text(MessageKey::Server))
+                .color(colors.text)
+                .size(16.0));
+            ui.add(egui::TextEdit::singleline(&mut self.server_url).desired_width(140.0));
+            if self.server.is_none() {
+                if ui.button(session.localization().get_text(MessageKey::Lobby)).clicked() {
+                    match Connection::connect(self.server_url.trim()) {
+                        Ok(server) => {
+                            server.send(ClientMessage::Lobby);
+                            self.server = Some(server);
+                            self.online = OnlineState::default();
+                        }
+                        Err(e) => self.online.error = Some(e.to_string()),
+                    }
+                }
+            } else {
+                leave = ui.button(session.localization().get_text(MessageKey::Exit)).clicked();
+            }
+        });
+        if leave {
+            self.server = None;
+            self.online = OnlineState::default();
+            session.reset_game();
+        }
+
+        let names = self.remote(session).map(|remote| remote.names).unwrap_or_default();
+        let localization = session.localization();
+        if let Some(server) = &self.server {
+            match (&self.online.seat, &self.online.watching) {
+                (Some((code, player)), _) => {
+                    let side = localization.format(MessageKey::NetYouPlay, &[("player", player.mark(localization).into())]);
+                    ui.label(egui::RichText::new(format!("{} · {}", code, side)).color(colors.text));
+                    self.draw_room_info(ui, session);
+                    // Действие применится, когда сервер пришлет новое состояние
+                    let seated = self.online.room.as_ref().is_some_and(|room| room.is_full()).then_some(*player);
+                    if let Some(action) = app::game_action_buttons(ui, localization, &colors, &names, session.game(), seated) {
+                        server.send(action.message());
+                    }
+                }
+                (None, Some(code)) => {
+                    // Режим зрителя: доска обновляется сервером, клики отключены
+                    ui.label(egui::RichText::new(localization.format(MessageKey::Watching, &[("code", code.as_str().into())]))
+                        .color(colors.highlight));
+                    self.draw_room_info(ui, session);
+                }
+                (None, None) => {
+                    if self.online.seeks.is_empty() {
+                        ui.label(egui::RichText::new(localization.get_text(MessageKey::NoSeeks)).color(colors.text));
+                    }
+                    for seek in &self.online.seeks {
+                        bidi::horizontal(ui, rtl, |ui| {
+                            let time_control = seek.time_control.map(|tc| tc.to_string()).unwrap_or_default();
+                            ui.label(egui::RichText::new(format!("{} ({}) {} {}", seek.name, seek.rating, seek.rules, time_control))
+                                .color(colors.text));
+                            if self.online.my_seek != Some(seek.id) && ui.button(localization.get_text(MessageKey::Accept)).clicked() {
+                                server.send(ClientMessage::Accept { id: seek.id, name: session.player_name().to_string() });
+                            }
+                        });
+                    }
+                    let rules = session.game().rules();
+                    bidi::horizontal(ui, rtl, |ui| {
+                        ui.label(session.localization().get_text(MessageKey::PlayerName));
+                        ui.add(egui::TextEdit::singleline(session.player_name_mut()).desired_width(70.0));
+                        ui.label(session.localization().get_text(MessageKey::TimeControl));
+                        ui.add(egui::TextEdit::singleline(&mut self.seek_time_control).desired_width(40.0));
+                        if self.online.my_seek.is_some() {
+                            if ui.button(session.localization().get_text(MessageKey::CancelSeek)).clicked() {
+                                server.send(ClientMessage::CancelSeek);
+                                self.online.my_seek = None;
+                            }
+                        } else if ui.button(session.localization().get_text(MessageKey::Seek)).clicked() {
+                            let time_control = match self.seek_time_control.trim() {
+                                "" => Ok(None),
+                                text => text.parse().map(Some),
+                            };
+                            match time_control {
+                                Ok(time_control) => server.send(ClientMessage::Seek {
+                                    name: session.player_name().to_string(),
+                                    rules,
+                                    time_control,
+                                }),
+                                Err(e) => self.online.error = Some(e),
+                            }
+                        }
+                    });
+                    bidi::horizontal(ui, rtl, |ui| {
+                        ui.add(egui::TextEdit::singleline(&mut self.watch_code).desired_width(70.0));
+                        if ui.button(session.localization().get_text(MessageKey::Watch)).clicked() {
+                            server.send(ClientMessage::Watch { code: self.watch_code.clone() });
+                        }
+                    });
+                }
+            }
+        }
+
+        if let Some(error) = &self.online.error {
+            ui.label(egui::RichText::new(session.localization().format(MessageKey::NetError, &[("error", error.as_str().into())]))
+                .color(colors.x_color));
+        }
     }
 
-    /// Обрабатывает клики мыши по игровому полю
-    /// 
-    /// Преобразует координаты клика в позицию на игровом поле
-    /// и делает соответствующий ход, если это возможно.
-    /// Ходы не принимаются после окончания игры.
-    fn handle_click(&mut self, response: &egui::Response) {
-        // Проверяем, не закончена ли игра
-        if self.game.is_game_over() {
-            return; // Игра закончена - ходы не принимаются
+    /// Отрисовывает зрителей, контроль времени и ходы партии на сервере
+    fn draw_room_info(&self, ui: &mut egui::Ui, session: &Session) {
+        let Some(room) = &self.online.room else { return };
+        let (localization, colors) = (session.localization(), session.colors());
+        let mut info = localization.format(MessageKey::Spectators, &[("count", room.spectators.into())]);
+        if let Some(time_control) = room.time_control {
+            info.push_str(&format!(" · ⏱ {}", time_control));
+        }
+        ui.label(egui::RichText::new(info).color(colors.text).size(12.0));
+        if !room.moves.is_empty() {
+            ui.label(egui::RichText::new(localization.format(MessageKey::History, &[("moves", self.online.history().as_str().into())]))
+                .color(colors.text)
+                .size(12.0));
+        }
+    }
+
+    /// Участники текущей партии для статистики (`None` - партия не своя)
+    fn participants(&self, session: &Session) -> Option<[Participant; 2]> {
+        let me = Participant::human(session.player_name());
+        if let Some(net) = &self.network {
+            let peer = Participant::other(net.peer_name());
+            return Some(match net.local_player() {
+                Player::X => [me, peer],
+                Player::O => [peer, me],
+            });
+        }
+        if let Some((_, side)) = &self.online.seat {
+            let room = self.online.room.as_ref()?;
+            let name = |index: usize| room.players[index].as_deref().unwrap_or("?");
+            return Some(match side {
+                Player::X => [me, Participant::other(name(1))],
+                Player::O => [Participant::other(name(0)), me],
+            });
         }
-        
-        // Получаем позицию клика относительно игрового поля
-        let click_pos = response.hover_pos().unwrap();
-        let board_pos = click_pos - response.rect.min;
-        
-        // Вычисляем номер строки и столбца
-        let col = (board_pos.x / self.cell_size) as usize;
-        let row = (board_pos.y / self.cell_size) as usize;
-        
-        // Проверяем корректность позиции и делаем ход
-        if row < 3 && col < 3 {
-            let position = row * 3 + col;
-            self.game.make_move(position);
+        if self.online.watching.is_some() {
+            return None;
         }
+        Some([Player::X, Player::O].map(|player| match (session.external(player), session.side(player)) {
+            (Some(name), _) => Participant::other(name),
+            (None, AgentKind::Human) => Participant::human(session.player_name()),
+            (None, kind) => Participant::ai(kind),
+        }))
     }
 
-    /// Отрисовывает статус игры
-    /// 
-    /// Показывает текущего игрока или результат игры:
-    /// - "Ход игрока: X/O" - во время игры
-    /// - "Победитель: X/O!" - при победе
-    /// - "Ничья!" - при ничьей
-    fn draw_status(&self, ui: &mut egui::Ui) {
-        ui.add_space(20.0);
-        
-        if self.game.is_game_over() {
-            // Игра закончена - показываем результат
-            match self.game.get_winner() {
-                Some(player) => {
-                    let text = self.localization.get_text("winner").replace("{}", player.symbol());
-                    ui.heading(egui::RichText::new(text)
-                        .color(self.colors.highlight)
-                        .size(24.0));
+    /// Отрисовывает счет и рейтинг игрока: всего, по соперникам, вариантам и видам ИИ
+    fn draw_stats(&self, ui: &mut egui::Ui, session: &Session) {
+        let (localization, colors) = (session.localization(), session.colors());
+        egui::CollapsingHeader::new(egui::RichText::new(localization.get_text(MessageKey::Statistics))
+            .color(colors.text)
+            .size(16.0))
+            .show(ui, |ui| {
+                let name = session.player_name();
+                let total = localization.format(MessageKey::StatsTotal, &[("score", self.stats.score(name).to_string().into())]);
+                ui.label(egui::RichText::new(total).color(colors.highlight));
+                let rating = localization.format(MessageKey::StatsRating, &[("rating", self.ratings.rating(name).to_string().into())]);
+                ui.label(egui::RichText::new(rating).color(colors.highlight));
+                let sections = [
+                    (MessageKey::StatsOpponents, self.stats.by_opponent(name).into_iter()
+                        .map(|(opponent, score)| (format!("{} ({})", opponent, self.ratings.rating(&opponent).rounded()), score))
+                        .collect::<Vec<_>>()),
+                    (MessageKey::StatsVariants, self.stats.by_variant(name).into_iter().map(|(key, score)| (key.to_string(), score)).collect()),
+                    (MessageKey::StatsAi, self.stats.by_ai(name).into_iter()
+                        .map(|(key, score)| (localization.get_text(key.text_key()), score))
+                        .collect()),
+                ];
+                for (key, rows) in sections.iter().filter(|(_, rows)| !rows.is_empty()) {
+                    ui.label(egui::RichText::new(localization.get_text(*key)).color(colors.text));
+                    for (row, score) in rows {
+                        ui.label(egui::RichText::new(format!("  {}: {}", row, score)).color(colors.text).size(12.0));
+                    }
                 }
-                None => {
-                    ui.heading(egui::RichText::new(self.localization.get_text("draw"))
-                        .color(self.colors.highlight)
-                        .size(24.0));
+                if let Some(error) = &self.stats_error {
+                    ui.label(egui::RichText::new(localization.format(MessageKey::StatsError, &[("error", error.as_str().into())]))
+                        .color(colors.x_color));
+                }
+            });
+    }
+}
+
+impl Shell for NativeShell {
+    /// Применяет события сети и сервера; запоминает начало новой партии
+    fn poll(&mut self, session: &mut Session, ctx: &egui::Context) {
+        // Внешний движок: ждем конца рукопожатия
+        if self.engine_loading.is_some() {
+            self.poll_engine(session);
+            ctx.request_repaint_after(Duration::from_millis(100));
+        }
+
+        // Сетевая игра: применяем ходы соперника и ждем новые
+        if self.network.is_some() || self.connecting.is_some() {
+            self.poll_network(session);
+            ctx.request_repaint_after(Duration::from_millis(100));
+        }
+
+        if self.server.is_some() {
+            self.poll_server(session);
+            ctx.request_repaint_after(Duration::from_millis(100));
+        }
+
+        if session.game().moves().is_empty() && !session.game().is_game_over() {
+            self.game_started_ms = stats::unix_time_ms();
+        }
+    }
+
+    /// Сетевая партия, своя партия на сервере или просматриваемая
+    fn remote(&self, session: &Session) -> Option<Remote> {
+        if let Some(net) = &self.network {
+            let mut names = PlayerNames::default();
+            names.set(net.local_player(), session.names().name(net.local_player()));
+            names.set(net.local_player().opponent(), net.peer_name());
+            return Some(Remote { names, local_turn: net.is_local_turn(), remaining: None, running: None });
+        }
+        if self.online.seat.is_none() && self.online.watching.is_none() {
+            return None;
+        }
+        let room = self.online.room.as_ref();
+        let name = |index: usize| room.and_then(|room| room.players[index].clone()).unwrap_or_default();
+        let remaining = [Player::X, Player::O].map(|player| self.online.clock_remaining(player));
+        Some(Remote {
+            names: PlayerNames::new(&name(0), &name(1)),
+            local_turn: self.online.is_local_turn(),
+            remaining: remaining[0].zip(remaining[1]).map(|(x, o)| [x, o]),
+            running: room.and_then(|room| room.clock).and_then(|clock| clock.running),
+        })
+    }
+
+    fn send_move(&mut self, session: &mut Session, position: usize) {
+        match &mut self.network {
+            Some(net) => {
+                if net.send_move(position).is_ok() {
+                    session.set_game(net.game().clone());
+                }
+            }
+            // Ход применится, когда сервер пришлет новое состояние
+            None => {
+                if let (Some(server), Some(message)) = (&self.server, self.online.move_message(position)) {
+                    server.send(message);
                 }
             }
-        } else {
-            // Игра продолжается - показываем текущего игрока
-            let text = self.localization.get_text("current_player_turn").replace("{}", self.game.current_player_symbol());
-            ui.heading(egui::RichText::new(text)
-                .color(self.colors.text)
-                .size(20.0));
         }
     }
 
-    /// Отрисовывает элементы управления
-    /// 
-    /// Создает кнопки для управления игрой:
-    /// - "Новая игра" - сбрасывает игру
-    /// - "Выход" - закрывает приложение
-    fn draw_controls(&mut self, ui: &mut egui::Ui) {
-        ui.add_space(20.0);
-        
-        // Переключатель языка
-        ui.horizontal(|ui| {
-            ui.label(egui::RichText::new(self.localization.get_text("language_switch"))
-                .color(self.colors.text)
+    /// Внешний движок, игра по сети и лобби игрового сервера
+    fn draw_panels(&mut self, ui: &mut egui::Ui, session: &mut Session) {
+        let rtl = session.localization().is_rtl();
+        let colors = *session.colors();
+
+        // Внешний движок в качестве соперника
+        let mut load = false;
+        bidi::horizontal(ui, rtl, |ui| {
+            ui.label(egui::RichText::new(session.localization().get_text(MessageKey::ExternalEngine))
+                .color(colors.text)
                 .size(16.0));
-            
-            if ui.button(egui::RichText::new(self.localization.get_text("russian"))
-                .color(if self.localization.language == Language::Russian { self.colors.highlight } else { self.colors.text })
-                .size(14.0))
-                .clicked() {
-                self.localization.language = Language::Russian;
-            }
-            
-            if ui.button(egui::RichText::new(self.localization.get_text("english"))
-                .color(if self.localization.language == Language::English { self.colors.highlight } else { self.colors.text })
-                .size(14.0))
-                .clicked() {
-                self.localization.language = Language::English;
+            ui.add(egui::TextEdit::singleline(&mut self.engine_path).desired_width(120.0));
+            let button = egui::Button::new(session.localization().get_text(MessageKey::LoadEngine));
+            load = ui.add_enabled(self.engine_loading.is_none(), button).clicked();
+            if self.engine_loading.is_some() {
+                ui.spinner();
             }
         });
-        
-        ui.add_space(10.0);
-        
-        // Кнопка "Новая игра"
-        if ui.button(egui::RichText::new(self.localization.get_text("new_game"))
-            .color(self.colors.text)
-            .size(16.0))
-            .clicked() {
-            self.game.reset();
+        if load {
+            self.load_external_engine();
         }
-        
-        // Кнопка "Выход"
-        if ui.button(egui::RichText::new(self.localization.get_text("exit"))
-            .color(self.colors.text)
-            .size(16.0))
-            .clicked() {
-            std::process::exit(0);
+        if let Some(error) = &self.engine_error {
+            ui.label(egui::RichText::new(error).color(colors.x_color));
         }
-    }
-}
 
-/// Реализация главного цикла приложения
-/// 
-/// Обрабатывает обновление интерфейса, отрисовку всех элементов
-/// и взаимодействие с пользователем.
-impl eframe::App for TicTacToeGUI {
-    /// Основной метод обновления интерфейса
-    /// 
-    /// Вызывается каждый кадр для отрисовки интерфейса.
-    /// Создает центральную панель с игровым полем и элементами управления.
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        egui::CentralPanel::default().show(ctx, |ui| {
-            // Центрируем все элементы интерфейса
-            ui.vertical_centered(|ui| {
-                // Заголовок игры
-                ui.heading(egui::RichText::new("Крестики-нолики")
-                    .color(self.colors.highlight)
-                    .size(28.0));
-                
-                ui.add_space(20.0);
-                
-                // Игровое поле
-                self.draw_board(ui);
-                
-                // Статус игры
-                self.draw_status(ui);
-                
-                // Элементы управления
-                self.draw_controls(ui);
-            });
-        });
+        // Игра по сети
+        self.draw_network(ui, session);
+
+        // Лобби игрового сервера
+        self.draw_lobby(ui, session);
     }
-}
 
-// === РЕАЛИЗАЦИЯ КЛОНИРОВАНИЯ ===
+    /// Статистика и кнопка "Выход"
+    fn draw_footer(&mut self, ui: &mut egui::Ui, session: &mut Session) {
+        self.draw_stats(ui, session);
 
-impl Clone for TicTacToeGUI {
-    /// Создает копию графического интерфейса
-    /// 
-    /// Требуется для egui, который может создавать несколько экземпляров
-    /// приложения для разных окон или контекстов.
-    fn clone(&self) -> Self {
-        Self {
-            game: self.game.clone(),
-            cell_size: self.cell_size,
-            colors: self.colors.clone(),
-            localization: self.localization.clone(),
+        if ui.button(egui::RichText::new(session.localization().get_text(MessageKey::Exit))
+            .color(session.colors().text)
+            .size(16.0))
+            .clicked() {
+            // Закрытие окна, а не выход из процесса: eframe успеет вызвать `App::save`
+            ui.ctx().send_viewport_cmd(egui::ViewportCommand::Close);
         }
     }
-}
 
-impl Clone for GameColors {
-    /// Создает копию цветовой схемы
-    /// 
-    /// Простое копирование всех цветовых значений.
-    fn clone(&self) -> Self {
-        Self {
-            background: self.background,
-            grid: self.grid,
-            x_color: self.x_color,
-            o_color: self.o_color,
-            highlight: self.highlight,
-            text: self.text,
+    /// Сохраняет законченную партию в статистику
+    fn game_finished(&mut self, session: &Session) {
+        let Some([x, o]) = self.participants(session) else { return };
+        if let Err(e) = self.stats.record(session.game(), &x, &o, self.game_started_ms) {
+            self.stats_error = Some(e.to_string());
         }
+        self.ratings = self.stats.ratings(self.ratings.system());
+    }
+}
+
+/// Открывает файл статистики; при ошибке статистика ведется только в памяти
+///
+/// ## Возвращает
+/// Статистику и сообщение об ошибке открытия файла
+fn open_stats() -> (Stats, Option<String>) {
+    match JsonStatsStore::open_default() {
+        Ok(store) => (Stats::new(Box::new(store)), None),
+        Err(e) => (Stats::new(Box::new(MemoryStatsStore::default())), Some(e.to_string())),
     }
 }
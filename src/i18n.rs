@@ -0,0 +1,779 @@
+//! # Локализация
+//!
+//! Тексты интерфейса хранятся в каталогах [Fluent](https://projectfluent.org)
+//! (`locales/*.ftl`). Встроенные каталоги ([`Language::ALL`]) вшиты в
+//! программу при сборке; дополнительные загружаются во время работы из
+//! каталога [`LOCALES_ENV`] или через [`Localization::load_dir`]: файл
+//! `fr.ftl` добавляет язык `fr`, а файл `ru.ftl` заменяет отдельные строки
+//! встроенного русского.
+//!
+//! Строки запрашиваются по ключам [`MessageKey`], поэтому опечатка в ключе -
+//! ошибка компиляции. Полноту каталогов проверяет [`Localization::check`]:
+//! в каждом языке должны быть все ключи и не должно быть лишних. Встроенные
+//! каталоги проверяет тест, загруженные - команда `cargo run -- check-locales`.
+//!
+//! Если строки нет в выбранном языке, она ищется дальше по цепочке: язык
+//! без региона (`en-US` → `en`), затем [`DEFAULT_LOCALE`]. Строку, которой
+//! нет нигде, заменяет ее ключ, чтобы пропуск было видно.
+//!
+//! egui сам не умеет писать справа налево, поэтому графические версии
+//! включают [`Localization::set_visual_order`]: строки языков справа налево
+//! возвращаются уже в порядке отображения (см. [`crate::bidi`]).
+//!
+//! ## Использование
+//! ```rust
+//! use rust_tic_tac_toe::{Language, Localization, MessageKey};
+//!
+//! let mut localization = Localization::new(Language::English);
+//! assert_eq!(localization.format(MessageKey::Winner, &[("player", "X".into())]), "Winner: X!");
+//!
+//! localization.add_catalog("ru", "spectators = Зрителей: { $count }").unwrap();
+//! localization.set_language(Language::Russian);
+//! assert_eq!(localization.format(MessageKey::Spectators, &[("count", 3.into())]), "Зрителей: 3");
+//! assert_eq!(localization.get_text(MessageKey::Draw), "Ничья!");
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource};
+use fluent_syntax::ast::Entry;
+use unic_langid::LanguageIdentifier;
+
+use crate::bidi;
+
+pub use fluent_bundle::FluentValue;
+
+/// Язык, строки которого используются, если их нет в выбранном
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// Переменная окружения с каталогом дополнительных файлов `.ftl`
+pub const LOCALES_ENV: &str = "TIC_TAC_TOE_LOCALES";
+
+/// Ключ, под которым графические версии хранят выбранный язык
+/// (хранилище eframe в настольной версии, `localStorage` в браузере)
+pub const LANGUAGE_STORAGE_KEY: &str = "tic_tac_toe_language";
+
+/// Переменные окружения с языком по убыванию приоритета
+pub const LOCALE_VARS: [&str; 3] = ["LC_ALL", "LC_MESSAGES", "LANG"];
+
+/// Встроенные каталоги: код языка и текст `.ftl`
+const BUILTIN: [(&str, &str); 7] = [
+    ("ru", include_str!("../locales/ru.ftl")),
+    ("en", include_str!("../locales/en.ftl")),
+    ("de", include_str!("../locales/de.ftl")),
+    ("es", include_str!("../locales/es.ftl")),
+    ("zh", include_str!("../locales/zh.ftl")),
+    ("ja", include_str!("../locales/ja.ftl")),
+    ("ar", include_str!("../locales/ar.ftl")),
+];
+
+/// Встроенные языки
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Language {
+    /// Русский язык
+    Russian,
+    /// Английский язык
+    English,
+    /// Немецкий язык
+    German,
+    /// Испанский язык
+    Spanish,
+    /// Китайский язык (упрощенное письмо)
+    Chinese,
+    /// Японский язык
+    Japanese,
+    /// Арабский язык (пишется справа налево)
+    Arabic,
+}
+
+impl Language {
+    /// Все встроенные языки
+    pub const ALL: [Language; 7] = [
+        Language::Russian,
+        Language::English,
+        Language::German,
+        Language::Spanish,
+        Language::Chinese,
+        Language::Japanese,
+        Language::Arabic,
+    ];
+
+    /// Код языка (имя файла каталога без `.ftl`)
+    pub fn tag(&self) -> &'static str {
+        match self {
+            Language::Russian => "ru",
+            Language::English => "en",
+            Language::German => "de",
+            Language::Spanish => "es",
+            Language::Chinese => "zh",
+            Language::Japanese => "ja",
+            Language::Arabic => "ar",
+        }
+    }
+
+    /// Пишется ли язык справа налево
+    pub fn is_rtl(&self) -> bool {
+        bidi::is_rtl_locale(self.tag())
+    }
+
+    /// Встроенный язык по коду (`None` - язык не встроен)
+    pub fn from_tag(tag: &str) -> Option<Language> {
+        Language::ALL.into_iter().find(|language| language.tag() == tag)
+    }
+}
+
+/// Язык из переменных окружения [`LOCALE_VARS`]
+///
+/// Берется первая непустая переменная; `ru_RU.UTF-8` превращается в `ru-RU`.
+///
+/// ## Возвращает
+/// Код языка; `None`, если переменные не заданы или выбрана локаль `C`/`POSIX`
+pub fn env_locale() -> Option<String> {
+    LOCALE_VARS
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty())
+        .and_then(|value| posix_locale_tag(&value))
+}
+
+/// Код языка из имени локали POSIX (`язык_РЕГИОН.кодировка@модификатор`)
+fn posix_locale_tag(value: &str) -> Option<String> {
+    let name = value.split(['.', '@']).next().unwrap_or_default();
+    match name {
+        "" | "C" | "POSIX" => None,
+        name => Some(name.replace('_', "-")),
+    }
+}
+
+// === КЛЮЧИ ===
+
+/// Объявляет [`MessageKey`]: вариант и ключ строки в каталогах `.ftl`
+macro_rules! message_keys {
+    ($($name:ident = $id:literal,)*) => {
+        /// Ключ строки каталога
+        #[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+        pub enum MessageKey {
+            $($name,)*
+        }
+
+        impl MessageKey {
+            /// Все ключи
+            pub const ALL: &'static [MessageKey] = &[$(MessageKey::$name,)*];
+
+            /// Ключ строки в каталоге `.ftl`
+            pub fn id(&self) -> &'static str {
+                match self {
+                    $(MessageKey::$name => $id,)*
+                }
+            }
+        }
+    };
+}
+
+message_keys! {
+    LanguageName = "language_name",
+
+    // Игра
+    GameTitle = "game_title",
+    MarkX = "mark_x",
+    MarkO = "mark_o",
+    PlayerLabel = "player_label",
+    PlayerNamePlaceholder = "player_name_placeholder",
+    CurrentPlayerTurn = "current_player_turn",
+    Winner = "winner",
+    Draw = "draw",
+    NewGame = "new_game",
+    Exit = "exit",
+    SideAgent = "side_agent",
+    AgentHuman = "agent_human",
+    AgentThreat = "agent_threat",
+    AgentMcts = "agent_mcts",
+    LanguageSwitch = "language_switch",
+    ThemeSwitch = "theme_switch",
+    ThemeSystem = "theme_system",
+    ThemeDark = "theme_dark",
+    ThemeLight = "theme_light",
+    ThemeColorBlind = "theme_color_blind",
+    ThemeHighContrast = "theme_high_contrast",
+    ReduceMotion = "reduce_motion",
+    Celebration = "celebration",
+
+    // Внешний движок
+    ExternalEngine = "external_engine",
+    LoadEngine = "load_engine",
+    EngineLoadError = "engine_load_error",
+
+    // Игра по сети
+    Network = "network",
+    HostGame = "host_game",
+    JoinGame = "join_game",
+    Connecting = "connecting",
+    NetOpponent = "net_opponent",
+    NetYouPlay = "net_you_play",
+    NetError = "net_error",
+    PeerDisconnected = "peer_disconnected",
+    PeerResigned = "peer_resigned",
+    YouResigned = "you_resigned",
+    Resign = "resign",
+    Rematch = "rematch",
+    RematchRequested = "rematch_requested",
+    Send = "send",
+
+    // Лобби игрового сервера
+    Server = "server",
+    Lobby = "lobby",
+    NoSeeks = "no_seeks",
+    Accept = "accept",
+    Seek = "seek",
+    CancelSeek = "cancel_seek",
+    PlayerName = "player_name",
+    TimeControl = "time_control",
+    Watch = "watch",
+    Watching = "watching",
+    Spectators = "spectators",
+    History = "history",
+
+    // Окончание партии
+    TimeOut = "time_out",
+    OfferDraw = "offer_draw",
+    AcceptDraw = "accept_draw",
+    DeclineDraw = "decline_draw",
+    Abort = "abort",
+    DrawOffered = "draw_offered",
+    DrawDeclined = "draw_declined",
+    TerminationLine = "termination_line",
+    TerminationFullBoard = "termination_full_board",
+    TerminationResignation = "termination_resignation",
+    TerminationAgreement = "termination_agreement",
+    TerminationAbandonment = "termination_abandonment",
+    TerminationAborted = "termination_aborted",
+    TerminationAdjudication = "termination_adjudication",
+
+    // Статистика и матчи
+    Statistics = "statistics",
+    StatsTotal = "stats_total",
+    StatsRating = "stats_rating",
+    StatsOpponents = "stats_opponents",
+    StatsVariants = "stats_variants",
+    StatsAi = "stats_ai",
+    StatsError = "stats_error",
+    MatchFormat = "match_format",
+    MatchScore = "match_score",
+    MatchWinner = "match_winner",
+    MatchDrawn = "match_drawn",
+
+    // Консоль
+    ConsoleWelcome = "console_welcome",
+    ConsoleHowToMove = "console_how_to_move",
+    ConsoleCommands = "console_commands",
+    ConsoleNetCommands = "console_net_commands",
+    ConsoleGuiHint = "console_gui_hint",
+    ConsolePrompt = "console_prompt",
+    ConsoleYourTurn = "console_your_turn",
+    ConsolePeerTurn = "console_peer_turn",
+    ConsoleMove = "console_move",
+    ConsoleNoMove = "console_no_move",
+    ConsoleDrawOffered = "console_draw_offered",
+    ConsoleInvalidMove = "console_invalid_move",
+    ConsoleInvalidInput = "console_invalid_input",
+    ConsoleNotNow = "console_not_now",
+    ConsoleDrawAccepted = "console_draw_accepted",
+    ConsoleDrawRejected = "console_draw_rejected",
+    ConsolePlaysX = "console_plays_x",
+    ConsoleNewMatch = "console_new_match",
+    ConsolePlayAgain = "console_play_again",
+    ConsoleThanks = "console_thanks",
+    ConsoleQuit = "console_quit",
+    ConsoleWaitingPeer = "console_waiting_peer",
+    ConsoleConnectingTo = "console_connecting_to",
+    ConsoleWaitingRematch = "console_waiting_rematch",
+    ConsolePeerAborted = "console_peer_aborted",
+    ConsoleChat = "console_chat",
+    ConsolePeerError = "console_peer_error",
+    ConsoleMessageRejected = "console_message_rejected",
+    ConsoleLocalesError = "console_locales_error",
+    ConsoleThemesError = "console_themes_error",
+    ConsoleDefaultName = "console_default_name",
+    ConsoleTournamentError = "console_tournament_error",
+    TournamentRules = "tournament_rules",
+    TournamentElo = "tournament_elo",
+    ConsoleTournamentStart = "console_tournament_start",
+    ConsoleMissingValue = "console_missing_value",
+    ConsoleInvalidValue = "console_invalid_value",
+    ConsoleInvalidRules = "console_invalid_rules",
+    ConsoleUnknownOption = "console_unknown_option",
+    ConsoleUnknownEngine = "console_unknown_engine",
+    ConsoleTooFewEngines = "console_too_few_engines",
+    ConsoleEngineUsage = "console_engine_usage",
+    ConsoleProtocolError = "console_protocol_error",
+    ConsoleUnknownAgent = "console_unknown_agent",
+    ConsoleMissingAddress = "console_missing_address",
+    ConsoleMissingMatch = "console_missing_match",
+    ConsoleStartingGui = "console_starting_gui",
+    ConsoleGuiError = "console_gui_error",
+    ConsoleGuiFallback = "console_gui_fallback",
+    ConsoleStatsNotSaved = "console_stats_not_saved",
+    ConsoleLocalesDirError = "console_locales_dir_error",
+    ConsoleCatalogMissing = "console_catalog_missing",
+    ConsoleCatalogUnused = "console_catalog_unused",
+    ConsoleCatalogsComplete = "console_catalogs_complete",
+    AnswerYes = "answer_yes",
+    AnswerNo = "answer_no",
+}
+
+impl MessageKey {
+    /// Ключ по его строке в каталоге (`None` - такого ключа нет)
+    pub fn from_id(id: &str) -> Option<MessageKey> {
+        MessageKey::ALL.iter().copied().find(|key| key.id() == id)
+    }
+}
+
+impl fmt::Display for MessageKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.id())
+    }
+}
+
+/// Нарушение полноты каталога
+#[derive(Clone, Debug, PartialEq)]
+pub enum CatalogProblem {
+    /// В языке нет строки для ключа
+    Missing { locale: String, key: MessageKey },
+    /// В языке есть строка, которой нет среди ключей
+    Unused { locale: String, id: String },
+}
+
+impl CatalogProblem {
+    /// Описание нарушения на языке интерфейса
+    pub fn describe(&self, localization: &Localization) -> String {
+        let (key, locale, id) = match self {
+            CatalogProblem::Missing { locale, key } => (MessageKey::ConsoleCatalogMissing, locale, key.id()),
+            CatalogProblem::Unused { locale, id } => (MessageKey::ConsoleCatalogUnused, locale, id.as_str()),
+        };
+        localization.format(key, &[("locale", locale.as_str().into()), ("key", id.into())])
+    }
+}
+
+// === КАТАЛОГИ ===
+
+/// Каталог строк одного языка
+struct Catalog {
+    /// Тексты `.ftl` в порядке загрузки (поздние заменяют ранние строки)
+    sources: Vec<String>,
+    /// Ключи всех строк каталога
+    ids: BTreeSet<String>,
+    bundle: FluentBundle<FluentResource>,
+}
+
+impl Catalog {
+    /// Собирает каталог из текстов `.ftl`
+    fn build(locale: &str, sources: Vec<String>) -> Result<Self, String> {
+        let id: LanguageIdentifier = locale.parse().map_err(|e| format!("{}: {}", locale, e))?;
+        let mut bundle = FluentBundle::new_concurrent(vec![id]);
+        // Символы изоляции направления текста egui показывает как квадраты
+        bundle.set_use_isolating(false);
+        let mut ids = BTreeSet::new();
+        for source in &sources {
+            let resource = FluentResource::try_new(source.clone()).map_err(|(_, errors)| {
+                let errors: Vec<String> = errors.iter().map(|e| format!("{:?}", e.kind)).collect();
+                format!("{}: {}", locale, errors.join("; "))
+            })?;
+            for entry in resource.entries() {
+                if let Entry::Message(message) = entry {
+                    ids.insert(message.id.name.to_string());
+                }
+            }
+            bundle.add_resource_overriding(resource);
+        }
+        Ok(Self { sources, ids, bundle })
+    }
+
+    /// Форматирует строку (`None` - строки в каталоге нет)
+    fn format(&self, key: &str, args: Option<&FluentArgs>) -> Option<String> {
+        let pattern = self.bundle.get_message(key)?.value()?;
+        let mut errors = Vec::new();
+        Some(self.bundle.format_pattern(pattern, args, &mut errors).into_owned())
+    }
+}
+
+/// Структура для локализации
+#[derive(Clone)]
+pub struct Localization {
+    /// Текущий язык
+    locale: String,
+    /// Каталоги по кодам языков
+    catalogs: BTreeMap<String, Arc<Catalog>>,
+    /// Возвращать строки языков справа налево в порядке отображения
+    visual_order: bool,
+}
+
+impl Localization {
+    /// Создает локализацию со встроенными каталогами и указанным языком
+    pub fn new(language: Language) -> Self {
+        let catalogs = BUILTIN
+            .iter()
+            .map(|(locale, source)| {
+                let catalog = Catalog::build(locale, vec![source.to_string()]).expect("встроенный каталог");
+                (locale.to_string(), Arc::new(catalog))
+            })
+            .collect();
+        Self { locale: language.tag().to_string(), catalogs, visual_order: false }
+    }
+
+    /// Текущий язык (код)
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    /// Текущий язык, если он встроенный
+    pub fn language(&self) -> Option<Language> {
+        Language::from_tag(&self.locale)
+    }
+
+    /// Выбирает встроенный язык
+    pub fn set_language(&mut self, language: Language) {
+        self.locale = language.tag().to_string();
+    }
+
+    /// Выбирает язык по коду; строки, которых у него нет, берутся по цепочке языков
+    pub fn set_locale(&mut self, locale: &str) {
+        self.locale = locale.to_string();
+    }
+
+    /// Ближайший язык, для которого есть каталог
+    ///
+    /// ## Параметры
+    /// - `requested`: код языка, возможно с регионом (`de-AT`, `pt_BR`)
+    ///
+    /// ## Возвращает
+    /// Сам язык, если для него есть каталог, иначе язык без региона;
+    /// `None` - подходящего каталога нет
+    pub fn supported_locale(&self, requested: &str) -> Option<String> {
+        let requested = requested.trim().replace('_', "-");
+        let language = requested.split('-').next().unwrap_or_default().to_lowercase();
+        [requested, language].into_iter().find(|locale| self.catalogs.contains_key(locale))
+    }
+
+    /// Выбирает первый язык из списка предпочтений, для которого есть каталог
+    ///
+    /// ## Возвращает
+    /// `true`, если язык выбран; иначе текущий язык не меняется
+    ///
+    /// ## Пример
+    /// ```rust
+    /// use rust_tic_tac_toe::{Language, Localization};
+    ///
+    /// let mut localization = Localization::new(Language::English);
+    /// assert!(localization.select_preferred(["pt-BR", "de-CH", "ru"]));
+    /// assert_eq!(localization.locale(), "de");
+    /// ```
+    pub fn select_preferred<'a>(&mut self, preferred: impl IntoIterator<Item = &'a str>) -> bool {
+        match preferred.into_iter().find_map(|locale| self.supported_locale(locale)) {
+            Some(locale) => {
+                self.locale = locale;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Пишется ли текущий язык справа налево
+    pub fn is_rtl(&self) -> bool {
+        bidi::is_rtl_locale(&self.locale)
+    }
+
+    /// Включает вывод строк языков справа налево в порядке отображения
+    ///
+    /// Нужен интерфейсам, которые рисуют текст слева направо без
+    /// перестановки (egui). Консоль оставляет логический порядок: терминал
+    /// переставляет символы сам.
+    pub fn set_visual_order(&mut self, enabled: bool) {
+        self.visual_order = enabled;
+    }
+
+    /// Строка для вывода на языке `locale`
+    fn display(&self, locale: &str, text: String) -> String {
+        if self.visual_order && bidi::is_rtl_locale(locale) {
+            bidi::visual_order(&text)
+        } else {
+            text
+        }
+    }
+
+    /// Коды языков, для которых есть каталоги
+    pub fn locales(&self) -> impl Iterator<Item = &str> {
+        self.catalogs.keys().map(String::as_str)
+    }
+
+    /// Название языка на нем самом (строка `language_name` его каталога)
+    pub fn language_name(&self, locale: &str) -> String {
+        self.catalogs
+            .get(locale)
+            .and_then(|catalog| catalog.format(MessageKey::LanguageName.id(), None))
+            .map(|name| self.display(locale, name))
+            .unwrap_or_else(|| locale.to_string())
+    }
+
+    /// Добавляет строки языка из текста `.ftl`
+    ///
+    /// Строки с теми же ключами заменяют уже загруженные.
+    ///
+    /// ## Возвращает
+    /// `Err` с описанием, если текст не разобран; каталог тогда не меняется
+    pub fn add_catalog(&mut self, locale: &str, source: &str) -> Result<(), String> {
+        let mut sources = self.catalogs.get(locale).map(|catalog| catalog.sources.clone()).unwrap_or_default();
+        sources.push(source.to_string());
+        let catalog = Catalog::build(locale, sources)?;
+        self.catalogs.insert(locale.to_string(), Arc::new(catalog));
+        Ok(())
+    }
+
+    /// Загружает все файлы `*.ftl` каталога; имя файла - код языка
+    ///
+    /// ## Возвращает
+    /// Коды загруженных языков
+    pub fn load_dir(&mut self, dir: impl AsRef<Path>) -> io::Result<Vec<String>> {
+        let mut loaded = Vec::new();
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            let Some(locale) = path.file_stem().and_then(|stem| stem.to_str()) else { continue };
+            if path.extension().is_some_and(|extension| extension == "ftl") {
+                let source = std::fs::read_to_string(&path)?;
+                self.add_catalog(locale, &source).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                loaded.push(locale.to_string());
+            }
+        }
+        loaded.sort();
+        Ok(loaded)
+    }
+
+    /// Загружает каталоги из [`LOCALES_ENV`], если переменная задана
+    pub fn load_env_dir(&mut self) -> io::Result<Vec<String>> {
+        match std::env::var(LOCALES_ENV) {
+            Ok(dir) => self.load_dir(dir),
+            Err(_) => Ok(Vec::new()),
+        }
+    }
+
+    /// Проверяет, что в каждом каталоге есть все ключи [`MessageKey`] и нет лишних строк
+    ///
+    /// ## Возвращает
+    /// Найденные нарушения (пусто - каталоги полные)
+    pub fn check(&self) -> Vec<CatalogProblem> {
+        let mut problems = Vec::new();
+        for (locale, catalog) in &self.catalogs {
+            for key in MessageKey::ALL.iter().filter(|key| !catalog.ids.contains(key.id())) {
+                problems.push(CatalogProblem::Missing { locale: locale.clone(), key: *key });
+            }
+            for id in catalog.ids.iter().filter(|id| MessageKey::from_id(id).is_none()) {
+                problems.push(CatalogProblem::Unused { locale: locale.clone(), id: id.clone() });
+            }
+        }
+        problems
+    }
+
+    /// Цепочка языков, в которых ищется строка
+    fn fallback_chain(&self) -> Vec<&str> {
+        let mut chain = vec![self.locale.as_str()];
+        if let Some((language, _)) = self.locale.split_once(['-', '_']) {
+            chain.push(language);
+        }
+        chain.push(DEFAULT_LOCALE);
+        chain.dedup();
+        chain
+    }
+
+    /// Разбирает ответ «да» или «нет»
+    ///
+    /// Варианты ответа перечислены через запятую в строках `answer_yes` и
+    /// `answer_no`. Подходят ответы всех языков цепочки, поэтому `y`
+    /// понимается и в русском интерфейсе.
+    ///
+    /// ## Возвращает
+    /// `Some(true)` - да, `Some(false)` - нет, `None` - ответ не распознан
+    ///
+    /// ## Пример
+    /// ```rust
+    /// use rust_tic_tac_toe::{Language, Localization};
+    ///
+    /// let localization = Localization::new(Language::Russian);
+    /// assert_eq!(localization.parse_answer("Да"), Some(true));
+    /// assert_eq!(localization.parse_answer("n"), Some(false));
+    /// assert_eq!(localization.parse_answer("может быть"), None);
+    /// ```
+    pub fn parse_answer(&self, input: &str) -> Option<bool> {
+        let input = input.trim().to_lowercase();
+        let answers = [(MessageKey::AnswerYes, true), (MessageKey::AnswerNo, false)];
+        self.fallback_chain()
+            .into_iter()
+            .filter_map(|locale| self.catalogs.get(locale))
+            .flat_map(|catalog| answers.iter().filter_map(|(key, answer)| Some((catalog.format(key.id(), None)?, *answer))))
+            .find(|(variants, _)| variants.split(',').any(|variant| variant.trim().to_lowercase() == input))
+            .map(|(_, answer)| answer)
+    }
+
+    /// Возвращает локализованный текст по ключу
+    pub fn get_text(&self, key: MessageKey) -> String {
+        self.format(key, &[])
+    }
+
+    /// Возвращает локализованный текст с подставленными аргументами
+    ///
+    /// ## Параметры
+    /// - `key`: ключ строки
+    /// - `args`: именованные аргументы; числа выбирают форму множественного числа
+    ///
+    /// ## Пример
+    /// ```rust
+    /// use rust_tic_tac_toe::{Language, Localization, MessageKey};
+    ///
+    /// let localization = Localization::new(Language::Russian);
+    /// assert_eq!(localization.format(MessageKey::Spectators, &[("count", 2.into())]), "2 зрителя");
+    /// ```
+    pub fn format(&self, key: MessageKey, args: &[(&str, FluentValue)]) -> String {
+        self.display(&self.locale, self.format_logical(key, args))
+    }
+
+    /// Локализованный текст в логическом порядке, даже если включен порядок отображения
+    ///
+    /// Для строк, которые выводит система, а не egui: заголовок окна или
+    /// вкладки браузера. Система переставляет символы справа налево сама.
+    pub fn get_logical_text(&self, key: MessageKey) -> String {
+        self.format_logical(key, &[])
+    }
+
+    /// Текст по ключу с аргументами в логическом порядке
+    fn format_logical(&self, key: MessageKey, args: &[(&str, FluentValue)]) -> String {
+        let mut fluent_args = FluentArgs::new();
+        for (name, value) in args {
+            fluent_args.set(*name, value.clone());
+        }
+        self.fallback_chain()
+            .into_iter()
+            .filter_map(|locale| self.catalogs.get(locale))
+            .find_map(|catalog| catalog.format(key.id(), Some(&fluent_args)))
+            .unwrap_or_else(|| key.id().to_string())
+    }
+}
+
+// === ТЕСТЫ ===
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Тест: аргументы и множественное число
+    #[test]
+    fn test_format() {
+        let localization = Localization::new(Language::Russian);
+        assert_eq!(localization.format(MessageKey::Winner, &[("player", "O".into())]), "Победитель: O!");
+        let spectators = |count: usize| localization.format(MessageKey::Spectators, &[("count", count.into())]);
+        assert_eq!(spectators(1), "1 зритель");
+        assert_eq!(spectators(3), "3 зрителя");
+        assert_eq!(spectators(11), "11 зрителей");
+        assert_eq!(Localization::new(Language::English).format(MessageKey::Spectators, &[("count", 1.into())]), "1 spectator");
+    }
+
+    /// Тест: цепочка языков и ключ вместо пропущенной строки
+    #[test]
+    fn test_fallback() {
+        let mut localization = Localization::new(Language::English);
+        localization.add_catalog("fr", "language_name = Français\nnew_game = Nouvelle partie").unwrap();
+        localization.set_locale("fr-CA");
+        assert_eq!(localization.get_text(MessageKey::NewGame), "Nouvelle partie");
+        assert_eq!(localization.get_text(MessageKey::Exit), "Exit");
+        assert_eq!(localization.language_name("fr"), "Français");
+        assert!(localization.add_catalog("fr", "broken = {").is_err());
+        assert_eq!(localization.get_text(MessageKey::NewGame), "Nouvelle partie");
+    }
+
+    /// Тест: во встроенных каталогах есть все ключи и нет лишних строк
+    #[test]
+    fn test_builtin_catalogs_complete() {
+        let english = Localization::new(Language::English);
+        let problems = english.check();
+        let described: Vec<String> = problems.iter().map(|problem| problem.describe(&english)).collect();
+        assert!(problems.is_empty(), "{}", described.join("\n"));
+
+        let mut localization = Localization::new(Language::English);
+        localization.add_catalog("fr", "language_name = Français\nnew_gmae = Nouvelle partie").unwrap();
+        let problems = localization.check();
+        assert!(problems.contains(&CatalogProblem::Missing { locale: "fr".into(), key: MessageKey::NewGame }));
+        assert!(problems.contains(&CatalogProblem::Unused { locale: "fr".into(), id: "new_gmae".into() }));
+        let unused = CatalogProblem::Unused { locale: "fr".into(), id: "new_gmae".into() };
+        assert_eq!(unused.describe(&english), "fr: unused string new_gmae");
+    }
+
+    /// Тест: язык из переменных окружения и ответы да/нет
+    #[test]
+    fn test_console_locale() {
+        assert_eq!(posix_locale_tag("ru_RU.UTF-8"), Some("ru-RU".to_string()));
+        assert_eq!(posix_locale_tag("de_DE@euro"), Some("de-DE".to_string()));
+        assert_eq!(posix_locale_tag("C.UTF-8"), None);
+        assert_eq!(posix_locale_tag("POSIX"), None);
+
+        let mut localization = Localization::new(Language::English);
+        localization.set_locale("ru-RU");
+        assert_eq!(localization.parse_answer(" ДА "), Some(true));
+        assert_eq!(localization.parse_answer("yes"), Some(true), "английские ответы из цепочки языков");
+        assert_eq!(localization.parse_answer("н"), Some(false));
+        localization.set_language(Language::English);
+        assert_eq!(localization.parse_answer("да"), None);
+        assert_eq!(localization.parse_answer(""), None);
+    }
+
+    /// Тест: новые языки и порядок отображения для арабского
+    #[test]
+    fn test_more_languages() {
+        let mut localization = Localization::new(Language::German);
+        assert_eq!(localization.format(MessageKey::Spectators, &[("count", 1.into())]), "1 Zuschauer");
+        localization.set_language(Language::Japanese);
+        assert_eq!(localization.format(MessageKey::Winner, &[("player", "X".into())]), "勝者：X！");
+        assert_eq!(localization.parse_answer("はい"), Some(true));
+
+        localization.set_language(Language::Arabic);
+        assert!(localization.is_rtl() && Language::Arabic.is_rtl() && !Language::Chinese.is_rtl());
+        let logical = localization.get_text(MessageKey::Draw);
+        assert_eq!(logical, "تعادل!");
+        localization.set_visual_order(true);
+        assert_eq!(localization.get_text(MessageKey::Draw), bidi::visual_order(&logical));
+        assert_eq!(localization.get_logical_text(MessageKey::Draw), logical);
+        assert!(localization.get_text(MessageKey::Draw).starts_with('!'));
+        assert_eq!(localization.language_name("de"), "Deutsch");
+    }
+
+    /// Тест: выбор поддерживаемого языка по предпочтениям
+    #[test]
+    fn test_select_preferred() {
+        let mut localization = Localization::new(Language::English);
+        assert_eq!(localization.supported_locale("zh_CN"), Some("zh".to_string()));
+        assert_eq!(localization.supported_locale("ES"), Some("es".to_string()));
+        assert_eq!(localization.supported_locale("pt-BR"), None);
+
+        assert!(!localization.select_preferred(["pt-BR", "ko"]));
+        assert_eq!(localization.locale(), "en");
+        localization.add_catalog("pt-BR", "new_game = Novo jogo").unwrap();
+        assert!(localization.select_preferred(["pt-BR", "ru"]));
+        assert_eq!(localization.get_text(MessageKey::NewGame), "Novo jogo");
+    }
+
+    /// Тест: каталоги загружаются из файлов
+    #[test]
+    fn test_load_dir() {
+        let dir = std::env::temp_dir().join(format!("ttt_locales_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("ru.ftl"), "new_game = Еще партия").unwrap();
+        std::fs::write(dir.join("readme.txt"), "не каталог").unwrap();
+
+        let mut localization = Localization::new(Language::Russian);
+        assert_eq!(localization.load_dir(&dir).unwrap(), vec!["ru".to_string()]);
+        assert_eq!(localization.get_text(MessageKey::NewGame), "Еще партия");
+        assert_eq!(localization.get_text(MessageKey::Exit), "Выход", "остальные строки встроенного каталога остаются");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -18,85 +18,21 @@
 //! wasm-pack build --target web
 //! ```
 
-/// Поддерживаемые языки
-#[derive(Clone, Copy, PartialEq, Debug)]
-pub enum Language {
-    /// Русский язык
-    Russian,
-    /// Английский язык
-    English,
-}
+use std::fmt;
+use std::str::FromStr;
 
-/// Структура для локализации
-#[derive(Clone)]
-pub struct Localization {
-    /// Текущий язык
-    pub language: Language,
-}
+use serde::{Deserialize, Serialize};
 
-impl Localization {
-    /// Создает новую локализацию с указанным языком
-    pub fn new(language: Language) -> Self {
-        Self { language }
-    }
-
-    /// Возвращает локализованный текст по ключу
-    pub fn get_text(&self, key: &str) -> &'static str {
-        match self.language {
-            Language::Russian => match key {
-                "game_title" => "Крестики-нолики",
-                "current_player_turn" => "Ход игрока: {}",
-                "winner" => "Победитель: {}!",
-                "draw" => "Ничья!",
-                "new_game" => "Новая игра",
-                "exit" => "Выход",
-                "loading" => "Загрузка игры...",
-                "game_loaded" => "Игра загружена!",
-                "loading_error" => "Ошибка загрузки игры: {}",
-                "performance" => "Производительность",
-                "performance_desc" => "Нативная скорость Rust в браузере",
-                "design" => "Дизайн",
-                "design_desc" => "Современный и красивый интерфейс",
-                "compatibility" => "Совместимость",
-                "compatibility_desc" => "Работает на всех устройствах",
-                "download_source" => "Скачать исходный код",
-                "play_again" => "Играть снова",
-                "created_with" => "Создано с ❤️ на Rust + WebAssembly",
-                "language_switch" => "Язык",
-                "russian" => "Русский",
-                "english" => "English",
-                _ => "Unknown",
-            },
-            Language::English => match key {
-                "game_title" => "Tic-Tac-Toe",
-                "current_player_turn" => "Player's turn: {}",
-                "winner" => "Winner: {}!",
-                "draw" => "Draw!",
-                "new_game" => "New Game",
-                "exit" => "Exit",
-                "loading" => "Loading game...",
-                "game_loaded" => "Game loaded!",
-                "loading_error" => "Loading error: {}",
-                "performance" => "Performance",
-                "performance_desc" => "Native Rust speed in browser",
-                "design" => "Design",
-                "design_desc" => "Modern and beautiful interface",
-                "compatibility" => "Compatibility",
-                "compatibility_desc" => "Works on all devices",
-                "download_source" => "Download source code",
-                "play_again" => "Play again",
-                "created_with" => "Created with ❤️ using Rust + WebAssembly",
-                "language_switch" => "Language",
-                "russian" => "Русский",
-                "english" => "English",
-                _ => "Unknown",
-            },
-        }
-    }
-}
+// === ЛОКАЛИЗАЦИЯ ===
+
+pub mod bidi;
+pub mod fonts;
+pub mod i18n;
+
+pub use i18n::{Language, Localization, MessageKey};
 
 /// Игрок в игре "Крестики-нолики"
-#[derive(Clone, Copy, PartialEq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
 pub enum Player {
     /// Игрок X (ходит первым)
     X,
@@ -112,17 +48,302 @@ impl Player {
             Player::O => "O",
         }
     }
+
+    /// Возвращает соперника данного игрока
+    pub fn opponent(&self) -> Player {
+        match self {
+            Player::X => Player::O,
+            Player::O => Player::X,
+        }
+    }
+
+    /// Ключ локализованного названия знака
+    pub fn mark_key(&self) -> MessageKey {
+        match self {
+            Player::X => MessageKey::MarkX,
+            Player::O => MessageKey::MarkO,
+        }
+    }
+
+    /// Локализованное название знака (`X`, «крестики», `×`)
+    pub fn mark(&self, localization: &Localization) -> String {
+        localization.get_text(self.mark_key())
+    }
+
+    /// Номер стороны: 0 - X, 1 - O
+    fn index(&self) -> usize {
+        match self {
+            Player::X => 0,
+            Player::O => 1,
+        }
+    }
+}
+
+/// Имена игроков, которые показываются в сообщениях вместе со знаком
+///
+/// ## Пример
+/// ```rust
+/// use rust_tic_tac_toe::{Language, Localization, Player, PlayerNames};
+///
+/// let localization = Localization::new(Language::English);
+/// let names = PlayerNames::new("Alice", "");
+/// assert_eq!(names.label(Player::X, &localization), "Alice (X)");
+/// assert_eq!(names.label(Player::O, &localization), "O");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PlayerNames {
+    names: [String; 2],
+}
+
+impl PlayerNames {
+    /// Имена игроков X и O (пустое имя - показывать только знак)
+    pub fn new(x: &str, o: &str) -> Self {
+        Self { names: [x.trim().to_string(), o.trim().to_string()] }
+    }
+
+    /// Имя игрока (пусто, если не задано)
+    pub fn name(&self, player: Player) -> &str {
+        &self.names[player.index()]
+    }
+
+    /// Изменяет имя игрока
+    pub fn set(&mut self, player: Player, name: &str) {
+        self.names[player.index()] = name.trim().to_string();
+    }
+
+    /// Имя для поля ввода
+    pub fn name_mut(&mut self, player: Player) -> &mut String {
+        &mut self.names[player.index()]
+    }
+
+    /// Меняет имена сторон местами (участники матча поменялись цветом)
+    pub fn swap(&mut self) {
+        self.names.swap(0, 1);
+    }
+
+    /// Имя со знаком, `Alice (X)`, или только знак, если имени нет
+    pub fn label(&self, player: Player, localization: &Localization) -> String {
+        let mark = player.mark(localization);
+        match self.name(player).trim() {
+            "" => mark,
+            name => localization.format(MessageKey::PlayerLabel, &[("name", name.into()), ("mark", mark.into())]),
+        }
+    }
+}
+
+/// Правила игры: размер поля и длина выигрышной линии
+///
+/// Классические крестики-нолики — поле 3x3 и линия из 3 символов,
+/// "гомоку" — поле 15x15 и линия из 5 символов.
+///
+/// ## Пример
+/// ```rust
+/// use rust_tic_tac_toe::{Rules, TicTacToe};
+///
+/// let game = TicTacToe::with_rules(Rules::gomoku());
+/// assert_eq!(game.rules().cell_count(), 225);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+pub struct Rules {
+    /// Размер стороны квадратного поля
+    pub size: usize,
+    /// Сколько символов подряд нужно для победы
+    pub win_length: usize,
+}
+
+impl Rules {
+    /// Наибольший размер стороны поля
+    ///
+    /// Столбцы в записи партии обозначаются одной буквой от `a` до `z`.
+    pub const MAX_SIZE: usize = 26;
+
+    /// Создает правила с указанным размером поля и длиной линии
+    ///
+    /// ## Паника
+    /// Если правила не проходят проверку [`Rules::try_new`]
+    pub fn new(size: usize, win_length: usize) -> Self {
+        Self::try_new(size, win_length).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Создает правила, проверяя размер поля и длину линии
+    ///
+    /// Правила, пришедшие по сети или из файла, нужно проверять до создания
+    /// партии: слишком большое поле не поместится в памяти.
+    ///
+    /// ## Параметры
+    /// - `size`: размер стороны поля, от 2 до [`Rules::MAX_SIZE`]
+    /// - `win_length`: длина выигрышной линии, от 2 до размера поля
+    ///
+    /// ## Возвращает
+    /// Правила или описание ошибки
+    ///
+    /// ## Пример
+    /// ```rust
+    /// use rust_tic_tac_toe::Rules;
+    ///
+    /// assert_eq!(Rules::try_new(15, 5), Ok(Rules::gomoku()));
+    /// assert!(Rules::try_new(3, 4).is_err());
+    /// assert!(Rules::try_new(usize::MAX, 5).is_err());
+    /// ```
+    pub fn try_new(size: usize, win_length: usize) -> Result<Self, String> {
+        if !(2..=Self::MAX_SIZE).contains(&size) {
+            return Err(format!("размер поля должен быть от 2 до {}", Self::MAX_SIZE));
+        }
+        if !(2..=size).contains(&win_length) {
+            return Err("длина линии должна быть от 2 до размера поля".to_string());
+        }
+        Ok(Self { size, win_length })
+    }
+
+    /// Проверяет уже собранные правила, например разобранные из сообщения
+    ///
+    /// ## Возвращает
+    /// Те же правила или описание ошибки, как у [`Rules::try_new`]
+    pub fn validate(self) -> Result<Self, String> {
+        Self::try_new(self.size, self.win_length)
+    }
+
+    /// Классические правила: поле 3x3, линия из 3
+    pub const fn classic() -> Self {
+        Self { size: 3, win_length: 3 }
+    }
+
+    /// Правила "пять в ряд": поле 15x15, линия из 5
+    pub const fn gomoku() -> Self {
+        Self { size: 15, win_length: 5 }
+    }
+
+    /// Количество клеток на поле
+    pub fn cell_count(&self) -> usize {
+        self.size * self.size
+    }
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self::classic()
+    }
+}
+
+impl fmt::Display for Rules {
+    /// Формат `15x15/5`: размер поля и длина линии
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}x{}/{}", self.size, self.size, self.win_length)
+    }
+}
+
+impl FromStr for Rules {
+    type Err = String;
+
+    /// Разбирает правила из строки
+    ///
+    /// Поддерживаются названия `classic` и `gomoku`, а также формат `NxN/K`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "classic" => return Ok(Rules::classic()),
+            "gomoku" => return Ok(Rules::gomoku()),
+            _ => {}
+        }
+
+        let error = || format!("неверные правила: {}", s);
+        let (board, win_length) = s.split_once('/').ok_or_else(error)?;
+        let (width, height) = board.split_once('x').ok_or_else(error)?;
+        let size: usize = width.parse().map_err(|_| error())?;
+        let win_length: usize = win_length.parse().map_err(|_| error())?;
+        if height.parse::<usize>().ok() != Some(size) {
+            return Err(error());
+        }
+        Rules::try_new(size, win_length).map_err(|e| format!("{}: {}", error(), e))
+    }
+}
+
+/// Направления линий: горизонталь, вертикаль и две диагонали
+pub(crate) const DIRECTIONS: [(isize, isize); 4] = [(0, 1), (1, 0), (1, 1), (1, -1)];
+
+/// Причина окончания партии
+///
+/// ## Пример
+/// ```rust
+/// use rust_tic_tac_toe::{Player, Termination, TicTacToe};
+///
+/// let mut game = TicTacToe::new();
+/// game.make_move(4);
+/// game.resign(Player::O);
+/// assert_eq!(game.termination(), Some(Termination::Resignation(Player::O)));
+/// assert_eq!(game.get_winner(), Some(Player::X));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Termination {
+    /// Собрана линия
+    Line,
+    /// Поле заполнено без победителя
+    FullBoard,
+    /// Игрок сдался
+    Resignation(Player),
+    /// Ничья по соглашению
+    Agreement,
+    /// У игрока истекло время
+    Timeout(Player),
+    /// Игрок покинул партию
+    Abandonment(Player),
+    /// Партия прервана до первых ходов и не считается
+    Aborted,
+    /// Результат назначен судьей (`None` - ничья)
+    Adjudication(Option<Player>),
+}
+
+impl Termination {
+    /// Ключ локализованного описания; аргумент `$player` - символ игрока из [`Termination::player`]
+    pub fn text_key(&self) -> MessageKey {
+        match self {
+            Termination::Line => MessageKey::TerminationLine,
+            Termination::FullBoard => MessageKey::TerminationFullBoard,
+            Termination::Resignation(_) => MessageKey::TerminationResignation,
+            Termination::Agreement => MessageKey::TerminationAgreement,
+            Termination::Timeout(_) => MessageKey::TimeOut,
+            Termination::Abandonment(_) => MessageKey::TerminationAbandonment,
+            Termination::Aborted => MessageKey::TerminationAborted,
+            Termination::Adjudication(_) => MessageKey::TerminationAdjudication,
+        }
+    }
+
+    /// Игрок, из-за которого закончилась партия (сдался, просрочил время или ушел)
+    pub fn player(&self) -> Option<Player> {
+        match *self {
+            Termination::Resignation(player) | Termination::Timeout(player) | Termination::Abandonment(player) => {
+                Some(player)
+            }
+            _ => None,
+        }
+    }
+
+    /// Локализованное описание причины
+    ///
+    /// ## Параметры
+    /// - `names`: имена игроков; без имени игрок называется знаком
+    pub fn describe(&self, localization: &Localization, names: &PlayerNames) -> String {
+        match self.player() {
+            Some(player) => localization.format(self.text_key(), &[("player", names.label(player, localization).into())]),
+            None => localization.get_text(self.text_key()),
+        }
+    }
 }
 
 /// Основная структура игры "Крестики-нолики"
 /// 
-/// Игровое поле представляет собой массив 3x3, где:
+/// Игровое поле хранится построчно. Для классических правил (3x3):
 /// - Индексы 0-2: первая строка (сверху)
 /// - Индексы 3-5: вторая строка (по центру)  
 /// - Индексы 6-8: третья строка (снизу)
 /// 
+/// Для поля размера `n` клетка в строке `row` и столбце `col`
+/// имеет индекс `row * n + col`.
+/// 
 /// ## Пример использования
 /// ```rust
+/// use rust_tic_tac_toe::TicTacToe;
+///
 /// let mut game = TicTacToe::new();
 /// 
 /// // X делает ход в центр (позиция 4)
@@ -141,42 +362,94 @@ impl Player {
 /// ```
 #[derive(Clone)]
 pub struct TicTacToe {
-    /// Игровое поле: массив 9 элементов, где None = пустая клетка
-    board: [Option<Player>; 9],
+    /// Правила игры
+    rules: Rules,
+    /// Игровое поле, где None = пустая клетка
+    board: Vec<Option<Player>>,
     /// Текущий игрок, который должен сделать ход
     current_player: Player,
     /// Флаг, указывающий, что игра закончена
     game_over: bool,
+    /// Победитель, если он определился
+    winner: Option<Player>,
+    /// Причина окончания партии
+    termination: Option<Termination>,
+    /// Игрок, предложивший ничью
+    draw_offer: Option<Player>,
+    /// История сделанных ходов
+    history: Vec<usize>,
 }
 
 impl TicTacToe {
-    /// Создает новую игру
+    /// Создает новую игру по классическим правилам
     /// 
     /// Игра начинается с игрока X, поле пустое
     pub fn new() -> Self {
+        Self::with_rules(Rules::classic())
+    }
+
+    /// Создает новую игру с указанными правилами
+    pub fn with_rules(rules: Rules) -> Self {
         Self {
-            board: [None; 9],
+            rules,
+            board: vec![None; rules.cell_count()],
             current_player: Player::X,
             game_over: false,
+            winner: None,
+            termination: None,
+            draw_offer: None,
+            history: Vec::new(),
         }
     }
     
     /// Сбрасывает игру в начальное состояние
     pub fn reset(&mut self) {
-        self.board = [None; 9];
+        self.board = vec![None; self.rules.cell_count()];
         self.current_player = Player::X;
         self.game_over = false;
+        self.winner = None;
+        self.termination = None;
+        self.draw_offer = None;
+        self.history.clear();
+    }
+
+    /// Возвращает правила игры
+    pub fn rules(&self) -> Rules {
+        self.rules
+    }
+
+    /// Возвращает текущего игрока
+    pub fn current_player(&self) -> Player {
+        self.current_player
     }
     
     /// Возвращает символ текущего игрока
     pub fn current_player_symbol(&self) -> &'static str {
         self.current_player.symbol()
     }
+
+    /// Возвращает содержимое клетки
+    pub fn cell(&self, position: usize) -> Option<Player> {
+        self.board.get(position).copied().flatten()
+    }
+
+    /// Возвращает историю ходов в порядке их совершения
+    pub fn moves(&self) -> &[usize] {
+        &self.history
+    }
+
+    /// Возвращает список свободных клеток
+    pub fn legal_moves(&self) -> Vec<usize> {
+        if self.game_over {
+            return Vec::new();
+        }
+        (0..self.board.len()).filter(|&i| self.board[i].is_none()).collect()
+    }
     
     /// Делает ход в указанную позицию
     /// 
     /// ## Параметры
-    /// - `position`: позиция для хода (0-8)
+    /// - `position`: позиция для хода (0-8 для классического поля)
     /// 
     /// ## Возвращает
     /// - `true` если ход успешно сделан
@@ -184,6 +457,8 @@ impl TicTacToe {
     /// 
     /// ## Пример
     /// ```rust
+    /// use rust_tic_tac_toe::TicTacToe;
+    ///
     /// let mut game = TicTacToe::new();
     /// assert!(game.make_move(4));  // X в центр - успешно
     /// assert!(!game.make_move(4)); // X в центр снова - неуспешно (занято)
@@ -191,54 +466,122 @@ impl TicTacToe {
     /// ```
     pub fn make_move(&mut self, position: usize) -> bool {
         // Проверяем корректность позиции
-        if position >= 9 || self.board[position].is_some() {
+        if self.game_over || position >= self.board.len() || self.board[position].is_some() {
             return false;
         }
         
-        // Делаем ход
+        // Делаем ход; ход отклоняет предложение ничьей соперника
         self.board[position] = Some(self.current_player);
+        self.history.push(position);
+        if self.draw_offer == Some(self.current_player.opponent()) {
+            self.draw_offer = None;
+        }
         
         // Проверяем, есть ли победитель
         if self.check_winner(position) {
             self.game_over = true;
+            self.winner = Some(self.current_player);
+            self.termination = Some(Termination::Line);
         } else if self.is_board_full() {
             // Если доска заполнена без победителя - ничья
             self.game_over = true;
+            self.termination = Some(Termination::FullBoard);
         } else {
             // Переключаем игрока
-            self.current_player = match self.current_player {
-                Player::X => Player::O,
-                Player::O => Player::X,
-            };
+            self.current_player = self.current_player.opponent();
         }
         
         true
     }
+
+    /// Отменяет последний ход
+    ///
+    /// ## Возвращает
+    /// - `true` если ход отменен
+    /// - `false` если ходов еще не было
+    pub fn undo_move(&mut self) -> bool {
+        let Some(position) = self.history.pop() else {
+            return false;
+        };
+
+        if let Some(player) = self.board[position].take() {
+            self.current_player = player;
+        }
+        self.game_over = false;
+        self.winner = None;
+        self.termination = None;
+        self.draw_offer = None;
+
+        true
+    }
     
     /// Проверяет, есть ли победитель после хода в указанную позицию
-    /// 
-    /// Проверяет все возможные выигрышные комбинации:
-    /// - Горизонтали: [0,1,2], [3,4,5], [6,7,8]
-    /// - Вертикали: [0,3,6], [1,4,7], [2,5,8]  
-    /// - Диагонали: [0,4,8], [2,4,6]
     fn check_winner(&self, position: usize) -> bool {
-        let player = self.board[position].unwrap();
-        
-        // Все возможные выигрышные комбинации
-        let win_patterns = [
-            [0, 1, 2], [3, 4, 5], [6, 7, 8], // горизонтали
-            [0, 3, 6], [1, 4, 7], [2, 5, 8], // вертикали
-            [0, 4, 8], [2, 4, 6], // диагонали
-        ];
-        
-        // Проверяем каждую комбинацию
-        for pattern in &win_patterns {
-            if pattern.iter().all(|&pos| self.board[pos] == Some(player)) {
-                return true;
+        self.line_through(position).is_some()
+    }
+
+    /// Линия не короче выигрышной, проходящая через клетку
+    /// 
+    /// Считает одинаковые символы по обе стороны от клетки в каждом
+    /// из четырех направлений: горизонталь, вертикаль и две диагонали.
+    /// 
+    /// ## Возвращает
+    /// Крайние клетки линии или `None`, если линии нет
+    fn line_through(&self, position: usize) -> Option<(usize, usize)> {
+        let player = self.board[position]?;
+        let size = self.rules.size as isize;
+        let (row, col) = ((position / self.rules.size) as isize, (position % self.rules.size) as isize);
+
+        let count_towards = |dr: isize, dc: isize| {
+            let mut count = 0;
+            let (mut r, mut c) = (row + dr, col + dc);
+            while r >= 0 && r < size && c >= 0 && c < size
+                && self.board[(r * size + c) as usize] == Some(player)
+            {
+                count += 1;
+                r += dr;
+                c += dc;
             }
+            count
+        };
+        let cell = |steps: isize, dr: isize, dc: isize| ((row + steps * dr) * size + col + steps * dc) as usize;
+
+        DIRECTIONS.iter().find_map(|&(dr, dc)| {
+            let (forward, back) = (count_towards(dr, dc), count_towards(-dr, -dc));
+            (1 + forward + back >= self.rules.win_length).then(|| (cell(-(back as isize), dr, dc), cell(forward as isize, dr, dc)))
+        })
+    }
+
+    /// Выигрышная линия законченной партии
+    /// 
+    /// ## Возвращает
+    /// Крайние клетки линии, собранной последним ходом, или `None`, если
+    /// партия закончилась не линией
+    /// 
+    /// ## Пример
+    /// ```rust
+    /// use rust_tic_tac_toe::TicTacToe;
+    /// 
+    /// let mut game = TicTacToe::new();
+    /// for position in [2, 0, 4, 1, 6] {
+    ///     game.make_move(position);
+    /// }
+    /// assert_eq!(game.winning_line(), Some((2, 6)));
+    /// ```
+    pub fn winning_line(&self) -> Option<(usize, usize)> {
+        if self.termination != Some(Termination::Line) {
+            return None;
+        }
+        match self.history.last() {
+            Some(&position) => self.line_through(position),
+            // Позиция задана целиком, а не ходами
+            None => self.line_through(self.any_line()?),
         }
-        
-        false
+    }
+
+    /// Клетка, через которую проходит какая-нибудь выигрышная линия
+    fn any_line(&self) -> Option<usize> {
+        (0..self.board.len()).find(|&position| self.line_through(position).is_some())
     }
     
     /// Проверяет, заполнена ли доска полностью
@@ -260,29 +603,151 @@ impl TicTacToe {
         if !self.game_over {
             return None;
         }
-        
-        // Проверяем все возможные выигрышные комбинации
-        let win_patterns = [
-            [0, 1, 2], [3, 4, 5], [6, 7, 8],
-            [0, 3, 6], [1, 4, 7], [2, 5, 8],
-            [0, 4, 8], [2, 4, 6],
-        ];
-        
-        for pattern in &win_patterns {
-            if let Some(player) = self.board[pattern[0]] {
-                if pattern.iter().all(|&pos| self.board[pos] == Some(player)) {
-                    return Some(player);
-                }
+
+        self.winner
+    }
+
+    /// Причина окончания партии (`None` - партия продолжается)
+    pub fn termination(&self) -> Option<Termination> {
+        self.termination
+    }
+
+    /// Завершает партию по указанной причине
+    ///
+    /// Победитель определяется причиной: при сдаче побеждает соперник,
+    /// при истечении времени или уходе игрока - соперник, если он еще может
+    /// собрать линию, иначе ничья; соглашение и прерывание дают ничью.
+    ///
+    /// ## Возвращает
+    /// - `true` если партия завершена
+    /// - `false` если она уже была закончена
+    pub fn terminate(&mut self, termination: Termination) -> bool {
+        if self.game_over {
+            return false;
+        }
+        self.winner = match termination {
+            Termination::Resignation(player) => Some(player.opponent()),
+            Termination::Timeout(player) | Termination::Abandonment(player) => {
+                let opponent = player.opponent();
+                self.can_win(opponent).then_some(opponent)
             }
+            Termination::Adjudication(winner) => winner,
+            Termination::Line | Termination::FullBoard | Termination::Agreement | Termination::Aborted => None,
+        };
+        self.game_over = true;
+        self.termination = Some(termination);
+        self.draw_offer = None;
+        true
+    }
+
+    /// Игрок сдается
+    pub fn resign(&mut self, player: Player) -> bool {
+        self.terminate(Termination::Resignation(player))
+    }
+
+    /// Завершает игру из-за истечения времени игрока
+    ///
+    /// Соперник побеждает, если он еще может собрать линию, иначе - ничья.
+    /// Уже законченную игру не меняет.
+    ///
+    /// ## Пример
+    /// ```rust
+    /// use rust_tic_tac_toe::{Player, TicTacToe};
+    ///
+    /// let mut game = TicTacToe::new();
+    /// game.make_move(4);
+    /// game.time_out(Player::O);
+    /// assert_eq!(game.get_winner(), Some(Player::X));
+    /// ```
+    pub fn time_out(&mut self, player: Player) -> bool {
+        self.terminate(Termination::Timeout(player))
+    }
+
+    /// Игрок предлагает ничью
+    ///
+    /// Предложение действует, пока соперник не ответит или не сделает ход.
+    ///
+    /// ## Пример
+    /// ```rust
+    /// use rust_tic_tac_toe::{Player, Termination, TicTacToe};
+    ///
+    /// let mut game = TicTacToe::new();
+    /// game.make_move(4);
+    /// assert!(game.offer_draw(Player::O));
+    /// assert!(!game.accept_draw(Player::O)); // свое предложение принять нельзя
+    /// assert!(game.accept_draw(Player::X));
+    /// assert_eq!(game.termination(), Some(Termination::Agreement));
+    /// ```
+    pub fn offer_draw(&mut self, player: Player) -> bool {
+        if self.game_over || self.draw_offer.is_some() {
+            return false;
         }
-        
-        None // Ничья
+        self.draw_offer = Some(player);
+        true
+    }
+
+    /// Игрок, чье предложение ничьей ждет ответа
+    pub fn draw_offer(&self) -> Option<Player> {
+        self.draw_offer
+    }
+
+    /// Игрок принимает предложение ничьей соперника
+    pub fn accept_draw(&mut self, player: Player) -> bool {
+        self.draw_offer == Some(player.opponent()) && self.terminate(Termination::Agreement)
+    }
+
+    /// Игрок отклоняет предложение ничьей соперника
+    pub fn decline_draw(&mut self, player: Player) -> bool {
+        if self.game_over || self.draw_offer != Some(player.opponent()) {
+            return false;
+        }
+        self.draw_offer = None;
+        true
+    }
+
+    /// Может ли игрок прервать партию
+    ///
+    /// Прервать можно, пока игрок не сделал ни одного хода.
+    pub fn can_abort(&self, player: Player) -> bool {
+        let own_moves = match player {
+            Player::X => self.history.len().div_ceil(2),
+            Player::O => self.history.len() / 2,
+        };
+        !self.game_over && own_moves == 0
+    }
+
+    /// Прерывает партию без результата
+    pub fn abort(&mut self, player: Player) -> bool {
+        self.can_abort(player) && self.terminate(Termination::Aborted)
+    }
+
+    /// Назначает результат партии (`None` - ничья)
+    pub fn adjudicate(&mut self, winner: Option<Player>) -> bool {
+        self.terminate(Termination::Adjudication(winner))
+    }
+
+    /// Может ли игрок еще собрать линию
+    ///
+    /// Проверяет, есть ли на поле отрезок выигрышной длины без символов соперника.
+    pub fn can_win(&self, player: Player) -> bool {
+        let size = self.rules.size as isize;
+        let length = self.rules.win_length as isize;
+        let opponent = Some(player.opponent());
+        (0..size * size).any(|start| {
+            let (row, col) = (start / size, start % size);
+            DIRECTIONS.iter().any(|&(dr, dc)| {
+                let (end_row, end_col) = (row + dr * (length - 1), col + dc * (length - 1));
+                (0..size).contains(&end_row)
+                    && (0..size).contains(&end_col)
+                    && (0..length).all(|step| self.board[((row + dr * step) * size + col + dc * step) as usize] != opponent)
+            })
+        })
     }
     
     /// Отображает игровое поле в консоли
     /// 
     /// ## Пример вывода
-    /// ```
+    /// ```text
     ///  X | O | X
     /// ---------
     ///  O | X | O
@@ -290,20 +755,21 @@ impl TicTacToe {
     ///  O | X | X
     /// ```
     pub fn display_board(&self) {
+        let size = self.rules.size;
         println!();
-        for row in 0..3 {
-            let start = row * 3;
-            let row_values: Vec<String> = (start..start + 3)
+        for row in 0..size {
+            let start = row * size;
+            let row_values: Vec<&str> = (start..start + size)
                 .map(|i| match self.board[i] {
-                    Some(player) => player.symbol().to_string(),
-                    None => " ".to_string(),
+                    Some(player) => player.symbol(),
+                    None => " ",
                 })
                 .collect();
             
-            println!(" {} | {} | {} ", row_values[0], row_values[1], row_values[2]);
+            println!(" {} ", row_values.join(" | "));
             
-            if row < 2 {
-                println!("---------");
+            if row < size - 1 {
+                println!("{}", "-".repeat(size * 4 - 3));
             }
         }
         println!();
@@ -312,13 +778,29 @@ impl TicTacToe {
     // === МЕТОДЫ ДЛЯ ТЕСТИРОВАНИЯ ===
     
     /// Возвращает текущее состояние доски (для тестов)
-    pub fn get_board(&self) -> [Option<Player>; 9] {
-        self.board
+    pub fn get_board(&self) -> &[Option<Player>] {
+        &self.board
     }
     
     /// Устанавливает состояние доски (для тестов)
-    pub fn set_board(&mut self, board: [Option<Player>; 9]) {
-        self.board = board;
+    ///
+    /// Размер `board` должен совпадать с количеством клеток по правилам.
+    /// История ходов очищается, а победитель и окончание партии
+    /// определяются заново по новой позиции.
+    pub fn set_board(&mut self, board: &[Option<Player>]) {
+        assert_eq!(board.len(), self.rules.cell_count(), "неверный размер доски");
+        self.board = board.to_vec();
+        self.history.clear();
+        self.draw_offer = None;
+        self.winner = self.any_line().and_then(|position| self.board[position]);
+        self.termination = if self.winner.is_some() {
+            Some(Termination::Line)
+        } else if self.is_board_full() {
+            Some(Termination::FullBoard)
+        } else {
+            None
+        };
+        self.game_over = self.termination.is_some();
     }
     
     /// Устанавливает текущего игрока (для тестов)
@@ -327,6 +809,50 @@ impl TicTacToe {
     }
 }
 
+impl Default for TicTacToe {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// === ИСКУССТВЕННЫЙ ИНТЕЛЛЕКТ ===
+
+pub mod agent;
+pub mod engine;
+pub mod mcts;
+pub mod protocol;
+pub mod threat;
+
+// === ЧАСЫ ===
+
+pub mod clock;
+
+// === ЗАПИСИ И ТУРНИРЫ ===
+
+pub mod rating;
+pub mod record;
+pub mod series;
+pub mod stats;
+pub mod tournament;
+
+// === СЕТЕВАЯ ИГРА ===
+
+#[cfg(not(target_arch = "wasm32"))]
+pub mod audit;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod net;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod lobby;
+pub mod online;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod server;
+
+// === ИНТЕРФЕЙС ===
+
+pub mod app;
+pub mod board;
+pub mod theme;
+
 // === ВЕБ-ВЕРСИЯ ===
 
 #[cfg(target_arch = "wasm32")]
@@ -471,4 +997,104 @@ mod tests {
             assert!(cell.is_none());
         }
     }
+
+    /// Тест: сдача, ничья по соглашению и причина окончания
+    #[test]
+    fn test_termination() {
+        let mut game = TicTacToe::new();
+        game.make_move(4);
+        assert!(game.offer_draw(Player::X));
+        game.make_move(0); // ход O отклоняет предложение X
+        assert_eq!(game.draw_offer(), None);
+
+        assert!(game.offer_draw(Player::X));
+        assert!(game.decline_draw(Player::O));
+        assert!(!game.accept_draw(Player::O));
+        assert!(game.resign(Player::X));
+        assert_eq!(game.get_winner(), Some(Player::O));
+        assert_eq!(game.termination(), Some(Termination::Resignation(Player::X)));
+        assert!(!game.make_move(8));
+        assert!(!game.adjudicate(None));
+
+        game.undo_move();
+        assert!(!game.is_game_over());
+        assert_eq!(game.termination(), None);
+
+        let mut game = TicTacToe::new();
+        for position in [0, 3, 1, 4, 2] {
+            game.make_move(position);
+        }
+        assert_eq!(game.termination(), Some(Termination::Line));
+    }
+
+    /// Тест: прервать партию можно только до своего первого хода
+    #[test]
+    fn test_abort() {
+        let mut game = TicTacToe::new();
+        assert!(game.can_abort(Player::X));
+        game.make_move(4);
+        assert!(!game.can_abort(Player::X));
+        assert!(game.can_abort(Player::O));
+        assert!(!game.abort(Player::X));
+        assert!(game.abort(Player::O));
+        assert_eq!(game.get_winner(), None);
+        assert_eq!(game.termination(), Some(Termination::Aborted));
+        assert_eq!(
+            Termination::Abandonment(Player::O).describe(&Localization::new(Language::English), &PlayerNames::default()),
+            "O abandoned the game"
+        );
+        let names = PlayerNames::new("Alice", "Bob");
+        assert_eq!(
+            Termination::Resignation(Player::O).describe(&Localization::new(Language::Russian), &names),
+            "Bob (нолики) сдался"
+        );
+    }
+
+    /// Тест: правила вне допустимых границ отклоняются, а не ломают программу
+    #[test]
+    fn test_rules_bounds() {
+        assert_eq!(Rules::try_new(Rules::MAX_SIZE, 5).map(|rules| rules.cell_count()), Ok(676));
+        assert!(Rules::try_new(Rules::MAX_SIZE + 1, 5).is_err());
+        assert!(Rules::try_new(4_294_967_296, 5).is_err());
+        assert!(Rules::try_new(3, 1).is_err());
+        assert!(Rules { size: 0, win_length: 0 }.validate().is_err());
+        assert!("100x100/5".parse::<Rules>().is_err());
+        assert_eq!("4x4/3".parse::<Rules>(), Ok(Rules::new(4, 3)));
+    }
+
+    /// Тест: выигрышная линия - крайние клетки, включая ход в середину линии
+    #[test]
+    fn test_winning_line() {
+        let mut game = TicTacToe::with_rules(Rules::new(5, 4));
+        for position in [0, 5, 1, 6, 3, 7, 2] {
+            game.make_move(position);
+        }
+        assert_eq!(game.winning_line(), Some((0, 3)));
+
+        let mut game = TicTacToe::new();
+        game.make_move(4);
+        game.resign(Player::O);
+        assert_eq!(game.winning_line(), None, "партия закончилась не линией");
+    }
+
+    /// Тест: позиция, заданная целиком, сразу получает победителя и окончание
+    #[test]
+    fn test_set_board_recomputes_state() {
+        let (x, o) = (Some(Player::X), Some(Player::O));
+        let mut game = TicTacToe::new();
+        game.set_board(&[x, x, x, o, o, None, None, None, None]);
+        assert!(game.is_game_over());
+        assert_eq!(game.get_winner(), Some(Player::X));
+        assert_eq!(game.termination(), Some(Termination::Line));
+        assert_eq!(game.winning_line(), Some((0, 2)));
+        assert!(game.legal_moves().is_empty());
+
+        game.set_board(&[x, o, x, x, o, o, o, x, x]);
+        assert_eq!(game.termination(), Some(Termination::FullBoard));
+        assert_eq!(game.get_winner(), None);
+
+        game.set_board(&[x, None, None, None, o, None, None, None, None]);
+        assert!(!game.is_game_over());
+        assert!(game.make_move(8));
+    }
 }
@@ -0,0 +1,456 @@
+//! # Лобби и подбор соперников
+//!
+//! Лобби хранит открытые заявки на игру. Новая заявка сразу сравнивается
+//! с уже выставленными: если есть заявка с теми же правилами и контролем
+//! времени от игрока близкого рейтинга, сервер начинает партию. Иначе заявка
+//! появляется в списке, и ее может принять любой игрок.
+//!
+//! Рейтинги, сыгранные партии и их журналы хранятся через [`LobbyStore`]: в памяти
+//! ([`MemoryStore`]) или в JSON-файле ([`JsonFileStore`]). Другие хранилища
+//! подключаются реализацией этого трейта.
+//!
+//! ## Использование
+//! ```rust
+//! use rust_tic_tac_toe::Rules;
+//! use rust_tic_tac_toe::lobby::{Lobby, MemoryStore, SeekOutcome};
+//!
+//! let mut lobby = Lobby::new(Box::new(MemoryStore::default()));
+//! assert!(matches!(lobby.add_seek(1, "Alice", Rules::classic(), None), Ok(SeekOutcome::Posted(_))));
+//! assert!(matches!(lobby.add_seek(2, "Bob", Rules::classic(), None), Ok(SeekOutcome::Paired(_))));
+//! ```
+
+use std::collections::{BTreeMap, HashSet};
+use std::io;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::audit::AuditLog;
+use crate::online::{SeekInfo, TimeControl};
+use crate::rating;
+use crate::record::GameRecord;
+use crate::server::ConnectionId;
+use crate::{Player, Rules};
+
+/// Рейтинг нового игрока
+pub const DEFAULT_RATING: i32 = rating::DEFAULT_RATING as i32;
+
+/// Наибольшая разница рейтингов при автоматическом подборе
+pub const DEFAULT_RATING_GAP: i32 = 200;
+
+// === ХРАНИЛИЩА ===
+
+/// Хранилище рейтингов и сыгранных партий
+pub trait LobbyStore: Send {
+    /// Рейтинг игрока (`None` - игрок еще не играл)
+    fn rating(&self, name: &str) -> Option<i32>;
+
+    /// Сохраняет рейтинг игрока
+    fn set_rating(&mut self, name: &str, rating: i32) -> io::Result<()>;
+
+    /// Сохраняет сыгранную партию
+    fn save_game(&mut self, record: &GameRecord) -> io::Result<()>;
+
+    /// Все сохраненные партии
+    fn games(&self) -> Vec<GameRecord>;
+
+    /// Сохраняет журнал партии
+    fn save_audit(&mut self, log: &AuditLog) -> io::Result<()>;
+
+    /// Журнал партии по коду комнаты
+    fn audit(&self, code: &str) -> Option<AuditLog>;
+}
+
+/// Данные хранилища
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+struct StoreData {
+    ratings: BTreeMap<String, i32>,
+    games: Vec<GameRecord>,
+    #[serde(default)]
+    audits: Vec<AuditLog>,
+}
+
+/// Хранилище в памяти; данные теряются при перезапуске сервера
+#[derive(Default)]
+pub struct MemoryStore {
+    data: StoreData,
+}
+
+impl LobbyStore for MemoryStore {
+    fn rating(&self, name: &str) -> Option<i32> {
+        self.data.ratings.get(name).copied()
+    }
+
+    fn set_rating(&mut self, name: &str, rating: i32) -> io::Result<()> {
+        self.data.ratings.insert(name.to_string(), rating);
+        Ok(())
+    }
+
+    fn save_game(&mut self, record: &GameRecord) -> io::Result<()> {
+        self.data.games.push(record.clone());
+        Ok(())
+    }
+
+    fn games(&self) -> Vec<GameRecord> {
+        self.data.games.clone()
+    }
+
+    fn save_audit(&mut self, log: &AuditLog) -> io::Result<()> {
+        self.data.audits.push(log.clone());
+        Ok(())
+    }
+
+    fn audit(&self, code: &str) -> Option<AuditLog> {
+        self.data.audits.iter().rev().find(|log| log.code == code).cloned()
+    }
+}
+
+/// Хранилище в JSON-файле, который перезаписывается после каждого изменения
+pub struct JsonFileStore {
+    path: PathBuf,
+    data: StoreData,
+}
+
+impl JsonFileStore {
+    /// Открывает файл хранилища или начинает новое, если файла нет
+    pub fn open(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let data = match std::fs::read_to_string(&path) {
+            Ok(text) => serde_json::from_str(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => StoreData::default(),
+            Err(e) => return Err(e),
+        };
+        Ok(Self { path, data })
+    }
+
+    fn flush(&self) -> io::Result<()> {
+        let text = serde_json::to_string_pretty(&self.data).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        std::fs::write(&self.path, text)
+    }
+}
+
+impl LobbyStore for JsonFileStore {
+    fn rating(&self, name: &str) -> Option<i32> {
+        self.data.ratings.get(name).copied()
+    }
+
+    fn set_rating(&mut self, name: &str, rating: i32) -> io::Result<()> {
+        self.data.ratings.insert(name.to_string(), rating);
+        self.flush()
+    }
+
+    fn save_game(&mut self, record: &GameRecord) -> io::Result<()> {
+        self.data.games.push(record.clone());
+        self.flush()
+    }
+
+    fn games(&self) -> Vec<GameRecord> {
+        self.data.games.clone()
+    }
+
+    fn save_audit(&mut self, log: &AuditLog) -> io::Result<()> {
+        self.data.audits.push(log.clone());
+        self.flush()
+    }
+
+    fn audit(&self, code: &str) -> Option<AuditLog> {
+        self.data.audits.iter().rev().find(|log| log.code == code).cloned()
+    }
+}
+
+// === ЛОББИ ===
+
+/// Заявка на игру
+#[derive(Clone, Debug, PartialEq)]
+pub struct Seek {
+    /// Номер заявки
+    pub id: u64,
+    /// Подключение автора заявки
+    pub connection: ConnectionId,
+    /// Имя игрока
+    pub name: String,
+    /// Рейтинг игрока на момент заявки
+    pub rating: i32,
+    /// Правила партии
+    pub rules: Rules,
+    /// Контроль времени
+    pub time_control: Option<TimeControl>,
+}
+
+impl Seek {
+    /// Описание заявки для клиентов
+    pub fn info(&self) -> SeekInfo {
+        SeekInfo {
+            id: self.id,
+            name: self.name.clone(),
+            rating: self.rating,
+            rules: self.rules,
+            time_control: self.time_control,
+        }
+    }
+}
+
+/// Пара игроков для новой партии
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pairing {
+    /// Играет за X (автор более ранней заявки)
+    pub x: Seek,
+    /// Играет за O
+    pub o: Seek,
+}
+
+/// Результат выставления заявки
+#[derive(Clone, Debug, PartialEq)]
+pub enum SeekOutcome {
+    /// Заявка ждет соперника
+    Posted(u64),
+    /// Соперник найден сразу
+    Paired(Pairing),
+}
+
+/// Лобби с заявками и подписчиками
+pub struct Lobby {
+    seeks: Vec<Seek>,
+    subscribers: HashSet<ConnectionId>,
+    store: Box<dyn LobbyStore>,
+    next_id: u64,
+    /// Наибольшая разница рейтингов при автоматическом подборе
+    pub max_rating_gap: i32,
+    /// Коэффициент K: наибольшее изменение рейтинга за партию
+    pub k_factor: f64,
+}
+
+impl Lobby {
+    /// Создает пустое лобби с указанным хранилищем
+    pub fn new(store: Box<dyn LobbyStore>) -> Self {
+        Self {
+            seeks: Vec::new(),
+            subscribers: HashSet::new(),
+            store,
+            next_id: 1,
+            max_rating_gap: DEFAULT_RATING_GAP,
+            k_factor: rating::DEFAULT_K,
+        }
+    }
+
+    /// Открытые заявки в порядке выставления
+    pub fn seeks(&self) -> &[Seek] {
+        &self.seeks
+    }
+
+    /// Открытые заявки для рассылки клиентам
+    pub fn info(&self) -> Vec<SeekInfo> {
+        self.seeks.iter().map(Seek::info).collect()
+    }
+
+    /// Хранилище лобби
+    pub fn store(&self) -> &dyn LobbyStore {
+        self.store.as_ref()
+    }
+
+    /// Рейтинг игрока (новые игроки получают [`DEFAULT_RATING`])
+    pub fn rating(&self, name: &str) -> i32 {
+        self.store.rating(name).unwrap_or(DEFAULT_RATING)
+    }
+
+    /// Подписывает подключение на изменения списка заявок
+    pub fn subscribe(&mut self, connection: ConnectionId) {
+        self.subscribers.insert(connection);
+    }
+
+    /// Подписчики лобби
+    pub fn subscribers(&self) -> impl Iterator<Item = ConnectionId> + '_ {
+        self.subscribers.iter().copied()
+    }
+
+    /// Выставляет заявку или сразу подбирает соперника
+    ///
+    /// ## Параметры
+    /// - `connection`: подключение игрока (одна заявка на подключение)
+    /// - `name`: имя игрока
+    /// - `rules`, `time_control`: условия партии; подбираются только одинаковые
+    ///
+    /// ## Возвращает
+    /// - `Ok(SeekOutcome::Paired)` - найден соперник с близким рейтингом
+    /// - `Ok(SeekOutcome::Posted)` - заявка добавлена в список
+    /// - `Err` - у подключения уже есть заявка
+    pub fn add_seek(
+        &mut self,
+        connection: ConnectionId,
+        name: &str,
+        rules: Rules,
+        time_control: Option<TimeControl>,
+    ) -> Result<SeekOutcome, String> {
+        if self.seeks.iter().any(|seek| seek.connection == connection) {
+            return Err("заявка уже выставлена".to_string());
+        }
+        let seek = Seek {
+            id: self.next_id,
+            connection,
+            name: name.to_string(),
+            rating: self.rating(name),
+            rules,
+            time_control,
+        };
+        self.next_id += 1;
+
+        // Ближайший по рейтингу соперник с теми же условиями
+        let opponent = self
+            .seeks
+            .iter()
+            .enumerate()
+            .filter(|(_, other)| other.rules == rules && other.time_control == time_control)
+            // Партия с самим собой под одним именем только накручивала бы рейтинг
+            .filter(|(_, other)| other.name != seek.name)
+            .filter(|(_, other)| (other.rating - seek.rating).abs() <= self.max_rating_gap)
+            .min_by_key(|(_, other)| (other.rating - seek.rating).abs())
+            .map(|(index, _)| index);
+
+        match opponent {
+            Some(index) => Ok(SeekOutcome::Paired(Pairing { x: self.seeks.remove(index), o: seek })),
+            None => {
+                self.seeks.push(seek);
+                Ok(SeekOutcome::Posted(self.next_id - 1))
+            }
+        }
+    }
+
+    /// Принимает заявку из списка
+    pub fn accept(&mut self, id: u64, connection: ConnectionId, name: &str) -> Result<Pairing, String> {
+        let index = self.seeks.iter().position(|seek| seek.id == id).ok_or("заявка не найдена")?;
+        if self.seeks[index].connection == connection || self.seeks[index].name == name {
+            return Err("нельзя принять свою заявку".to_string());
+        }
+        // Своя заявка больше не нужна
+        self.cancel(connection);
+        let index = self.seeks.iter().position(|seek| seek.id == id).unwrap();
+        let x = self.seeks.remove(index);
+        let o = Seek {
+            id: 0,
+            connection,
+            name: name.to_string(),
+            rating: self.rating(name),
+            rules: x.rules,
+            time_control: x.time_control,
+        };
+        Ok(Pairing { x, o })
+    }
+
+    /// Снимает заявку подключения
+    ///
+    /// ## Возвращает
+    /// `true`, если заявка была
+    pub fn cancel(&mut self, connection: ConnectionId) -> bool {
+        let before = self.seeks.len();
+        self.seeks.retain(|seek| seek.connection != connection);
+        self.seeks.len() != before
+    }
+
+    /// Забывает отключившийся клиент
+    ///
+    /// ## Возвращает
+    /// `true`, если изменился список заявок
+    pub fn remove_connection(&mut self, connection: ConnectionId) -> bool {
+        self.subscribers.remove(&connection);
+        self.cancel(connection)
+    }
+
+    /// Сохраняет результат рейтинговой партии и пересчитывает рейтинги (Эло)
+    ///
+    /// Запись предварительно проигрывается заново: партия с недопустимыми
+    /// ходами или неверным результатом не меняет рейтинги. Партия игрока
+    /// с самим собой тоже не учитывается.
+    pub fn record_result(&mut self, record: &GameRecord) -> io::Result<()> {
+        if let Some(problem) = record.verify().first() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, problem.to_string()));
+        }
+        if record.x == record.o {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "оба игрока под одним именем"));
+        }
+        let (rating_x, rating_o) = (self.rating(&record.x), self.rating(&record.o));
+        let score_x = match record.winner {
+            Some(Player::X) => 1.0,
+            Some(Player::O) => 0.0,
+            None => 0.5,
+        };
+        let change = rating::elo_change(f64::from(rating_x), f64::from(rating_o), score_x, self.k_factor).round() as i32;
+
+        self.store.set_rating(&record.x, rating_x + change)?;
+        self.store.set_rating(&record.o, rating_o - change)?;
+        self.store.save_game(record)
+    }
+
+    /// Сохраняет журнал партии в хранилище
+    pub fn save_audit(&mut self, log: &AuditLog) -> io::Result<()> {
+        self.store.save_audit(log)
+    }
+}
+
+// === ТЕСТЫ ===
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Тест: подбор только при одинаковых условиях и близком рейтинге
+    #[test]
+    fn test_matchmaking() {
+        let mut store = MemoryStore::default();
+        store.set_rating("strong", 2000).unwrap();
+        let mut lobby = Lobby::new(Box::new(store));
+        let blitz = Some(TimeControl::fischer(300, 3));
+
+        assert_eq!(lobby.add_seek(1, "strong", Rules::classic(), None), Ok(SeekOutcome::Posted(1)));
+        assert_eq!(lobby.add_seek(2, "a", Rules::classic(), blitz), Ok(SeekOutcome::Posted(2)));
+        // Рейтинг 1500 слишком далек от 2000, а у заявки 2 другой контроль
+        assert_eq!(lobby.add_seek(3, "b", Rules::classic(), None), Ok(SeekOutcome::Posted(3)));
+        assert!(lobby.add_seek(3, "b", Rules::gomoku(), None).is_err());
+
+        match lobby.add_seek(4, "c", Rules::classic(), blitz).unwrap() {
+            SeekOutcome::Paired(pairing) => {
+                assert_eq!(pairing.x.name, "a");
+                assert_eq!(pairing.o.name, "c");
+            }
+            other => panic!("ожидалась пара, получено {:?}", other),
+        }
+        assert_eq!(lobby.seeks().len(), 2);
+
+        let pairing = lobby.accept(1, 3, "b").unwrap();
+        assert_eq!((pairing.x.name.as_str(), pairing.o.name.as_str()), ("strong", "b"));
+        assert!(lobby.seeks().is_empty(), "заявка принявшего тоже снята");
+    }
+
+    /// Тест: рейтинговая партия под одним именем не создается
+    #[test]
+    fn test_refuses_same_name_pairing() {
+        let mut lobby = Lobby::new(Box::new(MemoryStore::default()));
+
+        assert_eq!(lobby.add_seek(1, "alice", Rules::classic(), None), Ok(SeekOutcome::Posted(1)));
+        assert_eq!(lobby.add_seek(2, "alice", Rules::classic(), None), Ok(SeekOutcome::Posted(2)));
+        assert!(lobby.accept(1, 3, "alice").is_err());
+        assert_eq!(lobby.seeks().len(), 2, "заявки остались в лобби");
+    }
+
+    /// Тест: рейтинги меняются после партии и сохраняются в файл
+    #[test]
+    fn test_ratings_persist_in_file() {
+        let path = std::env::temp_dir().join(format!("ttt_lobby_{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let mut lobby = Lobby::new(Box::new(JsonFileStore::open(&path).unwrap()));
+        let record = GameRecord { rules: Rules::classic(), x: "a".into(), o: "b".into(), moves: vec![0, 3, 1, 4, 2], winner: Some(Player::X), termination: None };
+        lobby.record_result(&record).unwrap();
+        assert_eq!(lobby.rating("a"), 1516);
+        assert_eq!(lobby.rating("b"), 1484);
+
+        // Подделанный результат не принимается
+        let forged = GameRecord { winner: Some(Player::O), ..record.clone() };
+        assert!(lobby.record_result(&forged).is_err());
+        assert_eq!(lobby.rating("a"), 1516);
+
+        let reopened = JsonFileStore::open(&path).unwrap();
+        assert_eq!(reopened.rating("a"), Some(1516));
+        assert_eq!(reopened.games(), vec![record]);
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -16,12 +16,72 @@
 //! cargo run -- --gui
 //! ```
 //! 
+//! ### Выбор игроков
+//! Каждой стороне можно назначить человека или ИИ (`human`, `threat`, `mcts`):
+//! ```bash
+//! cargo run -- --x human --o mcts
+//! cargo run -- --gui --x threat --o human
+//! ```
+//! 
+//! ### Матч
+//! Счет ведется между партиями, участники по очереди играют за X.
+//! Формат `bo3` - лучший из 3 партий, `ft2` - до 2 побед:
+//! ```bash
+//! cargo run -- --x human --o mcts --match bo3
+//! ```
+//! 
+//! ### Язык
+//! Консоль говорит на языке из `--lang`, а если он не указан - из переменных
+//! `LC_ALL`, `LC_MESSAGES` или `LANG`:
+//! ```bash
+//! cargo run -- --lang en
+//! LANG=ru_RU.UTF-8 cargo run
+//! ```
+//! 
+//! ### Турнир между движками
+//! ```bash
+//! cargo run --release -- tournament --agents threat,mcts --rules gomoku --games 20 --threads 4
+//! ```
+//! Дополнительные параметры: `--format round-robin|gauntlet`, `--sprt elo0,elo1`,
+//! `--seed N`, `--json файл`, `--records файл`.
+//! 
+//! ### Игра по сети
+//! Один игрок создает партию, другой подключается к ней по адресу:
+//! ```bash
+//! cargo run -- --host 0.0.0.0:7878 --name Alice
+//! cargo run -- --join 192.168.0.10:7878 --name Bob
+//! ```
+//! С флагом `--gui` сетевая партия открывается в графической версии.
+//! 
+//! ### Статистика
+//! Законченные партии сохраняются в файл `~/.rust_tic_tac_toe_stats.json`.
+//! Счет игрока по соперникам, вариантам и видам ИИ:
+//! ```bash
+//! cargo run -- stats --name Alice
+//! ```
+//! 
+//! ### Проверка переводов
+//! Каталоги `.ftl` должны содержать все строки и не содержать лишних.
+//! Проверка встроенных каталогов и каталогов из указанной папки:
+//! ```bash
+//! cargo run -- check-locales путь/к/locales
+//! ```
+//! 
+//! ### Режим движка
+//! Встроенный ИИ обслуживает текстовый протокол через stdin/stdout
+//! (см. модуль `protocol`), чтобы его можно было подключать к другим программам:
+//! ```bash
+//! cargo run --release -- engine --agent mcts
+//! ```
+//! 
 //! ## Управление
 //! 
 //! **Консольная версия:**
 //! - Числа 1-9 для выбора позиции
+//! - 'resign', 'draw', 'accept', 'decline', 'abort' - сдаться, ничья, прервать партию
+//! - 'stats' - статистика сыгранных партий
 //! - 'q' или 'quit' для выхода
-//! - 'y' для новой игры, 'n' для завершения
+//! - 'y' или 'д' для новой игры, 'n' или 'н' для завершения (ответы берутся из каталога языка)
 //! 
 //! **Графическая версия:**
 //! - Клики мышью по клеткам
@@ -30,7 +90,14 @@
 
 use std::io::{self, Write};
 use std::env;
-use rust_tic_tac_toe::TicTacToe;
+use rust_tic_tac_toe::agent::{self, Agent, AgentKind, EngineAgent};
+use rust_tic_tac_toe::tournament::{Format, Participant, SprtConfig, Tournament, TournamentConfig};
+use rust_tic_tac_toe::net::{self, NetEvent, NetGame, NetHost};
+use rust_tic_tac_toe::protocol;
+use rust_tic_tac_toe::series::{Match, MatchFormat};
+use rust_tic_tac_toe::stats::{self, JsonStatsStore, MemoryStatsStore, Stats};
+use rust_tic_tac_toe::i18n;
+use rust_tic_tac_toe::{Language, Localization, MessageKey, Player, PlayerNames, Rules, Termination, TicTacToe};
 
 /// Модуль графического интерфейса
 mod gui;
@@ -40,20 +107,325 @@ mod gui;
 /// Анализирует аргументы командной строки и запускает соответствующий режим:
 /// - Без аргументов: консольная версия
 /// - С аргументом `--gui`: графическая версия
+/// - `--x <агент>` и `--o <агент>`: кто играет за каждую сторону
+/// - `--host <адрес>` или `--join <адрес>`: игра по сети
+/// - `--lang <код>`: язык интерфейса (`ru`, `en`, `en-US`)
 fn main() {
     let args: Vec<String> = env::args().collect();
+    let localization = console_localization(&args);
+    
+    match args.get(1).map(String::as_str) {
+        Some("tournament") => {
+            if let Err(e) = run_tournament(&without_lang(&args[2..]), &localization) {
+                eprintln!("{}", localization.format(MessageKey::ConsoleTournamentError, &[("error", e.into())]));
+            }
+            return;
+        }
+        Some("engine") => {
+            run_engine(&without_lang(&args[2..]), &localization);
+            return;
+        }
+        Some("check-locales") => {
+            if !check_locales(args.get(2).filter(|arg| !arg.starts_with("--")), &localization) {
+                std::process::exit(1);
+            }
+            return;
+        }
+        Some("stats") => {
+            let default_name = localization.get_text(MessageKey::ConsoleDefaultName);
+            let name = option_value(&args[2..], "--name").unwrap_or(&default_name);
+            print!("{}", open_stats(&localization).report(name, &localization));
+            return;
+        }
+        _ => {}
+    }
+    let mut gui_mode = false;
+    let mut sides = [AgentKind::Human, AgentKind::Human];
+    let mut network = None;
+    let mut name = localization.get_text(MessageKey::ConsoleDefaultName);
+    let mut format = MatchFormat::Open;
+    
+    let mut iter = args.iter().skip(1);
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--gui" => gui_mode = true,
+            "--x" | "--o" => {
+                let index = if arg == "--x" { 0 } else { 1 };
+                match iter.next().and_then(|name| AgentKind::parse(name)) {
+                    Some(kind) => sides[index] = kind,
+                    None => {
+                        eprintln!("{}", localization.format(MessageKey::ConsoleUnknownAgent, &[("option", arg.as_str().into())]));
+                        return;
+                    }
+                }
+            }
+            "--host" | "--join" => match iter.next() {
+                Some(addr) => network = Some((arg == "--host", addr.clone())),
+                None => {
+                    eprintln!("{}", localization.format(MessageKey::ConsoleMissingAddress, &[("option", arg.as_str().into())]));
+                    return;
+                }
+            },
+            "--name" => {
+                if let Some(value) = iter.next() {
+                    name = value.clone();
+                }
+            }
+            "--lang" => {
+                // Язык уже выбран в console_localization
+                iter.next();
+            }
+            "--match" => match iter.next().map(|text| text.parse()) {
+                Some(Ok(value)) => format = value,
+                Some(Err(e)) => {
+                    eprintln!("{}", e);
+                    return;
+                }
+                None => {
+                    eprintln!("{}", localization.get_text(MessageKey::ConsoleMissingMatch));
+                    return;
+                }
+            },
+            _ => {}
+        }
+    }
     
-    if args.len() > 1 && args[1] == "--gui" {
+    if let Some((host, addr)) = network {
+        if gui_mode {
+            let mut gui = gui::TicTacToeGUI::new();
+            if let Some(locale) = option_value(&args, "--lang") {
+                gui.set_locale(locale);
+            }
+            gui.connect(host, &addr, &name);
+            if let Err(e) = gui.run() {
+                eprintln!("{}", localization.format(MessageKey::ConsoleGuiError, &[("error", e.to_string().into())]));
+            }
+            return;
+        }
+        match connect(host, &addr, &name, &localization) {
+            Ok(net) => run_network_game(net, &name, &localization),
+            Err(e) => eprintln!("{}", localization.format(MessageKey::NetError, &[("error", e.to_string().into())])),
+        }
+        return;
+    }
+    
+    if gui_mode {
         // Запуск графической версии
-        println!("Запуск графической версии...");
+        println!("{}", localization.get_text(MessageKey::ConsoleStartingGui));
         let mut gui = gui::TicTacToeGUI::new();
+        gui.set_sides(sides[0], sides[1]);
+        if let Some(locale) = option_value(&args, "--lang") {
+            gui.set_locale(locale);
+        }
         if let Err(e) = gui.run() {
-            eprintln!("Ошибка запуска GUI: {}", e);
-            eprintln!("Попробуйте запустить консольную версию: cargo run");
+            eprintln!("{}", localization.format(MessageKey::ConsoleGuiError, &[("error", e.to_string().into())]));
+            eprintln!("{}", localization.get_text(MessageKey::ConsoleGuiFallback));
         }
     } else {
         // Запуск консольной версии
-        run_console_game();
+        run_console_game(sides, &name, format, &localization);
+    }
+}
+
+/// Значение параметра командной строки: `--flag значение`
+fn option_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    args.windows(2).find(|pair| pair[0] == flag).map(|pair| pair[1].as_str())
+}
+
+/// Аргументы подкоманды без общего для всех режимов `--lang <код>`
+fn without_lang(args: &[String]) -> Vec<String> {
+    let mut rest = Vec::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg == "--lang" {
+            iter.next();
+        } else {
+            rest.push(arg.clone());
+        }
+    }
+    rest
+}
+
+/// Локализация консоли
+///
+/// Язык берется из `--lang`, затем из `LC_ALL`, `LC_MESSAGES` и `LANG`;
+/// если язык нигде не задан, используется русский. Дополнительные каталоги
+/// загружаются из [`i18n::LOCALES_ENV`].
+fn console_localization(args: &[String]) -> Localization {
+    let mut localization = Localization::new(Language::Russian);
+    if let Some(locale) = option_value(args, "--lang").map(str::to_string).or_else(i18n::env_locale) {
+        localization.set_locale(&locale);
+    }
+    if let Err(e) = localization.load_env_dir() {
+        eprintln!("{}", localization.format(MessageKey::ConsoleLocalesError, &[("error", e.to_string().into())]));
+    }
+    localization
+}
+
+/// Запускает встроенный движок в режиме текстового протокола
+/// 
+/// ## Параметры
+/// - `args`: аргументы после `engine`; `--agent threat|mcts` выбирает движок
+/// - `localization`: язык сообщений об ошибках
+fn run_engine(args: &[String], localization: &Localization) {
+    let kind = match args {
+        [flag, name] if flag == "--agent" => AgentKind::parse(name),
+        [] => Some(AgentKind::ThreatSpace),
+        _ => None,
+    };
+    let Some(mut engine) = kind.and_then(|kind| kind.engine(Rules::classic())) else {
+        eprintln!("{}", localization.get_text(MessageKey::ConsoleEngineUsage));
+        return;
+    };
+    
+    let stdin = io::stdin();
+    if let Err(e) = protocol::serve(engine.as_mut(), stdin.lock(), io::stdout()) {
+        eprintln!("{}", localization.format(MessageKey::ConsoleProtocolError, &[("error", e.to_string().into())]));
+    }
+}
+
+/// Запускает турнир между движками
+/// 
+/// ## Параметры
+/// - `args`: аргументы командной строки после `tournament`
+/// - `localization`: язык сообщений
+///
+/// ## Возвращает
+/// Описание ошибки на языке консоли, если параметры неверны
+fn run_tournament(args: &[String], localization: &Localization) -> Result<(), String> {
+    let mut config = TournamentConfig::default();
+    let mut kinds = vec![AgentKind::ThreatSpace, AgentKind::Mcts];
+    let mut json_path = None;
+    let mut records_path = None;
+    let mut gauntlet = false;
+    
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let option = || ("option", arg.as_str().into());
+        let invalid = |text: &str| localization.format(MessageKey::ConsoleInvalidValue, &[option(), ("value", text.into())]);
+        let mut value =
+            || iter.next().cloned().ok_or_else(|| localization.format(MessageKey::ConsoleMissingValue, &[option()]));
+        match arg.as_str() {
+            "--agents" => {
+                kinds = value()?
+                    .split(',')
+                    .map(|name| match AgentKind::parse(name) {
+                        Some(AgentKind::Human) | None => {
+                            Err(localization.format(MessageKey::ConsoleUnknownEngine, &[("name", name.into())]))
+                        }
+                        Some(kind) => Ok(kind),
+                    })
+                    .collect::<Result<_, _>>()?;
+            }
+            "--rules" => {
+                // Ошибка разбора правил не переведена, поэтому заменяем ее своей
+                let text = value()?;
+                config.rules = text.parse().map_err(|_| {
+                    let args = [("rules", text.as_str().into()), ("max", Rules::MAX_SIZE.into())];
+                    localization.format(MessageKey::ConsoleInvalidRules, &args)
+                })?;
+            }
+            "--games" => {
+                let text = value()?;
+                config.games_per_pair = text.parse().map_err(|_| invalid(&text))?;
+            }
+            "--threads" => {
+                let text = value()?;
+                config.threads = text.parse().map_err(|_| invalid(&text))?;
+            }
+            "--seed" => {
+                let text = value()?;
+                config.seed = text.parse().map_err(|_| invalid(&text))?;
+            }
+            "--format" => match value()?.as_str() {
+                "round-robin" => gauntlet = false,
+                "gauntlet" => gauntlet = true,
+                other => return Err(invalid(other)),
+            },
+            "--sprt" => {
+                let text = value()?;
+                let (elo0, elo1) = text.split_once(',').ok_or_else(|| invalid(&text))?;
+                config.sprt = Some(SprtConfig {
+                    elo0: elo0.parse().map_err(|_| invalid(&text))?,
+                    elo1: elo1.parse().map_err(|_| invalid(&text))?,
+                    ..Default::default()
+                });
+            }
+            "--json" => json_path = Some(value()?),
+            "--records" => records_path = Some(value()?),
+            _ => return Err(localization.format(MessageKey::ConsoleUnknownOption, &[option()])),
+        }
+    }
+    if kinds.len() < 2 {
+        return Err(localization.get_text(MessageKey::ConsoleTooFewEngines));
+    }
+    if gauntlet {
+        config.format = Format::Gauntlet { challenger: 0 };
+    }
+    
+    // Одинаковые движки различаем по номеру
+    let participants = kinds
+        .iter()
+        .enumerate()
+        .map(|(index, &kind)| {
+            let name = format!("{:?}#{}", kind, index + 1);
+            Participant::new(name, move |rules, seed| {
+                Box::new(EngineAgent::new(kind.engine_seeded(rules, seed).unwrap())) as Box<dyn Agent + Send>
+            })
+        })
+        .collect();
+    
+    println!("{}", localization.format(MessageKey::ConsoleTournamentStart, &[("games", config.games_per_pair.into())]));
+    let report = Tournament::new(participants, config)?.run();
+    println!("{}", report.summary(localization));
+    
+    if let Some(path) = json_path {
+        std::fs::write(&path, report.to_json()).map_err(|e| format!("{}: {}", path, e))?;
+    }
+    if let Some(path) = records_path {
+        std::fs::write(&path, report.records_notation()).map_err(|e| format!("{}: {}", path, e))?;
+    }
+    Ok(())
+}
+
+/// Создает синхронного агента для консольной версии
+///
+/// Для человека возвращает `None`: его ходы и команды читает сам игровой цикл.
+fn console_agent(kind: AgentKind, game: &TicTacToe) -> Option<Box<dyn Agent>> {
+    kind.engine(game.rules()).map(|engine| Box::new(EngineAgent::new(engine)) as Box<dyn Agent>)
+}
+
+/// Ввод игрока в консоли: ход или команда
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum ConsoleInput {
+    /// Ход в клетку (с нуля)
+    Move(usize),
+    /// `resign` - сдаться
+    Resign,
+    /// `draw` - предложить ничью
+    OfferDraw,
+    /// `accept` - принять ничью
+    AcceptDraw,
+    /// `decline` - отклонить ничью
+    DeclineDraw,
+    /// `abort` - прервать партию до своего первого хода
+    Abort,
+    /// `stats` - показать статистику
+    Stats,
+}
+
+impl ConsoleInput {
+    /// Разбирает введенную строку
+    fn parse(text: &str) -> Option<Self> {
+        match text {
+            "resign" => Some(Self::Resign),
+            "draw" => Some(Self::OfferDraw),
+            "accept" => Some(Self::AcceptDraw),
+            "decline" => Some(Self::DeclineDraw),
+            "abort" => Some(Self::Abort),
+            "stats" => Some(Self::Stats),
+            _ => text.parse::<usize>().ok().map(|n| Self::Move(n.wrapping_sub(1))),
+        }
     }
 }
 
@@ -64,102 +436,466 @@ fn main() {
 /// - Принимает ввод от пользователя
 /// - Показывает статус игры
 /// - Предлагает начать новую игру
-fn run_console_game() {
+/// 
+/// ## Параметры
+/// - `sides`: агенты для сторон X и O
+/// - `name`: имя человека для статистики
+/// - `format`: формат матча; между партиями печатается счет, а участники меняются цветом
+/// - `localization`: язык сообщений и ответов на вопросы
+fn run_console_game(mut sides: [AgentKind; 2], name: &str, format: MatchFormat, localization: &Localization) {
     let mut game = TicTacToe::new();
+    let mut agents = [console_agent(sides[0], &game), console_agent(sides[1], &game)];
+    let mut stats = open_stats(localization);
+    let mut participants = sides.map(|kind| match kind {
+        AgentKind::Human => stats::Participant::human(name),
+        kind => stats::Participant::ai(kind),
+    });
+    let mut series = match &participants {
+        [x, o] if x.name == o.name => Match::new(format, &format!("{} 1", x.name), &format!("{} 2", o.name)),
+        [x, o] => Match::new(format, &x.name, &o.name),
+    };
+    let [x_name, o_name] = sides.map(|kind| match kind {
+        AgentKind::Human => name.to_string(),
+        kind => localization.get_text(kind.text_key()),
+    });
+    let mut names = PlayerNames::new(&x_name, &o_name);
+    let mut started_ms = stats::unix_time_ms();
     
     // Приветствие и инструкции
-    println!("Добро пожаловать в Крестики-нолики!");
-    println!("Используйте числа 1-9 для хода:");
+    println!("{}", localization.get_text(MessageKey::ConsoleWelcome));
+    println!("{}", localization.get_text(MessageKey::ConsoleHowToMove));
     println!("1 | 2 | 3");
     println!("---------");
     println!("4 | 5 | 6");
     println!("---------");
     println!("7 | 8 | 9");
     println!();
-    println!("Для запуска графической версии используйте: cargo run -- --gui");
+    println!("{}", localization.get_text(MessageKey::ConsoleCommands));
+    println!();
+    println!("{}", localization.get_text(MessageKey::ConsoleGuiHint));
     println!();
     
     // Главный игровой цикл
-    while !game.is_game_over() {
+    loop {
+        if game.is_game_over() {
+            // Показываем финальное состояние и результат
+            game.display_board();
+            if let Some(termination) = game.termination() {
+                println!("{}.", termination.describe(localization, &names));
+            }
+            if game.termination() != Some(Termination::Aborted) {
+                print_result(game.get_winner(), &names, localization);
+            }
+            if let Err(e) = stats.record(&game, &participants[0], &participants[1], started_ms) {
+                println!("{}", localization.format(MessageKey::StatsError, &[("error", e.to_string().into())]));
+            }
+            if series.record(&game) {
+                // Участники матча меняются цветом
+                sides.swap(0, 1);
+                agents.swap(0, 1);
+                participants.swap(0, 1);
+                names.swap();
+            }
+            println!("{}", localization.format(MessageKey::MatchScore, &[("score", series.to_string().into())]));
+            if series.is_over() {
+                match series.winner() {
+                    Some(index) => {
+                        let winner = series.players()[index].as_str();
+                        println!("{}", localization.format(MessageKey::MatchWinner, &[("name", winner.into())]));
+                    }
+                    None => println!("{}", localization.get_text(MessageKey::MatchDrawn)),
+                }
+            }
+            
+            // Предлагаем начать новую игру
+            if ask_play_again(localization) {
+                // Сбрасываем игру и продолжаем
+                game.reset();
+                for agent in agents.iter_mut().flatten() {
+                    agent.new_game(&game);
+                }
+                started_ms = stats::unix_time_ms();
+                if series.is_over() {
+                    series.reset();
+                    println!("\n{}", localization.get_text(MessageKey::ConsoleNewMatch));
+                } else {
+                    println!("\n{}!", localization.get_text(MessageKey::NewGame));
+                }
+                let first = series.players()[series.x_index()].as_str();
+                println!("{}", localization.format(MessageKey::ConsolePlaysX, &[("name", first.into())]));
+                println!();
+                continue;
+            }
+            // Завершаем игру
+            println!("{}", localization.get_text(MessageKey::ConsoleThanks));
+            break;
+        }
+        
         // Отображаем текущее состояние игры
         game.display_board();
-        println!("Ход игрока: {}", game.current_player_symbol());
+        let player = game.current_player();
+        println!("{}", localization.format(MessageKey::CurrentPlayerTurn, &[("player", names.label(player, localization).into())]));
         
-        // Получаем ход от пользователя
-        let position = get_user_input();
-        if let Some(pos) = position {
-            // Проверяем корректность хода
-            if !game.make_move(pos) {
-                println!("Неверный ход! Попробуйте снова.");
-                continue;
+        let current = match player {
+            Player::X => 0,
+            Player::O => 1,
+        };
+        if let Some(offerer) = game.draw_offer().filter(|&offerer| offerer != player) {
+            println!("{}", localization.format(MessageKey::ConsoleDrawOffered, &[("player", names.label(offerer, localization).into())]));
+        }
+        
+        // Человек вводит ход или команду, остальные агенты только ходят
+        let pos = if sides[current] == AgentKind::Human {
+            match get_user_input(localization) {
+                Some(ConsoleInput::Move(pos)) => pos,
+                Some(ConsoleInput::Stats) => {
+                    print!("{}", stats.report(name, localization));
+                    continue;
+                }
+                Some(command) => {
+                    run_console_command(&mut game, command, sides[1 - current] != AgentKind::Human, localization);
+                    continue;
+                }
+                None => {
+                    println!("{}", localization.get_text(MessageKey::ConsoleInvalidInput));
+                    continue;
+                }
             }
         } else {
-            println!("Неверный ввод! Введите число от 1 до 9.");
+            // Недопустимый ход ИИ тоже означает, что хода нет
+            let legal = |&pos: &usize| pos < game.get_board().len() && game.cell(pos).is_none();
+            match agents[current].as_mut().and_then(|agent| agent.choose_move(&game)).filter(legal) {
+                Some(pos) => pos,
+                None => {
+                    println!("{}", localization.format(MessageKey::ConsoleNoMove, &[("name", names.label(player, localization).into())]));
+                    game.adjudicate(Some(player.opponent()));
+                    continue;
+                }
+            }
+        };
+        
+        // Проверяем корректность хода
+        if !game.make_move(pos) {
+            println!("{}", localization.get_text(MessageKey::ConsoleInvalidMove));
             continue;
         }
+        if sides[current] != AgentKind::Human {
+            let mover = names.label(player, localization);
+            println!("{}", localization.format(MessageKey::ConsoleMove, &[("name", mover.into()), ("position", (pos + 1).into())]));
+        }
+        if let Some(agent) = &mut agents[1 - current] {
+            agent.observe_move(&game, pos);
+        }
+    }
+}
+
+/// Выполняет команду игрока, чей сейчас ход
+///
+/// ## Параметры
+/// - `opponent_is_ai`: соперник - ИИ, он сразу отвечает на предложение ничьей
+/// - `localization`: язык сообщений
+fn run_console_command(game: &mut TicTacToe, command: ConsoleInput, opponent_is_ai: bool, localization: &Localization) {
+    let player = game.current_player();
+    let done = match command {
+        ConsoleInput::Resign => game.resign(player),
+        ConsoleInput::OfferDraw => game.offer_draw(player),
+        ConsoleInput::AcceptDraw => game.accept_draw(player),
+        ConsoleInput::DeclineDraw => game.decline_draw(player),
+        ConsoleInput::Abort => game.abort(player),
+        ConsoleInput::Move(_) | ConsoleInput::Stats => false,
+    };
+    if !done {
+        println!("{}", localization.get_text(MessageKey::ConsoleNotNow));
+        return;
+    }
+    if command == ConsoleInput::OfferDraw && opponent_is_ai {
+        if agent::accepts_draw(game) {
+            game.accept_draw(player.opponent());
+            println!("{}", localization.get_text(MessageKey::ConsoleDrawAccepted));
+        } else {
+            game.decline_draw(player.opponent());
+            println!("{}", localization.get_text(MessageKey::ConsoleDrawRejected));
+        }
+    }
+}
+
+/// Создает сетевую партию или подключается к ней
+/// 
+/// ## Параметры
+/// - `host`: `true` - ждать соперника на адресе, `false` - подключиться к адресу
+/// - `addr`: адрес вида `host:port`
+/// - `name`: имя локального игрока
+/// - `localization`: язык сообщений
+fn connect(host: bool, addr: &str, name: &str, localization: &Localization) -> io::Result<NetGame> {
+    if host {
+        let host = NetHost::bind(addr, name, Rules::classic())?;
+        let address = host.local_addr()?.to_string();
+        println!("{}", localization.format(MessageKey::ConsoleWaitingPeer, &[("address", address.into())]));
+        host.accept()
+    } else {
+        println!("{}", localization.format(MessageKey::ConsoleConnectingTo, &[("address", addr.into())]));
+        net::join(addr, name)
+    }
+}
+
+/// Запускает сетевую партию в консоли
+/// 
+/// Во время своего хода игрок вводит номер клетки или команду:
+/// - `resign` - сдаться
+/// - `draw`, `accept`, `decline` - предложить ничью или ответить на предложение
+/// - `abort` - прервать партию до своего первого хода
+/// - `say <текст>` - сообщение сопернику
+/// - `stats` - статистика сыгранных партий
+///
+/// Каждая законченная партия сохраняется в статистику под именем `name`.
+/// Сообщения печатаются на языке `localization`.
+fn run_network_game(mut net: NetGame, name: &str, localization: &Localization) {
+    let mut stats = open_stats(localization);
+    let mut started_ms = stats::unix_time_ms();
+    let you_play = |net: &NetGame| localization.format(MessageKey::NetYouPlay, &[("player", net.local_player().mark(localization).into())]);
+    println!("{}. {}.", localization.format(MessageKey::NetOpponent, &[("name", net.peer_name().into())]), you_play(&net));
+    println!("{}", localization.get_text(MessageKey::ConsoleNetCommands));
+    println!();
+    
+    let mut redraw = true;
+    loop {
+        if redraw {
+            net.game().display_board();
+            redraw = false;
+        }
         
-        // Проверяем, закончилась ли игра
-        if game.is_game_over() {
-            // Показываем финальное состояние
-            game.display_board();
-            
-            // Отображаем результат
-            match game.get_winner() {
-                Some(player) => println!("Победитель: {}!", player.symbol()),
-                None => println!("Ничья!"),
+        if net.is_finished() {
+            match (net.resigned(), net.game().termination()) {
+                (Some(player), _) if player == net.local_player() => println!("{}.", localization.get_text(MessageKey::YouResigned)),
+                (Some(_), _) => println!("{}.", localization.get_text(MessageKey::PeerResigned)),
+                (None, Some(Termination::Aborted)) => println!("{}.", localization.get_text(MessageKey::TerminationAborted)),
+                (None, Some(Termination::Agreement)) => println!("{}.", localization.get_text(MessageKey::TerminationAgreement)),
+                (None, _) => print_result(net.game().get_winner(), &net_names(&net, name), localization),
             }
-            
-            // Предлагаем начать новую игру
-            print!("Хотите сыграть еще раз? (y/n): ");
-            io::stdout().flush().unwrap();
-            
-            let mut input = String::new();
-            io::stdin().read_line(&mut input).unwrap();
-            
-            if input.trim().to_lowercase() == "y" {
-                // Сбрасываем игру и продолжаем
-                game.reset();
-                println!("\nНовая игра!");
-                println!();
-            } else {
-                // Завершаем игру
-                println!("Спасибо за игру!");
-                break;
+            let (me, peer) = (stats::Participant::human(name), stats::Participant::other(net.peer_name()));
+            let (x, o) = match net.local_player() {
+                Player::X => (&me, &peer),
+                Player::O => (&peer, &me),
+            };
+            if let Err(e) = stats.record(net.game(), x, o, started_ms) {
+                println!("{}", localization.format(MessageKey::StatsError, &[("error", e.to_string().into())]));
+            }
+            if !ask_play_again(localization) {
+                println!("{}", localization.get_text(MessageKey::ConsoleThanks));
+                return;
+            }
+            let started = match net.request_rematch() {
+                Ok(started) => started,
+                Err(e) => {
+                    println!("{}", localization.format(MessageKey::NetError, &[("error", e.to_string().into())]));
+                    return;
+                }
+            };
+            if !started {
+                println!("{}", localization.get_text(MessageKey::ConsoleWaitingRematch));
+                loop {
+                    match net.wait_event() {
+                        NetEvent::RematchStarted => break,
+                        NetEvent::Disconnected => {
+                            println!("{}.", localization.get_text(MessageKey::PeerDisconnected));
+                            return;
+                        }
+                        event => print_net_event(&event, net.peer_name(), localization),
+                    }
+                }
             }
+            started_ms = stats::unix_time_ms();
+            println!("\n{}! {}.", localization.get_text(MessageKey::NewGame), you_play(&net));
+            redraw = true;
+            continue;
+        }
+        
+        if !net.is_local_turn() {
+            println!("{}", localization.get_text(MessageKey::ConsolePeerTurn));
+            match net.wait_event() {
+                NetEvent::Disconnected => {
+                    println!("{}.", localization.get_text(MessageKey::PeerDisconnected));
+                    return;
+                }
+                NetEvent::PeerMove(pos) => {
+                    let peer = net_names(&net, name).label(net.local_player().opponent(), localization);
+                    let args = [("name", peer.into()), ("position", (pos + 1).into())];
+                    println!("{}", localization.format(MessageKey::ConsoleMove, &args));
+                    redraw = true;
+                }
+                event => print_net_event(&event, net.peer_name(), localization),
+            }
+            continue;
+        }
+        
+        // Показываем то, что пришло, пока игрок думал
+        for event in net.poll() {
+            if event == NetEvent::Disconnected {
+                println!("{}.", localization.get_text(MessageKey::PeerDisconnected));
+                return;
+            }
+            print_net_event(&event, net.peer_name(), localization);
+        }
+        if net.is_finished() {
+            continue;
+        }
+        
+        let prompt = localization.format(MessageKey::ConsoleYourTurn, &[("player", net.local_player().mark(localization).into())]);
+        let command = read_command(&prompt, localization);
+        let result = if let Some(text) = command.strip_prefix("say ") {
+            net.chat(text).map_err(net::NetError::from)
+        } else {
+            match ConsoleInput::parse(&command) {
+                Some(ConsoleInput::Move(pos)) => {
+                    redraw = true;
+                    net.send_move(pos)
+                }
+                Some(ConsoleInput::Resign) => net.resign().map_err(net::NetError::from),
+                Some(ConsoleInput::OfferDraw) => net.offer_draw(),
+                Some(ConsoleInput::AcceptDraw) => net.accept_draw(),
+                Some(ConsoleInput::DeclineDraw) => net.decline_draw(),
+                Some(ConsoleInput::Abort) => net.abort(),
+                Some(ConsoleInput::Stats) => {
+                    print!("{}", stats.report(name, localization));
+                    Ok(())
+                }
+                None => {
+                    println!("{}", localization.get_text(MessageKey::ConsoleInvalidInput));
+                    continue;
+                }
+            }
+        };
+        if let Err(e) = result {
+            println!("{}", e);
+            redraw = false;
         }
     }
 }
 
+/// Проверяет полноту встроенных каталогов перевода и каталогов из папки
+///
+/// Итог печатается на языке `localization`.
+///
+/// ## Возвращает
+/// `true`, если во всех каталогах есть все строки и нет лишних
+fn check_locales(dir: Option<&String>, localization: &Localization) -> bool {
+    let mut catalogs = Localization::new(Language::English);
+    if let Some(dir) = dir {
+        if let Err(e) = catalogs.load_dir(dir) {
+            let args = [("dir", dir.as_str().into()), ("error", e.to_string().into())];
+            println!("{}", localization.format(MessageKey::ConsoleLocalesDirError, &args));
+            return false;
+        }
+    }
+    let problems = catalogs.check();
+    for problem in &problems {
+        println!("{}", problem.describe(localization));
+    }
+    if problems.is_empty() {
+        let locales = catalogs.locales().collect::<Vec<_>>().join(", ");
+        println!("{}", localization.format(MessageKey::ConsoleCatalogsComplete, &[("locales", locales.into())]));
+    }
+    problems.is_empty()
+}
+
+/// Открывает файл статистики; при ошибке статистика ведется только в памяти
+fn open_stats(localization: &Localization) -> Stats {
+    match JsonStatsStore::open_default() {
+        Ok(store) => Stats::new(Box::new(store)),
+        Err(e) => {
+            eprintln!("{}", localization.format(MessageKey::ConsoleStatsNotSaved, &[("error", e.to_string().into())]));
+            Stats::new(Box::new(MemoryStatsStore::default()))
+        }
+    }
+}
+
+/// Печатает событие сетевой игры; `peer` - имя соперника
+fn print_net_event(event: &NetEvent, peer: &str, localization: &Localization) {
+    let text = |key| localization.get_text(key);
+    let error = |key, reason: &String| localization.format(key, &[("error", reason.as_str().into())]);
+    match event {
+        NetEvent::PeerResigned => println!("{}.", text(MessageKey::PeerResigned)),
+        NetEvent::DrawOffered => println!("{}", localization.format(MessageKey::ConsoleDrawOffered, &[("player", peer.into())])),
+        NetEvent::DrawDeclined => println!("{}.", text(MessageKey::DrawDeclined)),
+        NetEvent::DrawAgreed => println!("{}.", text(MessageKey::TerminationAgreement)),
+        NetEvent::PeerAborted => println!("{}", text(MessageKey::ConsolePeerAborted)),
+        NetEvent::RematchRequested => println!("{}.", text(MessageKey::RematchRequested)),
+        NetEvent::Chat(chat) => println!("{}", localization.format(MessageKey::ConsoleChat, &[("text", chat.as_str().into())])),
+        NetEvent::PeerError(reason) => println!("{}", error(MessageKey::ConsolePeerError, reason)),
+        NetEvent::IllegalMessage(reason) => println!("{}", error(MessageKey::ConsoleMessageRejected, reason)),
+        NetEvent::PeerMove(_) | NetEvent::RematchStarted | NetEvent::Disconnected => {}
+    }
+}
+
+/// Имена сторон сетевой партии: свое имя и имя соперника
+fn net_names(net: &NetGame, name: &str) -> PlayerNames {
+    let mut names = PlayerNames::default();
+    names.set(net.local_player(), name);
+    names.set(net.local_player().opponent(), net.peer_name());
+    names
+}
+
+/// Печатает победителя партии или ничью
+fn print_result(winner: Option<Player>, names: &PlayerNames, localization: &Localization) {
+    match winner {
+        Some(player) => println!("{}", localization.format(MessageKey::Winner, &[("player", names.label(player, localization).into())])),
+        None => println!("{}", localization.get_text(MessageKey::Draw)),
+    }
+}
+
+/// Спрашивает, сыграть ли еще раз, пока не будет дан ответ «да» или «нет»
+///
+/// Ответы берутся из каталога языка (см. [`Localization::parse_answer`]).
+fn ask_play_again(localization: &Localization) -> bool {
+    loop {
+        let prompt = format!("{} ", localization.get_text(MessageKey::ConsolePlayAgain));
+        if let Some(answer) = localization.parse_answer(&read_command(&prompt, localization)) {
+            return answer;
+        }
+    }
+}
+
+/// Читает строку с подсказкой; `q`, `quit` или конец ввода завершает программу
+///
+/// Без выхода по концу ввода (закрытый stdin) вопросы повторялись бы бесконечно.
+fn read_command(prompt: &str, localization: &Localization) -> String {
+    print!("{}", prompt);
+    io::stdout().flush().unwrap();
+    
+    let mut input = String::new();
+    let closed = !matches!(io::stdin().read_line(&mut input), Ok(read) if read > 0);
+    
+    let trimmed = input.trim();
+    if closed || trimmed == "q" || trimmed == "quit" {
+        println!("{}", localization.get_text(MessageKey::ConsoleQuit));
+        std::process::exit(0);
+    }
+    trimmed.to_string()
+}
+
 /// Получает ввод от пользователя
 /// 
 /// Запрашивает позицию для хода и обрабатывает специальные команды.
 /// 
+/// ## Параметры
+/// - `localization`: язык подсказки
+/// 
 /// ## Возвращает
-/// - `Some(position)` - позиция для хода (0-8)
+/// - `Some(ConsoleInput::Move(position))` - позиция для хода (0-8)
+/// - `Some(command)` - команда (`resign`, `draw`, `accept`, `decline`, `abort`)
 /// - `None` - некорректный ввод
 /// 
 /// ## Специальные команды
 /// - `q` или `quit` - выход из игры
 /// 
 /// ## Примеры
-/// - Ввод "5" → возвращает `Some(4)` (центр поля)
-/// - Ввод "1" → возвращает `Some(0)` (левый верхний угол)
-/// - Ввод "q" → завершает программу
-fn get_user_input() -> Option<usize> {
-    print!("Введите позицию (1-9): ");
-    io::stdout().flush().unwrap();
-    
-    let mut input = String::new();
-    io::stdin().read_line(&mut input).unwrap();
-    
-    let trimmed = input.trim();
-    
-    // Проверяем команды выхода
-    if trimmed == "q" || trimmed == "quit" {
-        println!("Выход из игры...");
-        std::process::exit(0);
-    }
+/// - Ввод "5" → возвращает `Some(Move(4))` (центр поля)
+/// - Ввод "1" → возвращает `Some(Move(0))` (левый верхний угол)
+/// - Ввод "q" или конец ввода → завершает программу
+fn get_user_input(localization: &Localization) -> Option<ConsoleInput> {
+    let prompt = format!("{} ", localization.get_text(MessageKey::ConsolePrompt));
+    let input = read_command(&prompt, localization);
     
-    // Парсим число и конвертируем в индекс массива (1-9 → 0-8)
-    trimmed.parse::<usize>().ok().map(|n| n - 1)
+    // Команда или число, которое конвертируем в индекс массива (1-9 → 0-8)
+    ConsoleInput::parse(&input)
 }
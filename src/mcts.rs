@@ -0,0 +1,435 @@
+//! # Поиск по дереву Монте-Карло
+//!
+//! Агент MCTS (UCT) для любых правил, которые поддерживает библиотека.
+//! Подходит для вариантов, где точный перебор невозможен.
+//!
+//! ## Использование
+//! ```rust
+//! use rust_tic_tac_toe::TicTacToe;
+//! use rust_tic_tac_toe::engine::Engine;
+//! use rust_tic_tac_toe::mcts::{MctsConfig, MctsEngine};
+//!
+//! let game = TicTacToe::new();
+//! let mut ai = MctsEngine::new(MctsConfig { iterations: 500, ..Default::default() });
+//! let position = ai.choose_move(&game).unwrap();
+//!
+//! // Статистика по каждому кандидату
+//! for stats in ai.last_stats() {
+//!     println!("{}: {} посещений, {:.0}%", stats.position, stats.visits, stats.win_rate * 100.0);
+//! }
+//! # assert!(position < 9);
+//! ```
+
+use std::time::Duration;
+use web_time::Instant;
+
+use crate::engine::{Engine, EngineInfo};
+use crate::{Player, TicTacToe, DIRECTIONS};
+
+/// Стратегия случайных доигрываний
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RolloutPolicy {
+    /// Полностью случайные ходы
+    Random,
+    /// Сначала выигрыш, затем защита, иначе случайный ход
+    WinBlock,
+}
+
+/// Настройки поиска
+#[derive(Clone, Debug)]
+pub struct MctsConfig {
+    /// Константа исследования в формуле UCT
+    pub exploration: f64,
+    /// Максимальное число итераций
+    pub iterations: usize,
+    /// Ограничение по времени (если задано)
+    pub time_budget: Option<Duration>,
+    /// Стратегия доигрываний
+    pub rollout: RolloutPolicy,
+    /// Рассматривать только клетки не дальше этого расстояния от занятых
+    ///
+    /// `None` - рассматривать все свободные клетки.
+    pub neighborhood: Option<usize>,
+    /// Зерно генератора случайных чисел
+    pub seed: u64,
+}
+
+impl Default for MctsConfig {
+    fn default() -> Self {
+        Self {
+            exploration: std::f64::consts::SQRT_2,
+            iterations: 10_000,
+            time_budget: None,
+            rollout: RolloutPolicy::WinBlock,
+            neighborhood: Some(2),
+            seed: 0x5EED,
+        }
+    }
+}
+
+/// Статистика по одному ходу-кандидату
+#[derive(Clone, Debug, PartialEq)]
+pub struct MoveStats {
+    /// Клетка хода
+    pub position: usize,
+    /// Сколько раз ход посещался
+    pub visits: u32,
+    /// Доля выигрышей (ничья считается за половину)
+    pub win_rate: f64,
+}
+
+/// Агент с поиском по дереву Монте-Карло
+#[derive(Clone, Debug)]
+pub struct MctsEngine {
+    /// Настройки поиска
+    pub config: MctsConfig,
+    /// Генератор случайных чисел
+    rng: SplitMix64,
+    /// Статистика последнего поиска
+    stats: Vec<MoveStats>,
+}
+
+impl Default for MctsEngine {
+    fn default() -> Self {
+        Self::new(MctsConfig::default())
+    }
+}
+
+impl MctsEngine {
+    /// Создает агента с указанными настройками
+    pub fn new(config: MctsConfig) -> Self {
+        Self {
+            rng: SplitMix64::new(config.seed),
+            config,
+            stats: Vec::new(),
+        }
+    }
+
+    /// Возвращает статистику кандидатов последнего поиска,
+    /// отсортированную по убыванию числа посещений
+    pub fn last_stats(&self) -> &[MoveStats] {
+        &self.stats
+    }
+
+    /// Выполняет поиск и возвращает статистику кандидатов
+    ///
+    /// Генератор заново получает зерно из настроек, поэтому одна и та же
+    /// позиция дает один и тот же ответ независимо от прошлых поисков.
+    /// Хотя бы одна итерация выполняется всегда, даже при нулевом
+    /// числе итераций или исчерпанном времени.
+    pub fn analyze(&mut self, game: &TicTacToe) -> &[MoveStats] {
+        self.stats.clear();
+        if game.is_game_over() {
+            return &self.stats;
+        }
+        self.rng = SplitMix64::new(self.config.seed);
+
+        let deadline = self.config.time_budget.map(|budget| Instant::now() + budget);
+        let mut tree = vec![Node::new(None, None, game.current_player().opponent(), self.candidates(game))];
+
+        for iteration in 0..self.config.iterations.max(1) {
+            if iteration > 0
+                && iteration.is_multiple_of(64)
+                && deadline.is_some_and(|deadline| Instant::now() >= deadline)
+            {
+                break;
+            }
+
+            let mut state = game.clone();
+            let mut node = 0;
+
+            // Выбор: спускаемся, пока узел полностью раскрыт
+            while tree[node].untried.is_empty() && !tree[node].children.is_empty() {
+                node = self.select_child(&tree, node);
+                state.make_move(tree[node].position.unwrap());
+            }
+
+            // Расширение: добавляем один непопробованный ход
+            if !tree[node].untried.is_empty() && !state.is_game_over() {
+                let index = self.rng.below(tree[node].untried.len());
+                let position = tree[node].untried.swap_remove(index);
+                let mover = state.current_player();
+                state.make_move(position);
+                let untried = if state.is_game_over() { Vec::new() } else { self.candidates(&state) };
+                tree.push(Node::new(Some(position), Some(node), mover, untried));
+                let child = tree.len() - 1;
+                tree[node].children.push(child);
+                node = child;
+            }
+
+            // Доигрывание
+            let winner = self.rollout(&mut state);
+
+            // Обратное распространение
+            let mut current = Some(node);
+            while let Some(index) = current {
+                let entry = &mut tree[index];
+                entry.visits += 1;
+                entry.reward += match winner {
+                    Some(player) if player == entry.mover => 1.0,
+                    Some(_) => 0.0,
+                    None => 0.5,
+                };
+                current = entry.parent;
+            }
+        }
+
+        self.stats = tree[0]
+            .children
+            .iter()
+            .map(|&child| MoveStats {
+                position: tree[child].position.unwrap(),
+                visits: tree[child].visits,
+                win_rate: tree[child].reward / f64::from(tree[child].visits.max(1)),
+            })
+            .collect();
+        self.stats.sort_by(|a, b| b.visits.cmp(&a.visits).then(a.position.cmp(&b.position)));
+
+        &self.stats
+    }
+
+    /// Выбирает потомка с наибольшим значением UCT
+    fn select_child(&self, tree: &[Node], node: usize) -> usize {
+        let parent_visits = f64::from(tree[node].visits.max(1)).ln();
+        let uct = |child: usize| {
+            let visits = f64::from(tree[child].visits.max(1));
+            tree[child].reward / visits + self.config.exploration * (parent_visits / visits).sqrt()
+        };
+
+        let mut best = tree[node].children[0];
+        let mut best_value = uct(best);
+        for &child in &tree[node].children[1..] {
+            let value = uct(child);
+            if value > best_value {
+                best = child;
+                best_value = value;
+            }
+        }
+        best
+    }
+
+    /// Ходы-кандидаты с учетом ограничения по окрестности
+    fn candidates(&self, game: &TicTacToe) -> Vec<usize> {
+        let legal = game.legal_moves();
+        let Some(distance) = self.config.neighborhood else {
+            return legal;
+        };
+        if legal.len() == game.get_board().len() {
+            let size = game.rules().size;
+            return vec![(size / 2) * size + size / 2];
+        }
+
+        let near: Vec<usize> = legal
+            .iter()
+            .copied()
+            .filter(|&position| has_neighbor(game, position, distance))
+            .collect();
+        if near.is_empty() {
+            legal
+        } else {
+            near
+        }
+    }
+
+    /// Доигрывает партию до конца и возвращает победителя
+    fn rollout(&mut self, state: &mut TicTacToe) -> Option<Player> {
+        while !state.is_game_over() {
+            let moves = self.candidates(state);
+            let position = match self.config.rollout {
+                RolloutPolicy::Random => moves[self.rng.below(moves.len())],
+                RolloutPolicy::WinBlock => {
+                    let me = state.current_player();
+                    moves
+                        .iter()
+                        .copied()
+                        .find(|&position| completes_line(state, position, me))
+                        .or_else(|| {
+                            moves
+                                .iter()
+                                .copied()
+                                .find(|&position| completes_line(state, position, me.opponent()))
+                        })
+                        .unwrap_or_else(|| moves[self.rng.below(moves.len())])
+                }
+            };
+            state.make_move(position);
+        }
+        state.get_winner()
+    }
+}
+
+impl Engine for MctsEngine {
+    fn name(&self) -> String {
+        "MCTS".to_string()
+    }
+
+    /// Ограничивает поиск по времени, снимая ограничение по итерациям
+    fn set_move_time(&mut self, time: Duration) {
+        self.config.time_budget = Some(time);
+        self.config.iterations = usize::MAX;
+    }
+
+    /// Оценка - доля выигрышей лучшего хода в процентах
+    fn info(&self) -> Option<EngineInfo> {
+        let best = self.stats.first()?;
+        Some(EngineInfo {
+            depth: 1,
+            nodes: self.stats.iter().map(|stats| u64::from(stats.visits)).sum(),
+            score: (best.win_rate * 100.0).round() as i64,
+        })
+    }
+
+    fn choose_move(&mut self, game: &TicTacToe) -> Option<usize> {
+        self.analyze(game).first().map(|stats| stats.position)
+    }
+}
+
+/// Узел дерева поиска
+#[derive(Clone, Debug)]
+struct Node {
+    /// Ход, который привел в этот узел
+    position: Option<usize>,
+    /// Родительский узел
+    parent: Option<usize>,
+    /// Игрок, сделавший ход в этот узел
+    mover: Player,
+    /// Раскрытые потомки
+    children: Vec<usize>,
+    /// Еще не опробованные ходы
+    untried: Vec<usize>,
+    /// Число посещений
+    visits: u32,
+    /// Сумма результатов с точки зрения `mover`
+    reward: f64,
+}
+
+impl Node {
+    fn new(position: Option<usize>, parent: Option<usize>, mover: Player, untried: Vec<usize>) -> Self {
+        Self { position, parent, mover, children: Vec::new(), untried, visits: 0, reward: 0.0 }
+    }
+}
+
+/// Есть ли занятая клетка на расстоянии не больше `distance`
+fn has_neighbor(game: &TicTacToe, position: usize, distance: usize) -> bool {
+    let size = game.rules().size as isize;
+    let (row, col) = ((position as isize) / size, (position as isize) % size);
+    let distance = distance as isize;
+    (-distance..=distance).any(|dr| {
+        (-distance..=distance).any(|dc| {
+            let (r, c) = (row + dr, col + dc);
+            r >= 0 && r < size && c >= 0 && c < size && game.cell((r * size + c) as usize).is_some()
+        })
+    })
+}
+
+/// Завершит ли ход игрока в свободную клетку выигрышную линию
+fn completes_line(game: &TicTacToe, position: usize, player: Player) -> bool {
+    let rules = game.rules();
+    let size = rules.size as isize;
+    let (row, col) = ((position as isize) / size, (position as isize) % size);
+    DIRECTIONS.iter().any(|&(dr, dc)| {
+        let mut length = 1;
+        for sign in [1, -1] {
+            let (mut r, mut c) = (row + sign * dr, col + sign * dc);
+            while r >= 0 && r < size && c >= 0 && c < size && game.cell((r * size + c) as usize) == Some(player) {
+                length += 1;
+                r += sign * dr;
+                c += sign * dc;
+            }
+        }
+        length >= rules.win_length
+    })
+}
+
+/// Простой детерминированный генератор случайных чисел
+#[derive(Clone, Debug)]
+struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Случайное число от 0 до `bound` (не включая)
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+}
+
+// === ТЕСТЫ ===
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Rules;
+
+    fn engine(iterations: usize, seed: u64) -> MctsEngine {
+        MctsEngine::new(MctsConfig { iterations, seed, ..Default::default() })
+    }
+
+    /// Тест: одинаковое зерно дает одинаковую статистику
+    #[test]
+    fn test_deterministic_with_seed() {
+        let mut game = TicTacToe::new();
+        game.make_move(4);
+
+        let mut first = engine(2000, 42);
+        let mut second = engine(2000, 42);
+        assert_eq!(first.analyze(&game), second.analyze(&game));
+
+        // Повторный поиск того же агента не зависит от предыдущего
+        let again = first.analyze(&game).to_vec();
+        assert_eq!(again, second.analyze(&game));
+    }
+
+    /// Тест: без итераций и без времени агент все равно выбирает ход
+    #[test]
+    fn test_always_searches_once() {
+        let game = TicTacToe::new();
+        assert!(engine(0, 1).choose_move(&game).is_some());
+
+        let mut ai = MctsEngine::new(MctsConfig { time_budget: Some(Duration::ZERO), ..Default::default() });
+        let visits: u32 = ai.analyze(&game).iter().map(|s| s.visits).sum();
+        assert!((1..100).contains(&visits), "{}", visits);
+    }
+
+    /// Тест: агент находит выигрыш и защиту на поле 3x3
+    #[test]
+    fn test_finds_win_and_block() {
+        let mut game = TicTacToe::new();
+        for position in [0, 3, 1, 4] {
+            game.make_move(position);
+        }
+        assert_eq!(engine(3000, 1).choose_move(&game), Some(2));
+
+        let mut game = TicTacToe::new();
+        for position in [0, 4, 1] {
+            game.make_move(position);
+        }
+        assert_eq!(engine(3000, 1).choose_move(&game), Some(2));
+    }
+
+    /// Тест: статистика содержит все кандидаты и работает на большом поле
+    #[test]
+    fn test_reports_stats_on_large_board() {
+        let mut game = TicTacToe::with_rules(Rules::new(9, 4));
+        game.make_move(40);
+
+        let mut ai = engine(300, 7);
+        let stats = ai.analyze(&game).to_vec();
+        assert!(!stats.is_empty());
+        assert_eq!(stats.iter().map(|s| s.visits).sum::<u32>(), 300);
+        assert!(stats.iter().all(|s| (0.0..=1.0).contains(&s.win_rate)));
+        assert!(stats.iter().all(|s| game.cell(s.position).is_none()));
+    }
+}
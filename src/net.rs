@@ -0,0 +1,617 @@
+//! # Сетевая игра по TCP
+//!
+//! Два экземпляра игры соединяются напрямую: один создает игру (хост),
+//! другой подключается к нему. Сообщения передаются построчно в формате JSON
+//! и имеют версию протокола.
+//!
+//! Обе стороны проверяют каждый ход через [`TicTacToe::make_move`]:
+//! ход не в свою очередь, в занятую клетку или после конца партии отклоняется,
+//! а отправителю возвращается сообщение об ошибке. Сдача, предложение ничьей
+//! и прерывание партии проверяются так же.
+//!
+//! ## Использование
+//! ```rust,no_run
+//! use rust_tic_tac_toe::Rules;
+//! use rust_tic_tac_toe::net::{self, NetHost};
+//!
+//! // На одном компьютере
+//! let host = NetHost::bind("0.0.0.0:7878", "Alice", Rules::classic())?;
+//! let mut game = host.accept()?;
+//!
+//! // На другом
+//! let mut game = net::join("192.168.0.10:7878", "Bob")?;
+//! game.send_move(4)?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Player, Rules, Termination, TicTacToe};
+
+/// Версия сетевого протокола
+pub const NET_PROTOCOL_VERSION: u32 = 1;
+
+/// Максимальная длина одного сообщения в байтах
+const MAX_MESSAGE_LEN: usize = 64 * 1024;
+
+/// Сколько ждать приветствия соперника
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Сообщение сетевого протокола
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Message {
+    /// Приветствие с версией протокола и именем игрока
+    Hello {
+        /// Версия протокола
+        version: u32,
+        /// Имя игрока
+        name: String,
+    },
+    /// Правила партии и сторона получателя (отправляет хост)
+    Rules {
+        /// Правила
+        rules: Rules,
+        /// За кого играет получатель
+        you_play: Player,
+    },
+    /// Ход
+    Move {
+        /// Клетка
+        position: usize,
+    },
+    /// Сдача партии
+    Resign,
+    /// Предложение ничьей
+    OfferDraw,
+    /// Согласие на ничью
+    AcceptDraw,
+    /// Отказ от ничьей
+    DeclineDraw,
+    /// Прерывание партии до своего первого хода
+    Abort,
+    /// Предложение сыграть еще раз
+    Rematch,
+    /// Сообщение в чат
+    Chat {
+        /// Текст
+        text: String,
+    },
+    /// Сообщение об ошибке (например, отклоненный ход)
+    Error {
+        /// Причина
+        reason: String,
+    },
+}
+
+/// Событие сетевой игры для интерфейса
+#[derive(Clone, Debug, PartialEq)]
+pub enum NetEvent {
+    /// Соперник сделал ход
+    PeerMove(usize),
+    /// Соперник сдался
+    PeerResigned,
+    /// Соперник предлагает ничью
+    DrawOffered,
+    /// Соперник отклонил ничью
+    DrawDeclined,
+    /// Соперник принял ничью
+    DrawAgreed,
+    /// Соперник прервал партию
+    PeerAborted,
+    /// Соперник предлагает реванш
+    RematchRequested,
+    /// Реванш начался, стороны поменялись
+    RematchStarted,
+    /// Сообщение в чате
+    Chat(String),
+    /// Соперник сообщил об ошибке
+    PeerError(String),
+    /// Соперник прислал некорректное сообщение, оно отклонено
+    IllegalMessage(String),
+    /// Соединение разорвано
+    Disconnected,
+}
+
+/// Ошибка локального действия в сетевой игре
+#[derive(Debug)]
+pub enum NetError {
+    /// Сейчас не ход локального игрока
+    NotYourTurn,
+    /// Ход некорректен
+    IllegalMove,
+    /// Действие сейчас невозможно (например, принять ничью, которую не предлагали)
+    NotAllowed,
+    /// Ошибка соединения
+    Io(io::Error),
+}
+
+impl std::fmt::Display for NetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NetError::NotYourTurn => write!(f, "сейчас ход соперника"),
+            NetError::IllegalMove => write!(f, "некорректный ход"),
+            NetError::NotAllowed => write!(f, "сейчас это невозможно"),
+            NetError::Io(e) => write!(f, "ошибка соединения: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for NetError {}
+
+impl From<io::Error> for NetError {
+    fn from(e: io::Error) -> Self {
+        NetError::Io(e)
+    }
+}
+
+/// Входящее сообщение от потока чтения
+enum Incoming {
+    Message(Message),
+    Invalid(String),
+    Closed,
+}
+
+/// Хост, ожидающий подключения соперника
+pub struct NetHost {
+    listener: TcpListener,
+    name: String,
+    rules: Rules,
+}
+
+impl NetHost {
+    /// Открывает порт для входящего подключения
+    pub fn bind(addr: impl ToSocketAddrs, name: &str, rules: Rules) -> io::Result<Self> {
+        Ok(Self { listener: TcpListener::bind(addr)?, name: name.to_string(), rules })
+    }
+
+    /// Адрес, на котором хост ждет подключения
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Ждет подключения и выполняет рукопожатие
+    ///
+    /// Хост играет за X, подключившийся игрок - за O.
+    pub fn accept(self) -> io::Result<NetGame> {
+        let (stream, _) = self.listener.accept()?;
+        let mut reader = BufReader::new(stream.try_clone()?);
+        stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+
+        let peer_name = match read_message(&mut reader)? {
+            Some(Message::Hello { version, name }) if version == NET_PROTOCOL_VERSION => name,
+            Some(Message::Hello { version, .. }) => {
+                let reason = format!("неподдерживаемая версия протокола: {}", version);
+                let _ = write_message(&stream, &Message::Error { reason: reason.clone() });
+                return Err(io::Error::new(io::ErrorKind::InvalidData, reason));
+            }
+            _ => return Err(io::Error::new(io::ErrorKind::InvalidData, "ожидалось приветствие")),
+        };
+
+        write_message(&stream, &Message::Hello { version: NET_PROTOCOL_VERSION, name: self.name.clone() })?;
+        write_message(&stream, &Message::Rules { rules: self.rules, you_play: Player::O })?;
+        NetGame::start(stream, reader, peer_name, self.rules, Player::X)
+    }
+}
+
+/// Подключается к хосту и выполняет рукопожатие
+pub fn join(addr: impl ToSocketAddrs, name: &str) -> io::Result<NetGame> {
+    let stream = TcpStream::connect(addr)?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+
+    write_message(&stream, &Message::Hello { version: NET_PROTOCOL_VERSION, name: name.to_string() })?;
+    let peer_name = match read_message(&mut reader)? {
+        Some(Message::Hello { name, .. }) => name,
+        Some(Message::Error { reason }) => return Err(io::Error::new(io::ErrorKind::InvalidData, reason)),
+        _ => return Err(io::Error::new(io::ErrorKind::InvalidData, "ожидалось приветствие")),
+    };
+    let (rules, local) = match read_message(&mut reader)? {
+        Some(Message::Rules { rules, you_play }) => (rules, you_play),
+        _ => return Err(io::Error::new(io::ErrorKind::InvalidData, "ожидались правила")),
+    };
+    // Хост может прислать что угодно: огромное поле не должно уронить клиента
+    let rules = rules.validate().map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    NetGame::start(stream, reader, peer_name, rules, local)
+}
+
+/// Сетевая партия с проверкой всех ходов
+pub struct NetGame {
+    stream: TcpStream,
+    incoming: Receiver<Incoming>,
+    game: TicTacToe,
+    local: Player,
+    peer_name: String,
+    /// Кто уже попросил реванш: (локальный игрок, соперник)
+    rematch: (bool, bool),
+    connected: bool,
+}
+
+impl NetGame {
+    fn start(
+        stream: TcpStream,
+        mut reader: BufReader<TcpStream>,
+        peer_name: String,
+        rules: Rules,
+        local: Player,
+    ) -> io::Result<Self> {
+        stream.set_read_timeout(None)?;
+        let (sender, incoming) = mpsc::channel();
+        std::thread::spawn(move || loop {
+            let item = match read_message(&mut reader) {
+                Ok(Some(message)) => Incoming::Message(message),
+                Err(e) if e.kind() == io::ErrorKind::InvalidData => Incoming::Invalid(e.to_string()),
+                Ok(None) | Err(_) => Incoming::Closed,
+            };
+            let closed = matches!(item, Incoming::Closed);
+            if sender.send(item).is_err() || closed {
+                break;
+            }
+        });
+
+        Ok(Self {
+            stream,
+            incoming,
+            game: TicTacToe::with_rules(rules),
+            local,
+            peer_name,
+            rematch: (false, false),
+            connected: true,
+        })
+    }
+
+    /// Текущее состояние партии
+    pub fn game(&self) -> &TicTacToe {
+        &self.game
+    }
+
+    /// За кого играет локальный игрок
+    pub fn local_player(&self) -> Player {
+        self.local
+    }
+
+    /// Имя соперника
+    pub fn peer_name(&self) -> &str {
+        &self.peer_name
+    }
+
+    /// Активно ли соединение
+    pub fn is_connected(&self) -> bool {
+        self.connected
+    }
+
+    /// Сдавшийся игрок, если партия закончилась сдачей
+    pub fn resigned(&self) -> Option<Player> {
+        match self.game.termination() {
+            Some(Termination::Resignation(player)) => Some(player),
+            _ => None,
+        }
+    }
+
+    /// Закончена ли партия (по правилам, сдачей, соглашением или прерыванием)
+    pub fn is_finished(&self) -> bool {
+        self.game.is_game_over()
+    }
+
+    /// Ход локального игрока
+    pub fn is_local_turn(&self) -> bool {
+        !self.is_finished() && self.game.current_player() == self.local
+    }
+
+    /// Делает ход локального игрока и отправляет его сопернику
+    pub fn send_move(&mut self, position: usize) -> Result<(), NetError> {
+        if !self.is_local_turn() {
+            return Err(NetError::NotYourTurn);
+        }
+        if !self.game.make_move(position) {
+            return Err(NetError::IllegalMove);
+        }
+        self.send(&Message::Move { position })?;
+        Ok(())
+    }
+
+    /// Сдает партию
+    pub fn resign(&mut self) -> io::Result<()> {
+        if !self.game.resign(self.local) {
+            return Ok(());
+        }
+        self.send(&Message::Resign)
+    }
+
+    /// Предлагает ничью
+    pub fn offer_draw(&mut self) -> Result<(), NetError> {
+        self.act(TicTacToe::offer_draw, Message::OfferDraw)
+    }
+
+    /// Принимает ничью, предложенную соперником
+    pub fn accept_draw(&mut self) -> Result<(), NetError> {
+        self.act(TicTacToe::accept_draw, Message::AcceptDraw)
+    }
+
+    /// Отклоняет ничью, предложенную соперником
+    pub fn decline_draw(&mut self) -> Result<(), NetError> {
+        self.act(TicTacToe::decline_draw, Message::DeclineDraw)
+    }
+
+    /// Прерывает партию до своего первого хода
+    pub fn abort(&mut self) -> Result<(), NetError> {
+        self.act(TicTacToe::abort, Message::Abort)
+    }
+
+    /// Применяет действие локального игрока и сообщает о нем сопернику
+    fn act(&mut self, action: fn(&mut TicTacToe, Player) -> bool, message: Message) -> Result<(), NetError> {
+        if !action(&mut self.game, self.local) {
+            return Err(NetError::NotAllowed);
+        }
+        self.send(&message)?;
+        Ok(())
+    }
+
+    /// Предлагает реванш; партия начнется, когда согласятся оба
+    pub fn request_rematch(&mut self) -> io::Result<bool> {
+        self.rematch.0 = true;
+        self.send(&Message::Rematch)?;
+        Ok(self.try_start_rematch())
+    }
+
+    /// Отправляет сообщение в чат
+    pub fn chat(&mut self, text: &str) -> io::Result<()> {
+        self.send(&Message::Chat { text: text.to_string() })
+    }
+
+    /// Обрабатывает все пришедшие сообщения, не блокируя
+    pub fn poll(&mut self) -> Vec<NetEvent> {
+        let mut events = Vec::new();
+        loop {
+            match self.incoming.try_recv() {
+                Ok(item) => events.extend(self.handle(item)),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    if self.connected {
+                        self.connected = false;
+                        events.push(NetEvent::Disconnected);
+                    }
+                    break;
+                }
+            }
+        }
+        events
+    }
+
+    /// Ждет следующее событие (блокирует)
+    pub fn wait_event(&mut self) -> NetEvent {
+        loop {
+            let item = match self.incoming.recv() {
+                Ok(item) => item,
+                Err(_) => Incoming::Closed,
+            };
+            if let Some(event) = self.handle(item) {
+                return event;
+            }
+        }
+    }
+
+    /// Применяет входящее сообщение к партии
+    fn handle(&mut self, item: Incoming) -> Option<NetEvent> {
+        let message = match item {
+            Incoming::Message(message) => message,
+            Incoming::Invalid(reason) => {
+                let _ = self.send(&Message::Error { reason: reason.clone() });
+                return Some(NetEvent::IllegalMessage(reason));
+            }
+            Incoming::Closed => {
+                if !self.connected {
+                    return None;
+                }
+                self.connected = false;
+                return Some(NetEvent::Disconnected);
+            }
+        };
+
+        match message {
+            Message::Move { position } => {
+                let peer_turn = !self.is_finished() && self.game.current_player() != self.local;
+                if peer_turn && self.game.make_move(position) {
+                    Some(NetEvent::PeerMove(position))
+                } else {
+                    self.reject(format!("ход {} отклонен", position))
+                }
+            }
+            Message::Resign if self.game.resign(self.local.opponent()) => Some(NetEvent::PeerResigned),
+            Message::OfferDraw if self.game.offer_draw(self.local.opponent()) => Some(NetEvent::DrawOffered),
+            Message::AcceptDraw if self.game.accept_draw(self.local.opponent()) => Some(NetEvent::DrawAgreed),
+            Message::DeclineDraw if self.game.decline_draw(self.local.opponent()) => Some(NetEvent::DrawDeclined),
+            Message::Abort if self.game.abort(self.local.opponent()) => Some(NetEvent::PeerAborted),
+            Message::Rematch if self.is_finished() => {
+                self.rematch.1 = true;
+                if self.try_start_rematch() {
+                    Some(NetEvent::RematchStarted)
+                } else {
+                    Some(NetEvent::RematchRequested)
+                }
+            }
+            Message::Chat { text } => Some(NetEvent::Chat(text)),
+            Message::Error { reason } => Some(NetEvent::PeerError(reason)),
+            other => self.reject(format!("неожиданное сообщение: {:?}", other)),
+        }
+    }
+
+    /// Отклоняет сообщение соперника и сообщает ему причину
+    fn reject(&mut self, reason: String) -> Option<NetEvent> {
+        let _ = self.send(&Message::Error { reason: reason.clone() });
+        Some(NetEvent::IllegalMessage(reason))
+    }
+
+    /// Начинает новую партию, если оба согласились на реванш
+    ///
+    /// Стороны меняются местами, чтобы первым ходил другой игрок.
+    fn try_start_rematch(&mut self) -> bool {
+        if self.rematch != (true, true) {
+            return false;
+        }
+        self.rematch = (false, false);
+        self.game.reset();
+        self.local = self.local.opponent();
+        true
+    }
+
+    fn send(&mut self, message: &Message) -> io::Result<()> {
+        let result = write_message(&self.stream, message);
+        if result.is_err() {
+            self.connected = false;
+        }
+        result
+    }
+}
+
+impl Drop for NetGame {
+    fn drop(&mut self) {
+        let _ = self.stream.shutdown(std::net::Shutdown::Both);
+    }
+}
+
+/// Отправляет сообщение одной строкой JSON
+fn write_message(mut stream: &TcpStream, message: &Message) -> io::Result<()> {
+    let mut line = serde_json::to_string(message).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    line.push('\n');
+    stream.write_all(line.as_bytes())?;
+    stream.flush()
+}
+
+/// Читает одно сообщение
+///
+/// ## Возвращает
+/// - `Ok(Some(message))` - сообщение
+/// - `Ok(None)` - соединение закрыто
+/// - `Err` с `InvalidData` - строка не является корректным сообщением
+fn read_message(reader: &mut BufReader<TcpStream>) -> io::Result<Option<Message>> {
+    let mut line = String::new();
+    let read = reader.by_ref().take(MAX_MESSAGE_LEN as u64).read_line(&mut line)?;
+    if read == 0 {
+        return Ok(None);
+    }
+    if !line.ends_with('\n') && read >= MAX_MESSAGE_LEN {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "слишком длинное сообщение"));
+    }
+    serde_json::from_str(line.trim())
+        .map(Some)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, format!("некорректное сообщение: {}", line.trim())))
+}
+
+// === ТЕСТЫ ===
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Создает пару соединенных партий на локальном адресе
+    fn connect() -> (NetGame, NetGame) {
+        let host = NetHost::bind("127.0.0.1:0", "host", Rules::classic()).unwrap();
+        let addr = host.local_addr().unwrap();
+        let accepted = std::thread::spawn(move || host.accept().unwrap());
+        let client = join(addr, "client").unwrap();
+        (accepted.join().unwrap(), client)
+    }
+
+    /// Тест: рукопожатие и обмен ходами
+    #[test]
+    fn test_handshake_and_moves() {
+        let (mut host, mut client) = connect();
+        assert_eq!(host.peer_name(), "client");
+        assert_eq!(client.peer_name(), "host");
+        assert_eq!(host.local_player(), Player::X);
+        assert_eq!(client.local_player(), Player::O);
+
+        host.send_move(4).unwrap();
+        assert_eq!(client.wait_event(), NetEvent::PeerMove(4));
+        assert!(matches!(host.send_move(0), Err(NetError::NotYourTurn)));
+
+        client.send_move(0).unwrap();
+        assert_eq!(host.wait_event(), NetEvent::PeerMove(0));
+        assert_eq!(host.game().get_board(), client.game().get_board());
+    }
+
+    /// Тест: ход не в свою очередь отклоняется хостом
+    #[test]
+    fn test_rejects_illegal_messages() {
+        let host = NetHost::bind("127.0.0.1:0", "host", Rules::classic()).unwrap();
+        let addr = host.local_addr().unwrap();
+        let accepted = std::thread::spawn(move || host.accept().unwrap());
+
+        // Модифицированный клиент: ходит вместо хоста и шлет мусор
+        let raw = TcpStream::connect(addr).unwrap();
+        write_message(&raw, &Message::Hello { version: NET_PROTOCOL_VERSION, name: "cheater".into() }).unwrap();
+        let mut host = accepted.join().unwrap();
+        write_message(&raw, &Message::Move { position: 4 }).unwrap();
+        (&raw).write_all(b"not json\n").unwrap();
+
+        assert!(matches!(host.wait_event(), NetEvent::IllegalMessage(_)));
+        assert!(matches!(host.wait_event(), NetEvent::IllegalMessage(_)));
+        assert!(host.game().moves().is_empty());
+
+        drop(raw);
+        assert_eq!(host.wait_event(), NetEvent::Disconnected);
+        assert!(!host.is_connected());
+    }
+
+    /// Тест: клиент отклоняет недопустимые правила хоста, а не падает
+    #[test]
+    fn test_rejects_invalid_host_rules() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let client = std::thread::spawn(move || join(addr, "client"));
+
+        // Модифицированный хост: присылает поле, которое не поместится в памяти
+        let (raw, _) = listener.accept().unwrap();
+        write_message(&raw, &Message::Hello { version: NET_PROTOCOL_VERSION, name: "host".into() }).unwrap();
+        let rules = Rules { size: 4_294_967_296, win_length: 5 };
+        write_message(&raw, &Message::Rules { rules, you_play: Player::O }).unwrap();
+
+        let error = client.join().unwrap().err().expect("правила должны быть отклонены");
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    /// Тест: сдача и реванш со сменой сторон
+    #[test]
+    fn test_resign_and_rematch() {
+        let (mut host, mut client) = connect();
+        host.send_move(4).unwrap();
+        assert_eq!(client.wait_event(), NetEvent::PeerMove(4));
+
+        client.resign().unwrap();
+        assert_eq!(host.wait_event(), NetEvent::PeerResigned);
+        assert_eq!(host.resigned(), Some(Player::O));
+
+        assert!(!host.request_rematch().unwrap());
+        assert_eq!(client.wait_event(), NetEvent::RematchRequested);
+        assert!(client.request_rematch().unwrap());
+        assert_eq!(host.wait_event(), NetEvent::RematchStarted);
+        assert_eq!(host.local_player(), Player::O);
+        assert_eq!(client.local_player(), Player::X);
+        assert!(client.is_local_turn());
+    }
+
+    /// Тест: ничья по соглашению и прерывание партии
+    #[test]
+    fn test_draw_and_abort() {
+        let (mut host, mut client) = connect();
+        assert!(matches!(client.accept_draw(), Err(NetError::NotAllowed)));
+        host.offer_draw().unwrap();
+        assert_eq!(client.wait_event(), NetEvent::DrawOffered);
+        client.decline_draw().unwrap();
+        assert_eq!(host.wait_event(), NetEvent::DrawDeclined);
+
+        host.send_move(4).unwrap();
+        assert_eq!(client.wait_event(), NetEvent::PeerMove(4));
+        assert!(matches!(host.abort(), Err(NetError::NotAllowed)));
+        client.abort().unwrap();
+        assert_eq!(host.wait_event(), NetEvent::PeerAborted);
+        assert_eq!(host.game().termination(), Some(Termination::Aborted));
+        assert!(host.is_finished() && client.is_finished());
+    }
+}
@@ -0,0 +1,450 @@
+//! # Сообщения онлайн-игры
+//!
+//! Общие типы для игрового сервера (модуль `server`) и браузерного клиента
+//! (модуль `web`). Сообщения передаются через WebSocket в виде JSON с полем `type`:
+//!
+//! ```json
+//! {"type":"create","name":"Alice","rules":{"size":3,"win_length":3}}
+//! {"type":"join","code":"K7QX2","name":"Bob"}
+//! {"type":"move","position":4,"seq":0}
+//! ```
+//!
+//! Ход содержит порядковый номер (`seq` - сколько ходов уже сделано в партии).
+//! Сервер знает, какое место занимает каждое подключение, и отклоняет ходы не
+//! в свою очередь и повторно отправленные сообщения, поэтому измененный клиент
+//! не может сходить за соперника или переиграть ход. Ключ места из
+//! [`ServerMessage::Welcome`] нужен только для возвращения в партию.
+//!
+//! Кроме комнат по коду сервер ведет лобби: клиент подписывается командой
+//! `lobby`, выставляет заявку (`seek`) или принимает чужую (`accept`).
+//! [`OnlineState`] собирает ответы сервера в состояние для интерфейса.
+//!
+//! ## Использование
+//! ```rust
+//! use rust_tic_tac_toe::online::ClientMessage;
+//!
+//! let message: ClientMessage = serde_json::from_str(r#"{"type":"join","code":"K7QX2","name":"Bob"}"#).unwrap();
+//! assert_eq!(message, ClientMessage::Join { code: "K7QX2".into(), name: "Bob".into() });
+//! ```
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::clock::ClockState;
+use crate::{Player, Rules, Termination, TicTacToe};
+
+pub use crate::clock::TimeControl;
+
+/// Порт игрового сервера по умолчанию
+pub const DEFAULT_PORT: u16 = 9001;
+
+/// Сообщение клиента серверу
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    /// Создать комнату; создатель играет за X
+    Create {
+        /// Имя игрока
+        name: String,
+        /// Правила партии
+        #[serde(default)]
+        rules: Rules,
+        /// Контроль времени (`None` - без часов)
+        #[serde(default)]
+        time_control: Option<TimeControl>,
+    },
+    /// Войти в комнату по коду; вошедший играет за O
+    Join {
+        /// Код комнаты
+        code: String,
+        /// Имя игрока
+        name: String,
+    },
+    /// Вернуться в партию после перезагрузки страницы
+    Rejoin {
+        /// Код комнаты
+        code: String,
+        /// Секретный ключ места, выданный в [`ServerMessage::Welcome`]
+        token: String,
+    },
+    /// Сделать ход
+    ///
+    /// Удобнее всего собирать через [`OnlineState::move_message`].
+    Move {
+        /// Клетка
+        position: usize,
+        /// Номер хода в партии (число уже сделанных ходов)
+        seq: usize,
+    },
+    /// Сдаться
+    Resign,
+    /// Предложить ничью
+    OfferDraw,
+    /// Принять предложение ничьей соперника
+    AcceptDraw,
+    /// Отклонить предложение ничьей соперника
+    DeclineDraw,
+    /// Прервать партию до своего первого хода
+    Abort,
+    /// Покинуть комнату; начатая партия засчитывается как уход из нее
+    Leave,
+    /// Подписаться на список заявок в лобби
+    Lobby,
+    /// Выставить заявку на игру; сервер сразу подберет соперника, если он есть
+    Seek {
+        /// Имя игрока
+        name: String,
+        /// Правила партии
+        #[serde(default)]
+        rules: Rules,
+        /// Контроль времени (`None` - без часов)
+        #[serde(default)]
+        time_control: Option<TimeControl>,
+    },
+    /// Снять свою заявку
+    CancelSeek,
+    /// Принять заявку из лобби
+    Accept {
+        /// Номер заявки
+        id: u64,
+        /// Имя игрока
+        name: String,
+    },
+    /// Наблюдать за партией без права ходить
+    Watch {
+        /// Код комнаты
+        code: String,
+    },
+}
+
+/// Сообщение сервера клиенту
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage {
+    /// Игрок занял место в комнате
+    Welcome {
+        /// Код комнаты, который можно передать сопернику
+        code: String,
+        /// Ключ для повторного подключения
+        token: String,
+        /// За кого играет клиент
+        you_play: Player,
+    },
+    /// Текущее состояние комнаты (после каждого изменения)
+    State(RoomState),
+    /// Список заявок в лобби (после каждого изменения)
+    Lobby {
+        /// Открытые заявки
+        seeks: Vec<SeekInfo>,
+    },
+    /// Заявка выставлена и ждет соперника
+    Seeking {
+        /// Номер заявки
+        id: u64,
+    },
+    /// Клиент стал зрителем партии
+    Watching {
+        /// Код комнаты
+        code: String,
+    },
+    /// Запрос отклонен
+    Error {
+        /// Причина
+        reason: String,
+    },
+}
+
+/// Состояние комнаты, которое сервер рассылает игрокам
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RoomState {
+    /// Код комнаты
+    pub code: String,
+    /// Правила партии
+    pub rules: Rules,
+    /// Имена игроков X и O (`None` - место свободно)
+    pub players: [Option<String>; 2],
+    /// Подключены ли игроки X и O сейчас
+    pub connected: [bool; 2],
+    /// Ходы партии
+    pub moves: Vec<usize>,
+    /// Контроль времени
+    #[serde(default)]
+    pub time_control: Option<TimeControl>,
+    /// Число зрителей
+    #[serde(default)]
+    pub spectators: usize,
+    /// Показания часов (`None` - партия без часов)
+    #[serde(default)]
+    pub clock: Option<ClockState>,
+    /// Причина окончания партии
+    #[serde(default)]
+    pub termination: Option<Termination>,
+    /// Игрок, чье предложение ничьей ждет ответа
+    #[serde(default)]
+    pub draw_offer: Option<Player>,
+}
+
+impl RoomState {
+    /// Восстанавливает партию по списку ходов
+    pub fn to_game(&self) -> TicTacToe {
+        let mut game = TicTacToe::with_rules(self.rules);
+        for &position in &self.moves {
+            game.make_move(position);
+        }
+        if let Some(player) = self.draw_offer {
+            game.offer_draw(player);
+        }
+        if let Some(termination) = self.termination {
+            game.terminate(termination);
+        }
+        game
+    }
+
+    /// Заняты ли оба места
+    pub fn is_full(&self) -> bool {
+        self.players.iter().all(Option::is_some)
+    }
+}
+
+/// Заявка на игру в лобби
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SeekInfo {
+    /// Номер заявки
+    pub id: u64,
+    /// Имя игрока
+    pub name: String,
+    /// Рейтинг игрока
+    pub rating: i32,
+    /// Правила партии
+    pub rules: Rules,
+    /// Контроль времени
+    pub time_control: Option<TimeControl>,
+}
+
+// === СОСТОЯНИЕ КЛИЕНТА ===
+
+/// Состояние онлайн-игры на стороне клиента
+///
+/// Применяет сообщения сервера по порядку; общий для графической
+/// и браузерной версий.
+#[derive(Clone, Debug, Default)]
+pub struct OnlineState {
+    /// Код комнаты и сторона после приветствия
+    pub seat: Option<(String, Player)>,
+    /// Ключ для возвращения в партию
+    pub token: Option<String>,
+    /// Последнее состояние комнаты
+    pub room: Option<RoomState>,
+    /// Заявки в лобби
+    pub seeks: Vec<SeekInfo>,
+    /// Своя заявка, ожидающая соперника
+    pub my_seek: Option<u64>,
+    /// Код комнаты, за которой клиент наблюдает
+    pub watching: Option<String>,
+    /// Последняя ошибка сервера
+    pub error: Option<String>,
+    /// Когда пришли последние показания часов
+    pub clock_received: Option<web_time::Instant>,
+}
+
+impl OnlineState {
+    /// Применяет сообщение сервера
+    pub fn apply(&mut self, message: ServerMessage) {
+        match message {
+            ServerMessage::Welcome { code, token, you_play } => {
+                self.seat = Some((code, you_play));
+                self.token = Some(token);
+                self.my_seek = None;
+                self.error = None;
+            }
+            ServerMessage::State(state) => {
+                self.clock_received = state.clock.map(|_| web_time::Instant::now());
+                self.room = Some(state);
+                self.error = None;
+            }
+            ServerMessage::Lobby { seeks } => self.seeks = seeks,
+            ServerMessage::Seeking { id } => self.my_seek = Some(id),
+            ServerMessage::Watching { code } => {
+                self.watching = Some(code);
+                self.error = None;
+            }
+            ServerMessage::Error { reason } => self.error = Some(reason),
+        }
+    }
+
+    /// Партия в комнате
+    pub fn game(&self) -> Option<TicTacToe> {
+        self.room.as_ref().map(RoomState::to_game)
+    }
+
+    /// Оставшееся время игрока по последним показаниям часов сервера
+    ///
+    /// ## Возвращает
+    /// `None`, если партия идет без часов
+    pub fn clock_remaining(&self, player: Player) -> Option<Duration> {
+        let clock = self.room.as_ref()?.clock?;
+        let elapsed = self.clock_received.map_or(Duration::ZERO, |received| received.elapsed());
+        Some(clock.remaining(player, elapsed))
+    }
+
+    /// Ходы партии с номерами (для зрителей и истории)
+    pub fn history(&self) -> String {
+        self.room
+            .as_ref()
+            .map(|room| crate::record::format_moves(room.rules, &room.moves))
+            .unwrap_or_default()
+    }
+
+    /// Сообщение с ходом для текущей позиции
+    ///
+    /// ## Возвращает
+    /// `None`, если клиент не занимает место в комнате
+    pub fn move_message(&self, position: usize) -> Option<ClientMessage> {
+        self.seat.as_ref()?;
+        let seq = self.room.as_ref().map_or(0, |room| room.moves.len());
+        Some(ClientMessage::Move { position, seq })
+    }
+
+    /// Ход локального игрока (у зрителя своего хода не бывает)
+    pub fn is_local_turn(&self) -> bool {
+        match (&self.seat, &self.room) {
+            (Some((_, player)), Some(room)) => {
+                let game = room.to_game();
+                room.is_full() && !game.is_game_over() && game.current_player() == *player
+            }
+            _ => false,
+        }
+    }
+}
+
+// === ПОДКЛЮЧЕНИЕ К СЕРВЕРУ ===
+
+/// Подключение к игровому серверу для нативных клиентов
+///
+/// Сообщения читаются и отправляются в фоновом потоке.
+#[cfg(not(target_arch = "wasm32"))]
+pub struct Connection {
+    outgoing: std::sync::mpsc::Sender<ClientMessage>,
+    incoming: std::sync::mpsc::Receiver<ServerMessage>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Connection {
+    /// Подключается к серверу по адресу вида `ws://host:port`
+    pub fn connect(url: &str) -> std::io::Result<Self> {
+        use std::io;
+        use std::sync::mpsc::{self, TryRecvError};
+        use tungstenite::Message;
+
+        let addr = url.strip_prefix("ws://").unwrap_or(url);
+        let addr = addr.split('/').next().unwrap_or(addr);
+        let stream = std::net::TcpStream::connect(addr)?;
+        let (mut socket, _) = tungstenite::client(url, stream)
+            .map_err(|e| io::Error::new(io::ErrorKind::ConnectionRefused, e.to_string()))?;
+        socket.get_ref().set_read_timeout(Some(Duration::from_millis(20)))?;
+
+        let (outgoing, requests) = mpsc::channel::<ClientMessage>();
+        let (responses, incoming) = mpsc::channel();
+        std::thread::spawn(move || loop {
+            match socket.read() {
+                Ok(Message::Text(text)) => {
+                    if let Ok(message) = serde_json::from_str(&text) {
+                        if responses.send(message).is_err() {
+                            break;
+                        }
+                    }
+                }
+                Ok(Message::Close(_)) => break,
+                Ok(_) => {}
+                Err(tungstenite::Error::Io(e))
+                    if matches!(e.kind(), io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut) => {}
+                Err(_) => break,
+            }
+            loop {
+                match requests.try_recv() {
+                    Ok(message) => {
+                        let text = serde_json::to_string(&message).expect("сообщение сериализуется");
+                        if socket.send(Message::Text(text)).is_err() {
+                            return;
+                        }
+                    }
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => {
+                        let _ = socket.close(None);
+                        return;
+                    }
+                }
+            }
+        });
+        Ok(Self { outgoing, incoming })
+    }
+
+    /// Отправляет сообщение серверу
+    pub fn send(&self, message: ClientMessage) {
+        let _ = self.outgoing.send(message);
+    }
+
+    /// Все пришедшие сообщения, не блокируя
+    pub fn poll(&self) -> Vec<ServerMessage> {
+        self.incoming.try_iter().collect()
+    }
+
+    /// Ждет следующее сообщение не дольше `timeout`
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<ServerMessage> {
+        self.incoming.recv_timeout(timeout).ok()
+    }
+}
+
+// === ТЕСТЫ ===
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Тест: контроль времени без поля `mode` читается как добавка Фишера
+    #[test]
+    fn test_time_control() {
+        let blitz: TimeControl = serde_json::from_str(r#"{"base_secs":300,"increment_secs":3}"#).unwrap();
+        assert_eq!(blitz, TimeControl::fischer(300, 3));
+        assert_eq!(blitz.to_string(), "5+3");
+    }
+
+    /// Тест: состояние клиента по сообщениям сервера
+    #[test]
+    fn test_online_state() {
+        let mut state = OnlineState::default();
+        state.apply(ServerMessage::Seeking { id: 3 });
+        assert_eq!(state.my_seek, Some(3));
+        state.apply(ServerMessage::Welcome { code: "ABCDE".into(), token: "t".into(), you_play: Player::O });
+        assert_eq!(state.my_seek, None);
+        state.apply(ServerMessage::State(RoomState {
+            code: "ABCDE".into(),
+            rules: Rules::classic(),
+            players: [Some("A".into()), Some("B".into())],
+            connected: [true, true],
+            moves: vec![4],
+            time_control: None,
+            spectators: 0,
+            clock: None,
+            termination: None,
+            draw_offer: Some(Player::X),
+        }));
+        assert!(state.is_local_turn());
+        assert_eq!(state.game().unwrap().moves(), &[4]);
+        assert_eq!(state.game().unwrap().draw_offer(), Some(Player::X));
+        assert_eq!(state.history(), "1. b2");
+    }
+
+    /// Тест: сообщение с ходом несет номер хода и есть только у игрока
+    #[test]
+    fn test_move_message() {
+        let mut state = OnlineState::default();
+        assert_eq!(state.move_message(4), None);
+        state.apply(ServerMessage::Welcome { code: "ABCDE".into(), token: "secret".into(), you_play: Player::X });
+        assert_eq!(
+            state.move_message(4),
+            Some(ClientMessage::Move { position: 4, seq: 0 })
+        );
+    }
+}
@@ -0,0 +1,620 @@
+//! # Текстовый протокол движков
+//!
+//! Построчный протокол в духе UCI, через который внешние движки
+//! (написанные на любом языке) подключаются к игре через stdin/stdout.
+//!
+//! ## Команды интерфейса
+//! ```text
+//! ttp                               рукопожатие
+//! isready                           проверка готовности
+//! newgame 15x15/5                   новая партия с указанными правилами
+//! position startpos moves h8 h9     позиция: начальная + ходы
+//! position fen x2/1o1/3 x moves c3  позиция: доска построчно + кто ходит + ходы
+//! go movetime 500                   начать поиск с ограничением по времени (мс)
+//! quit                              завершить работу
+//! ```
+//!
+//! ## Ответы движка
+//! ```text
+//! id name Threat-space              имя движка
+//! id protocol 1                     версия протокола
+//! ttpok                             рукопожатие завершено
+//! readyok                           движок готов
+//! info depth 6 nodes 1024 score 35  сведения о поиске
+//! bestmove h7                       лучший ход (или `bestmove none`)
+//! ```
+//!
+//! В записи `fen` строки идут сверху вниз через `/`, `x` и `o` - символы
+//! игроков, число - количество пустых клеток подряд.
+
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::time::Duration;
+
+use crate::engine::{Engine, EngineInfo};
+use crate::record::{format_position, parse_position};
+use crate::{Player, Rules, TicTacToe};
+
+/// Версия протокола
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Время на ход по умолчанию, если в `go` оно не указано
+pub const DEFAULT_MOVE_TIME: Duration = Duration::from_millis(1000);
+
+/// Сколько ждать ответа `ttpok` от запущенного движка
+pub const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Запас сверх времени на ход, после которого `bestmove` уже не ждут
+pub const SEARCH_GRACE: Duration = Duration::from_secs(1);
+
+/// Наибольшая длина строки ответа движка; более длинные строки режутся на части
+#[cfg(not(target_arch = "wasm32"))]
+const MAX_LINE_LEN: u64 = 64 * 1024;
+
+/// Описание начальной позиции в команде `position`
+#[derive(Clone, Debug, PartialEq)]
+pub enum StartPosition {
+    /// Пустое поле, первым ходит X
+    StartPos,
+    /// Произвольное поле и игрок, который ходит
+    Fen(String),
+}
+
+/// Команда интерфейса движку
+#[derive(Clone, Debug, PartialEq)]
+pub enum Command {
+    /// Рукопожатие
+    Ttp,
+    /// Проверка готовности
+    IsReady,
+    /// Новая партия с указанными правилами
+    NewGame(Rules),
+    /// Установка позиции
+    Position {
+        /// Начальная позиция
+        start: StartPosition,
+        /// Ходы после нее в координатной записи
+        moves: Vec<String>,
+    },
+    /// Начать поиск
+    Go {
+        /// Время на ход
+        movetime: Option<Duration>,
+    },
+    /// Завершение работы
+    Quit,
+}
+
+impl Command {
+    /// Разбирает строку команды
+    ///
+    /// ## Возвращает
+    /// - `Some(command)` - команда
+    /// - `None` - пустая строка или неизвестная команда
+    pub fn parse(line: &str) -> Option<Command> {
+        let mut words = line.split_whitespace();
+        match words.next()? {
+            "ttp" => Some(Command::Ttp),
+            "isready" => Some(Command::IsReady),
+            "newgame" => words.next().unwrap_or("classic").parse().ok().map(Command::NewGame),
+            "position" => {
+                let start = match words.next()? {
+                    "startpos" => StartPosition::StartPos,
+                    "fen" => {
+                        let board = words.next()?;
+                        let side = words.next()?;
+                        StartPosition::Fen(format!("{} {}", board, side))
+                    }
+                    _ => return None,
+                };
+                let moves = match words.next() {
+                    Some("moves") => words.map(str::to_string).collect(),
+                    Some(_) => return None,
+                    None => Vec::new(),
+                };
+                Some(Command::Position { start, moves })
+            }
+            "go" => {
+                let mut movetime = None;
+                while let Some(word) = words.next() {
+                    if word == "movetime" {
+                        movetime = words.next()?.parse().ok().map(Duration::from_millis);
+                    }
+                }
+                Some(Command::Go { movetime })
+            }
+            "quit" => Some(Command::Quit),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Command::Ttp => write!(f, "ttp"),
+            Command::IsReady => write!(f, "isready"),
+            Command::NewGame(rules) => write!(f, "newgame {}", rules),
+            Command::Position { start, moves } => {
+                match start {
+                    StartPosition::StartPos => write!(f, "position startpos")?,
+                    StartPosition::Fen(fen) => write!(f, "position fen {}", fen)?,
+                }
+                if !moves.is_empty() {
+                    write!(f, " moves {}", moves.join(" "))?;
+                }
+                Ok(())
+            }
+            Command::Go { movetime: Some(time) } => write!(f, "go movetime {}", time.as_millis()),
+            Command::Go { movetime: None } => write!(f, "go"),
+            Command::Quit => write!(f, "quit"),
+        }
+    }
+}
+
+/// Ответ движка интерфейсу
+#[derive(Clone, Debug, PartialEq)]
+pub enum Response {
+    /// Имя движка
+    IdName(String),
+    /// Версия протокола
+    IdProtocol(u32),
+    /// Рукопожатие завершено
+    TtpOk,
+    /// Движок готов
+    ReadyOk,
+    /// Сведения о поиске
+    Info(EngineInfo),
+    /// Произвольное сообщение движка
+    InfoString(String),
+    /// Лучший ход в координатной записи (`None` - хода нет)
+    BestMove(Option<String>),
+}
+
+impl Response {
+    /// Разбирает строку ответа
+    pub fn parse(line: &str) -> Option<Response> {
+        let mut words = line.split_whitespace();
+        match words.next()? {
+            "id" => match words.next()? {
+                "name" => Some(Response::IdName(words.collect::<Vec<_>>().join(" "))),
+                "protocol" => words.next()?.parse().ok().map(Response::IdProtocol),
+                _ => None,
+            },
+            "ttpok" => Some(Response::TtpOk),
+            "readyok" => Some(Response::ReadyOk),
+            "info" => {
+                let rest: Vec<&str> = words.collect();
+                if rest.first() == Some(&"string") {
+                    return Some(Response::InfoString(rest[1..].join(" ")));
+                }
+                let mut info = EngineInfo::default();
+                for pair in rest.chunks(2) {
+                    match pair {
+                        ["depth", value] => info.depth = value.parse().ok()?,
+                        ["nodes", value] => info.nodes = value.parse().ok()?,
+                        ["score", value] => info.score = value.parse().ok()?,
+                        _ => {}
+                    }
+                }
+                Some(Response::Info(info))
+            }
+            "bestmove" => match words.next()? {
+                "none" => Some(Response::BestMove(None)),
+                coord => Some(Response::BestMove(Some(coord.to_string()))),
+            },
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Response {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Response::IdName(name) => write!(f, "id name {}", name),
+            Response::IdProtocol(version) => write!(f, "id protocol {}", version),
+            Response::TtpOk => write!(f, "ttpok"),
+            Response::ReadyOk => write!(f, "readyok"),
+            Response::Info(info) => write!(f, "info depth {} nodes {} score {}", info.depth, info.nodes, info.score),
+            Response::InfoString(text) => write!(f, "info string {}", text),
+            Response::BestMove(Some(coord)) => write!(f, "bestmove {}", coord),
+            Response::BestMove(None) => write!(f, "bestmove none"),
+        }
+    }
+}
+
+/// Записывает поле и ходящего игрока в формате `fen`
+pub fn to_fen(game: &TicTacToe) -> String {
+    let size = game.rules().size;
+    let rows: Vec<String> = game
+        .get_board()
+        .chunks(size)
+        .map(|row| {
+            let mut text = String::new();
+            let mut empty = 0;
+            for cell in row {
+                match cell {
+                    None => empty += 1,
+                    Some(player) => {
+                        if empty > 0 {
+                            text += &empty.to_string();
+                            empty = 0;
+                        }
+                        text += &player.symbol().to_lowercase();
+                    }
+                }
+            }
+            if empty > 0 {
+                text += &empty.to_string();
+            }
+            text
+        })
+        .collect();
+    format!("{} {}", rows.join("/"), game.current_player_symbol().to_lowercase())
+}
+
+/// Восстанавливает игру из записи `fen`
+///
+/// История ходов в такой игре пуста.
+pub fn from_fen(rules: Rules, fen: &str) -> Option<TicTacToe> {
+    let (board, side) = fen.split_once(' ')?;
+    let mut cells = Vec::with_capacity(rules.cell_count());
+    // Пустые клетки добавляются, только если помещаются на поле:
+    // запись вроде `99999999999/...` не должна занимать гигабайты памяти
+    let push_empty = |cells: &mut Vec<Option<Player>>, digits: &str| -> Option<()> {
+        let count: usize = digits.parse().ok()?;
+        if count > rules.cell_count() - cells.len() {
+            return None;
+        }
+        cells.extend(std::iter::repeat_n(None, count));
+        Some(())
+    };
+    for row in board.split('/') {
+        let start = cells.len();
+        let mut digits = String::new();
+        for c in row.chars() {
+            if c.is_ascii_digit() {
+                digits.push(c);
+                continue;
+            }
+            if !digits.is_empty() {
+                push_empty(&mut cells, &digits)?;
+                digits.clear();
+            }
+            if cells.len() == rules.cell_count() {
+                return None;
+            }
+            cells.push(Some(match c {
+                'x' | 'X' => Player::X,
+                'o' | 'O' => Player::O,
+                _ => return None,
+            }));
+        }
+        if !digits.is_empty() {
+            push_empty(&mut cells, &digits)?;
+        }
+        if cells.len() - start != rules.size {
+            return None;
+        }
+    }
+    if cells.len() != rules.cell_count() {
+        return None;
+    }
+
+    let mut game = TicTacToe::with_rules(rules);
+    game.set_board(&cells);
+    game.set_current_player(match side {
+        "x" | "X" => Player::X,
+        "o" | "O" => Player::O,
+        _ => return None,
+    });
+    Some(game)
+}
+
+/// Обслуживает интерфейс по протоколу, используя встроенный движок
+///
+/// Читает команды из `input` и пишет ответы в `output`,
+/// пока не получит `quit` или не закончится ввод.
+pub fn serve<R: BufRead, W: Write>(engine: &mut dyn Engine, input: R, mut output: W) -> io::Result<()> {
+    let mut game = TicTacToe::new();
+
+    for line in input.lines() {
+        let line = line?;
+        let mut respond = |response: Response| writeln!(output, "{}", response);
+
+        match Command::parse(&line) {
+            Some(Command::Ttp) => {
+                respond(Response::IdName(engine.name()))?;
+                respond(Response::IdProtocol(PROTOCOL_VERSION))?;
+                respond(Response::TtpOk)?;
+            }
+            Some(Command::IsReady) => respond(Response::ReadyOk)?,
+            Some(Command::NewGame(rules)) => game = TicTacToe::with_rules(rules),
+            Some(Command::Position { start, moves }) => {
+                let rules = game.rules();
+                let position = match start {
+                    StartPosition::StartPos => Some(TicTacToe::with_rules(rules)),
+                    StartPosition::Fen(fen) => from_fen(rules, &fen),
+                };
+                match position.and_then(|mut position| {
+                    moves
+                        .iter()
+                        .all(|coord| parse_position(rules, coord).is_some_and(|p| position.make_move(p)))
+                        .then_some(position)
+                }) {
+                    Some(position) => game = position,
+                    None => respond(Response::InfoString(format!("invalid position: {}", line)))?,
+                }
+            }
+            Some(Command::Go { movetime }) => {
+                engine.set_move_time(movetime.unwrap_or(DEFAULT_MOVE_TIME));
+                let best = engine.choose_move(&game);
+                if let Some(info) = engine.info() {
+                    respond(Response::Info(info))?;
+                }
+                respond(Response::BestMove(best.map(|p| format_position(game.rules(), p))))?;
+            }
+            Some(Command::Quit) => break,
+            None if line.trim().is_empty() => {}
+            None => respond(Response::InfoString(format!("unknown command: {}", line.trim())))?,
+        }
+        output.flush()?;
+    }
+
+    Ok(())
+}
+
+/// Внешний движок, запущенный как дочерний процесс
+///
+/// Реализует [`Engine`], поэтому подключается везде, где используются
+/// встроенные движки: в интерфейсе, консоли и турнирах.
+#[cfg(not(target_arch = "wasm32"))]
+pub struct ExternalEngine {
+    name: String,
+    child: std::process::Child,
+    stdin: std::process::ChildStdin,
+    /// Строки ответа движка, прочитанные отдельным потоком
+    lines: std::sync::mpsc::Receiver<String>,
+    move_time: Duration,
+    rules: Option<Rules>,
+    info: Option<EngineInfo>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl ExternalEngine {
+    /// Запускает движок и выполняет рукопожатие
+    ///
+    /// Программа, которая не ответила `ttpok` за [`HANDSHAKE_TIMEOUT`],
+    /// считается не движком и завершается.
+    ///
+    /// ## Параметры
+    /// - `program`: путь к исполняемому файлу
+    /// - `args`: аргументы командной строки движка
+    pub fn spawn(program: &str, args: &[&str]) -> io::Result<Self> {
+        use std::io::Read;
+        use std::process::{Command as Process, Stdio};
+        use std::time::Instant;
+
+        let mut child = Process::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+        let stdin = child.stdin.take().ok_or_else(|| io::Error::other("нет stdin"))?;
+        let stdout = io::BufReader::new(child.stdout.take().ok_or_else(|| io::Error::other("нет stdout"))?);
+
+        // Чтение в отдельном потоке позволяет ждать ответ с ограничением по времени
+        let (sender, lines) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let mut stdout = stdout;
+            let mut line = String::new();
+            loop {
+                line.clear();
+                match (&mut stdout).take(MAX_LINE_LEN).read_line(&mut line) {
+                    Ok(0) | Err(_) => break,
+                    Ok(_) => {
+                        if sender.send(line.clone()).is_err() {
+                            break;
+                        }
+                    }
+                }
+            }
+        });
+
+        let mut engine = Self {
+            name: program.to_string(),
+            child,
+            stdin,
+            lines,
+            move_time: DEFAULT_MOVE_TIME,
+            rules: None,
+            info: None,
+        };
+        engine.send(&Command::Ttp)?;
+        let deadline = Instant::now() + HANDSHAKE_TIMEOUT;
+        loop {
+            match engine.receive_until(deadline)? {
+                Response::IdName(name) => engine.name = name,
+                Response::TtpOk => break,
+                _ => {}
+            }
+        }
+        Ok(engine)
+    }
+
+    fn send(&mut self, command: &Command) -> io::Result<()> {
+        writeln!(self.stdin, "{}", command)?;
+        self.stdin.flush()
+    }
+
+    /// Читает следующий распознанный ответ, пропуская посторонние строки
+    /// и ожидая не дольше `deadline`
+    fn receive_until(&mut self, deadline: std::time::Instant) -> io::Result<Response> {
+        use std::sync::mpsc::RecvTimeoutError;
+
+        loop {
+            let left = deadline.saturating_duration_since(std::time::Instant::now());
+            let line = self.lines.recv_timeout(left).map_err(|e| match e {
+                RecvTimeoutError::Timeout => io::Error::new(io::ErrorKind::TimedOut, "движок не ответил"),
+                RecvTimeoutError::Disconnected => io::Error::new(io::ErrorKind::UnexpectedEof, "движок завершил работу"),
+            })?;
+            if let Some(response) = Response::parse(&line) {
+                return Ok(response);
+            }
+        }
+    }
+
+    /// Просит движок найти ход и ждет `bestmove` не дольше времени на ход
+    /// плюс [`SEARCH_GRACE`]
+    ///
+    /// Опоздавший движок останавливается: его запоздалый `bestmove`
+    /// иначе был бы принят за ответ на следующую позицию.
+    fn search(&mut self, game: &TicTacToe) -> io::Result<Option<usize>> {
+        let rules = game.rules();
+        if self.rules != Some(rules) || game.moves().is_empty() {
+            self.send(&Command::NewGame(rules))?;
+            self.rules = Some(rules);
+        }
+        self.send(&Command::Position {
+            start: StartPosition::StartPos,
+            moves: game.moves().iter().map(|&p| format_position(rules, p)).collect(),
+        })?;
+        self.send(&Command::Go { movetime: Some(self.move_time) })?;
+
+        let deadline = std::time::Instant::now() + self.move_time + SEARCH_GRACE;
+        loop {
+            match self.receive_until(deadline) {
+                Ok(Response::Info(info)) => self.info = Some(info),
+                Ok(Response::BestMove(coord)) => return Ok(coord.and_then(|coord| parse_position(rules, &coord))),
+                Ok(_) => {}
+                Err(error) => {
+                    if error.kind() == io::ErrorKind::TimedOut {
+                        let _ = self.child.kill();
+                    }
+                    return Err(error);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Engine for ExternalEngine {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    /// Ошибка связи с движком или превышение времени означает, что хода нет
+    fn choose_move(&mut self, game: &TicTacToe) -> Option<usize> {
+        if game.is_game_over() {
+            return None;
+        }
+        self.search(game).ok().flatten()
+    }
+
+    fn set_move_time(&mut self, time: Duration) {
+        self.move_time = time;
+    }
+
+    fn info(&self) -> Option<EngineInfo> {
+        self.info.clone()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Drop for ExternalEngine {
+    fn drop(&mut self) {
+        let _ = self.send(&Command::Quit);
+        if !matches!(self.child.try_wait(), Ok(Some(_))) {
+            std::thread::sleep(Duration::from_millis(50));
+            let _ = self.child.kill();
+        }
+        let _ = self.child.wait();
+    }
+}
+
+// === ТЕСТЫ ===
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::threat::ThreatSpaceEngine;
+
+    /// Тест: команды и ответы разбираются и печатаются без потерь
+    #[test]
+    fn test_round_trip() {
+        for line in [
+            "ttp",
+            "newgame 15x15/5",
+            "position startpos moves h8 h9",
+            "position fen x2/1o1/3 x moves c3",
+            "go movetime 250",
+            "quit",
+        ] {
+            assert_eq!(Command::parse(line).unwrap().to_string(), line);
+        }
+        for line in ["id name Threat-space", "ttpok", "info depth 3 nodes 10 score -5", "bestmove a1", "bestmove none"] {
+            assert_eq!(Response::parse(line).unwrap().to_string(), line);
+        }
+    }
+
+    /// Тест: запись fen восстанавливает поле и ходящего игрока
+    #[test]
+    fn test_fen() {
+        let mut game = TicTacToe::new();
+        game.make_move(0);
+        game.make_move(4);
+        assert_eq!(to_fen(&game), "x2/1o1/3 x");
+
+        let restored = from_fen(Rules::classic(), "x2/1o1/3 x").unwrap();
+        assert_eq!(restored.get_board(), game.get_board());
+        assert_eq!(restored.current_player(), Player::X);
+        assert!(from_fen(Rules::classic(), "x3/1o1/3 x").is_none());
+        assert!(from_fen(Rules::classic(), "99999999999/1o1/3 x").is_none());
+        assert!(from_fen(Rules::classic(), "3/3/3/xxxxxxxxxx x").is_none());
+    }
+
+    /// Тест: программа, которая не отвечает по протоколу, не подвешивает запуск
+    #[cfg(unix)]
+    #[test]
+    fn test_spawn_handshake_timeout() {
+        let started = std::time::Instant::now();
+        let error = ExternalEngine::spawn("cat", &[]).err().expect("cat - не движок");
+        assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+        assert!(started.elapsed() < HANDSHAKE_TIMEOUT * 2);
+
+        // Смотря по тому, успеет ли `true` завершиться до отправки `ttp`
+        let error = ExternalEngine::spawn("true", &[]).err().expect("true - не движок");
+        assert!(matches!(error.kind(), io::ErrorKind::UnexpectedEof | io::ErrorKind::BrokenPipe), "{:?}", error);
+    }
+
+    /// Тест: движок, не приславший `bestmove`, не подвешивает поиск хода
+    #[cfg(unix)]
+    #[test]
+    fn test_search_deadline() {
+        let script = "while read line; do [ \"$line\" = ttp ] && echo ttpok; done";
+        let mut engine = ExternalEngine::spawn("sh", &["-c", script]).unwrap();
+        engine.set_move_time(Duration::from_millis(100));
+
+        let started = std::time::Instant::now();
+        assert_eq!(engine.choose_move(&TicTacToe::new()), None);
+        assert!(started.elapsed() < Duration::from_millis(100) + SEARCH_GRACE * 2);
+        // Остановленный движок больше не отвечает, но и не подвешивает
+        assert_eq!(engine.choose_move(&TicTacToe::new()), None);
+    }
+
+    /// Тест: встроенный движок отвечает по протоколу и находит выигрыш
+    #[test]
+    fn test_serve() {
+        let input = "ttp\nnewgame 3x3/3\nposition startpos moves a1 a2 b1 b2\ngo movetime 100\nbogus\nquit\n";
+        let mut output = Vec::new();
+        serve(&mut ThreatSpaceEngine::default(), input.as_bytes(), &mut output).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[0], "id name Threat-space");
+        assert!(lines.contains(&"ttpok"));
+        assert!(lines.contains(&"bestmove c1"));
+        assert!(lines.contains(&"info string unknown command: bogus"));
+    }
+}
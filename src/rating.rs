@@ -0,0 +1,427 @@
+//! # Рейтинги игроков и движков
+//!
+//! Рейтинги пересчитываются по сыгранным партиям одной из двух систем:
+//! - Эло с настраиваемым коэффициентом K;
+//! - Glicko-2: кроме рейтинга хранятся его отклонение (насколько рейтинг
+//!   известен) и волатильность.
+//!
+//! Результаты копятся в рейтинговом периоде и применяются все вместе, когда
+//! в периоде набирается заданное число партий. Внутри периода все игроки
+//! считаются по рейтингам на его начало, поэтому порядок партий не важен.
+//!
+//! Встроенные движки ИИ служат якорями: их рейтинги закреплены и не меняются,
+//! так что шкала не «уплывает», даже если люди играют в основном с ИИ.
+//!
+//! ## Использование
+//! ```rust
+//! use rust_tic_tac_toe::Player;
+//! use rust_tic_tac_toe::rating::{Ratings, RatingSystem};
+//!
+//! let mut ratings = Ratings::new(RatingSystem::Elo { k: 32.0 }).with_ai_anchors();
+//! ratings.add_result("Alice", "Bob", Some(Player::X));
+//! assert_eq!(ratings.rating("Alice").rating.round(), 1516.0);
+//! assert_eq!(ratings.rating("threat").rating, 2000.0);
+//! ```
+
+use std::collections::BTreeMap;
+use std::f64::consts::PI;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::agent::AgentKind;
+use crate::record::GameRecord;
+use crate::{Player, Termination};
+
+/// Рейтинг нового игрока
+pub const DEFAULT_RATING: f64 = 1500.0;
+
+/// Коэффициент K системы Эло по умолчанию
+pub const DEFAULT_K: f64 = 32.0;
+
+/// Отклонение рейтинга нового игрока в Glicko-2
+pub const DEFAULT_DEVIATION: f64 = 350.0;
+
+/// Волатильность нового игрока в Glicko-2
+pub const DEFAULT_VOLATILITY: f64 = 0.06;
+
+/// Ограничение изменения волатильности (τ) в Glicko-2 по умолчанию
+pub const DEFAULT_TAU: f64 = 0.5;
+
+/// Отклонение рейтинга движков-якорей
+const ANCHOR_DEVIATION: f64 = 30.0;
+
+/// Перевод между шкалой рейтинга и внутренней шкалой Glicko-2
+const GLICKO_SCALE: f64 = 173.7178;
+
+/// Точность подбора волатильности в Glicko-2
+const VOLATILITY_EPSILON: f64 = 1e-6;
+
+// === РЕЙТИНГ ===
+
+/// Рейтинг игрока
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Rating {
+    /// Рейтинг
+    pub rating: f64,
+    /// Отклонение рейтинга (в системе Эло не меняется)
+    pub deviation: f64,
+    /// Волатильность (в системе Эло не меняется)
+    pub volatility: f64,
+}
+
+impl Rating {
+    /// Рейтинг с отклонением и волатильностью нового игрока
+    pub fn new(rating: f64) -> Self {
+        Self { rating, deviation: DEFAULT_DEVIATION, volatility: DEFAULT_VOLATILITY }
+    }
+
+    /// Рейтинг, округленный до целого (для таблиц и подбора соперников)
+    pub fn rounded(&self) -> i32 {
+        self.rating.round() as i32
+    }
+}
+
+impl Default for Rating {
+    fn default() -> Self {
+        Self::new(DEFAULT_RATING)
+    }
+}
+
+impl fmt::Display for Rating {
+    /// Формат: `1532 ±120`
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ±{}", self.rounded(), self.deviation.round())
+    }
+}
+
+/// Ожидаемый результат игрока против соперника по Эло (от 0 до 1)
+pub fn expected_score(rating: f64, opponent: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf((opponent - rating) / 400.0))
+}
+
+/// Изменение рейтинга по Эло за одну партию
+///
+/// ## Параметры
+/// - `rating`, `opponent`: рейтинги игрока и соперника до партии
+/// - `score`: результат игрока (1 - победа, 0.5 - ничья, 0 - поражение)
+/// - `k`: коэффициент K
+pub fn elo_change(rating: f64, opponent: f64, score: f64, k: f64) -> f64 {
+    k * (score - expected_score(rating, opponent))
+}
+
+/// Рейтинг движка ИИ, закрепленный за ним как за якорем (`None` - не движок)
+pub fn ai_anchor(kind: AgentKind) -> Option<Rating> {
+    let rating = match kind {
+        AgentKind::Human => return None,
+        AgentKind::ThreatSpace => 2000.0,
+        AgentKind::Mcts => 1700.0,
+    };
+    Some(Rating { rating, deviation: ANCHOR_DEVIATION, volatility: DEFAULT_VOLATILITY })
+}
+
+// === СИСТЕМЫ ===
+
+/// Система пересчета рейтингов
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RatingSystem {
+    /// Эло с коэффициентом K
+    Elo { k: f64 },
+    /// Glicko-2 с ограничением изменения волатильности τ
+    Glicko2 { tau: f64 },
+}
+
+impl Default for RatingSystem {
+    fn default() -> Self {
+        RatingSystem::Glicko2 { tau: DEFAULT_TAU }
+    }
+}
+
+/// Результат партии за рейтинговый период
+#[derive(Clone, Debug, PartialEq)]
+struct PeriodResult {
+    x: String,
+    o: String,
+    /// Результат X (1 - победа, 0.5 - ничья, 0 - поражение)
+    score_x: f64,
+}
+
+/// Рейтинги игроков с якорями и текущим рейтинговым периодом
+#[derive(Clone, Debug)]
+pub struct Ratings {
+    system: RatingSystem,
+    period_length: usize,
+    players: BTreeMap<String, Rating>,
+    anchors: BTreeMap<String, Rating>,
+    pending: Vec<PeriodResult>,
+}
+
+impl Ratings {
+    /// Создает рейтинги без якорей; каждая партия - отдельный период
+    pub fn new(system: RatingSystem) -> Self {
+        Self {
+            system,
+            period_length: 1,
+            players: BTreeMap::new(),
+            anchors: BTreeMap::new(),
+            pending: Vec::new(),
+        }
+    }
+
+    /// Пересчитывает рейтинги после каждых `games` партий (не меньше одной)
+    pub fn with_period(mut self, games: usize) -> Self {
+        self.period_length = games.max(1);
+        self
+    }
+
+    /// Закрепляет рейтинги встроенных движков под их названиями ([`AgentKind::id`])
+    pub fn with_ai_anchors(mut self) -> Self {
+        for kind in AgentKind::ALL {
+            if let Some(rating) = ai_anchor(kind) {
+                self.set_anchor(kind.id(), rating);
+            }
+        }
+        self
+    }
+
+    /// Закрепляет рейтинг игрока: результаты партий его не меняют
+    pub fn set_anchor(&mut self, name: &str, rating: Rating) {
+        self.players.remove(name);
+        self.anchors.insert(name.to_string(), rating);
+    }
+
+    /// Система пересчета
+    pub fn system(&self) -> RatingSystem {
+        self.system
+    }
+
+    /// Рейтинг игрока (новые игроки получают [`Rating::default`])
+    pub fn rating(&self, name: &str) -> Rating {
+        self.anchors.get(name).or_else(|| self.players.get(name)).copied().unwrap_or_default()
+    }
+
+    /// Является ли игрок якорем
+    pub fn is_anchor(&self, name: &str) -> bool {
+        self.anchors.contains_key(name)
+    }
+
+    /// Игроки с рейтингами (без якорей) по убыванию рейтинга
+    pub fn table(&self) -> Vec<(&str, Rating)> {
+        let mut table: Vec<_> = self.players.iter().map(|(name, rating)| (name.as_str(), *rating)).collect();
+        table.sort_by(|a, b| b.1.rating.total_cmp(&a.1.rating));
+        table
+    }
+
+    /// Партии текущего периода, еще не учтенные в рейтингах
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Добавляет результат партии; в конце периода рейтинги пересчитываются
+    ///
+    /// ## Параметры
+    /// - `x`, `o`: имена игроков X и O (партия с самим собой не учитывается)
+    /// - `winner`: победитель (`None` - ничья)
+    pub fn add_result(&mut self, x: &str, o: &str, winner: Option<Player>) {
+        if x == o {
+            return;
+        }
+        let score_x = match winner {
+            Some(Player::X) => 1.0,
+            Some(Player::O) => 0.0,
+            None => 0.5,
+        };
+        self.pending.push(PeriodResult { x: x.to_string(), o: o.to_string(), score_x });
+        if self.pending.len() >= self.period_length {
+            self.close_period();
+        }
+    }
+
+    /// Добавляет результат записанной партии (прерванные не учитываются)
+    pub fn add_record(&mut self, record: &GameRecord) {
+        if record.termination != Some(Termination::Aborted) {
+            self.add_result(&record.x, &record.o, record.winner);
+        }
+    }
+
+    /// Пересчитывает рейтинги по записанным партиям
+    pub fn from_records<'a>(mut self, records: impl IntoIterator<Item = &'a GameRecord>) -> Self {
+        for record in records {
+            self.add_record(record);
+        }
+        self
+    }
+
+    /// Закрывает рейтинговый период досрочно и применяет его результаты
+    pub fn close_period(&mut self) {
+        let results = std::mem::take(&mut self.pending);
+        // Результаты каждого игрока: рейтинг соперника и набранные очки
+        let mut games: BTreeMap<&str, Vec<(Rating, f64)>> = BTreeMap::new();
+        for result in &results {
+            games.entry(&result.x).or_default().push((self.rating(&result.o), result.score_x));
+            games.entry(&result.o).or_default().push((self.rating(&result.x), 1.0 - result.score_x));
+        }
+
+        let mut updated = BTreeMap::new();
+        for (name, games) in &games {
+            if !self.is_anchor(name) {
+                updated.insert(name.to_string(), self.system.update(self.rating(name), games));
+            }
+        }
+        // В Glicko-2 отклонение тех, кто не играл в периоде, растет
+        if let RatingSystem::Glicko2 { .. } = self.system {
+            for (name, rating) in self.players.iter().filter(|(name, _)| !games.contains_key(name.as_str())) {
+                updated.insert(name.clone(), self.system.update(*rating, &[]));
+            }
+        }
+        self.players.extend(updated);
+    }
+}
+
+impl Default for Ratings {
+    /// Glicko-2 с τ по умолчанию, без якорей
+    fn default() -> Self {
+        Self::new(RatingSystem::default())
+    }
+}
+
+impl RatingSystem {
+    /// Новый рейтинг игрока после периода
+    ///
+    /// ## Параметры
+    /// - `rating`: рейтинг на начало периода
+    /// - `games`: рейтинги соперников и результаты партий периода
+    pub fn update(&self, rating: Rating, games: &[(Rating, f64)]) -> Rating {
+        match *self {
+            RatingSystem::Elo { k } => {
+                let change: f64 = games.iter().map(|(opponent, score)| elo_change(rating.rating, opponent.rating, *score, k)).sum();
+                Rating { rating: rating.rating + change, ..rating }
+            }
+            RatingSystem::Glicko2 { tau } => glicko2_update(rating, games, tau),
+        }
+    }
+}
+
+/// Пересчет по Glicko-2 (Glickman, «Example of the Glicko-2 system»)
+fn glicko2_update(rating: Rating, games: &[(Rating, f64)], tau: f64) -> Rating {
+    let mu = (rating.rating - DEFAULT_RATING) / GLICKO_SCALE;
+    let phi = rating.deviation / GLICKO_SCALE;
+    let sigma = rating.volatility;
+    let deviation = |phi: f64| (phi * GLICKO_SCALE).min(DEFAULT_DEVIATION);
+
+    if games.is_empty() {
+        let phi = (phi * phi + sigma * sigma).sqrt();
+        return Rating { deviation: deviation(phi), ..rating };
+    }
+
+    let g = |phi: f64| 1.0 / (1.0 + 3.0 * phi * phi / (PI * PI)).sqrt();
+    // Для каждого соперника: g(φj), ожидаемый результат и набранные очки
+    let terms: Vec<(f64, f64, f64)> = games
+        .iter()
+        .map(|(opponent, score)| {
+            let mu_j = (opponent.rating - DEFAULT_RATING) / GLICKO_SCALE;
+            let g_j = g(opponent.deviation / GLICKO_SCALE);
+            (g_j, 1.0 / (1.0 + (-g_j * (mu - mu_j)).exp()), *score)
+        })
+        .collect();
+    let v = 1.0 / terms.iter().map(|(g_j, e, _)| g_j * g_j * e * (1.0 - e)).sum::<f64>();
+    let improvement: f64 = terms.iter().map(|(g_j, e, s)| g_j * (s - e)).sum();
+    let delta = v * improvement;
+
+    // Новая волатильность: корень f(x) = 0 методом Иллинойса
+    let a = (sigma * sigma).ln();
+    let f = |x: f64| {
+        let ex = x.exp();
+        ex * (delta * delta - phi * phi - v - ex) / (2.0 * (phi * phi + v + ex).powi(2)) - (x - a) / (tau * tau)
+    };
+    let mut lower = a;
+    let mut upper = if delta * delta > phi * phi + v {
+        (delta * delta - phi * phi - v).ln()
+    } else {
+        let mut k = 1.0;
+        while f(a - k * tau) < 0.0 {
+            k += 1.0;
+        }
+        a - k * tau
+    };
+    let (mut f_lower, mut f_upper) = (f(lower), f(upper));
+    while (upper - lower).abs() > VOLATILITY_EPSILON {
+        let c = lower + (lower - upper) * f_lower / (f_upper - f_lower);
+        let f_c = f(c);
+        if f_c * f_upper <= 0.0 {
+            lower = upper;
+            f_lower = f_upper;
+        } else {
+            f_lower /= 2.0;
+        }
+        upper = c;
+        f_upper = f_c;
+    }
+    let volatility = (lower / 2.0).exp();
+
+    let phi_star = (phi * phi + volatility * volatility).sqrt();
+    let phi_new = 1.0 / (1.0 / (phi_star * phi_star) + 1.0 / v).sqrt();
+    let mu_new = mu + phi_new * phi_new * improvement;
+    Rating {
+        rating: mu_new * GLICKO_SCALE + DEFAULT_RATING,
+        deviation: deviation(phi_new),
+        volatility,
+    }
+}
+
+// === ТЕСТЫ ===
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Тест: пример из описания Glicko-2
+    #[test]
+    fn test_glicko2_example() {
+        let player = Rating { rating: 1500.0, deviation: 200.0, volatility: 0.06 };
+        let games = [
+            (Rating { rating: 1400.0, deviation: 30.0, volatility: 0.06 }, 1.0),
+            (Rating { rating: 1550.0, deviation: 100.0, volatility: 0.06 }, 0.0),
+            (Rating { rating: 1700.0, deviation: 300.0, volatility: 0.06 }, 0.0),
+        ];
+        let updated = RatingSystem::Glicko2 { tau: 0.5 }.update(player, &games);
+        assert!((updated.rating - 1464.06).abs() < 0.01, "{}", updated.rating);
+        assert!((updated.deviation - 151.52).abs() < 0.01, "{}", updated.deviation);
+        assert!((updated.volatility - 0.05999).abs() < 1e-5, "{}", updated.volatility);
+
+        // Без партий растет только отклонение
+        let idle = RatingSystem::Glicko2 { tau: 0.5 }.update(player, &[]);
+        assert_eq!(idle.rating, 1500.0);
+        assert!(idle.deviation > 200.0);
+    }
+
+    /// Тест: Эло по периодам считается по рейтингам на начало периода
+    #[test]
+    fn test_elo_periods() {
+        let mut ratings = Ratings::new(RatingSystem::Elo { k: 20.0 }).with_period(2);
+        ratings.add_result("a", "b", Some(Player::X));
+        assert_eq!(ratings.pending(), 1);
+        assert_eq!(ratings.rating("a").rating, DEFAULT_RATING);
+
+        ratings.add_result("b", "a", None);
+        assert_eq!(ratings.pending(), 0);
+        // Обе партии считаются от 1500: +10 за победу, 0 за ничью
+        assert_eq!(ratings.rating("a").rating, 1510.0);
+        assert_eq!(ratings.rating("b").rating, 1490.0);
+        assert_eq!(ratings.table()[0].0, "a");
+    }
+
+    /// Тест: якоря не меняются, прерванные партии не учитываются
+    #[test]
+    fn test_anchors() {
+        let win = GameRecord { rules: crate::Rules::classic(), x: "Alice".into(), o: "mcts".into(), moves: vec![0, 3, 1, 4, 2], winner: Some(Player::X), termination: None };
+        let aborted = GameRecord { moves: vec![], winner: None, termination: Some(Termination::Aborted), ..win.clone() };
+        let ratings = Ratings::default().with_ai_anchors().from_records([&win, &aborted]);
+
+        assert_eq!(ratings.rating("mcts"), ai_anchor(AgentKind::Mcts).unwrap());
+        assert!(ratings.rating("Alice").rating > 1700.0, "победа над якорем 1700 при большом отклонении");
+        assert!(ratings.rating("Alice").deviation < DEFAULT_DEVIATION);
+        assert_eq!(ratings.table().len(), 1);
+    }
+}
@@ -0,0 +1,325 @@
+//! # Записи партий
+//!
+//! Запись хранит правила, имена игроков, последовательность ходов и результат.
+//! Ходы записываются координатами: буква столбца и номер строки сверху,
+//! например `a1` - левый верхний угол, `b2` - центр классического поля.
+//!
+//! ## Использование
+//! ```rust
+//! use rust_tic_tac_toe::TicTacToe;
+//! use rust_tic_tac_toe::record::{self, GameRecord};
+//!
+//! let mut game = TicTacToe::new();
+//! game.make_move(4);
+//! assert_eq!(record::format_position(game.rules(), 4), "b2");
+//!
+//! let record = GameRecord::from_game(&game, "Alice", "Bob");
+//! println!("{}", record.to_notation());
+//! assert!(record.verify().is_empty());
+//! ```
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Player, Rules, Termination, TicTacToe};
+
+/// Запись одной партии
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GameRecord {
+    /// Правила партии
+    pub rules: Rules,
+    /// Имя игрока X
+    pub x: String,
+    /// Имя игрока O
+    pub o: String,
+    /// Ходы в порядке их совершения
+    pub moves: Vec<usize>,
+    /// Победитель (`None` - ничья или партия не закончена)
+    pub winner: Option<Player>,
+    /// Причина окончания партии (`None` - партия не закончена или запись старого формата)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub termination: Option<Termination>,
+}
+
+impl GameRecord {
+    /// Создает запись по текущему состоянию игры
+    pub fn from_game(game: &TicTacToe, x: &str, o: &str) -> Self {
+        Self {
+            rules: game.rules(),
+            x: x.to_string(),
+            o: o.to_string(),
+            moves: game.moves().to_vec(),
+            winner: game.get_winner(),
+            termination: game.termination(),
+        }
+    }
+
+    /// Результат в шахматной записи: `1-0`, `0-1` или `1/2-1/2`
+    pub fn result_string(&self) -> &'static str {
+        match self.winner {
+            Some(Player::X) => "1-0",
+            Some(Player::O) => "0-1",
+            None => "1/2-1/2",
+        }
+    }
+
+    /// Текстовая запись партии с заголовками и ходами
+    ///
+    /// ## Пример вывода
+    /// ```text
+    /// [Rules "3x3/3"]
+    /// [X "Alice"]
+    /// [O "Bob"]
+    /// [Result "1-0"]
+    ///
+    /// b2 a1 c3 a3 a2 c2 b1 b3 c1 1-0
+    /// ```
+    ///
+    /// Кавычки и обратная косая черта в именах экранируются обратной косой чертой,
+    /// чтобы имя не могло закрыть заголовок раньше времени.
+    pub fn to_notation(&self) -> String {
+        let moves: Vec<String> = self
+            .moves
+            .iter()
+            .map(|&position| format_position(self.rules, position))
+            .collect();
+        format!(
+            "[Rules \"{}\"]\n[X \"{}\"]\n[O \"{}\"]\n[Result \"{}\"]\n\n{} {}\n",
+            self.rules,
+            escape_header(&self.x),
+            escape_header(&self.o),
+            self.result_string(),
+            moves.join(" "),
+            self.result_string(),
+        )
+    }
+
+    /// Повторно проигрывает партию и ищет несоответствия
+    ///
+    /// Ходы после первой ошибки не проверяются: позиция уже недостоверна.
+    ///
+    /// ## Возвращает
+    /// Список несоответствий (пустой - запись корректна)
+    ///
+    /// ## Пример
+    /// ```rust
+    /// use rust_tic_tac_toe::{Player, Rules};
+    /// use rust_tic_tac_toe::record::{GameRecord, Inconsistency};
+    ///
+    /// let record = GameRecord {
+    ///     rules: Rules::classic(),
+    ///     x: "Alice".into(),
+    ///     o: "Bob".into(),
+    ///     moves: vec![4, 4],
+    ///     winner: Some(Player::X),
+    ///     termination: None,
+    /// };
+    /// assert_eq!(record.verify()[0], Inconsistency::IllegalMove { index: 1, position: 4 });
+    /// ```
+    pub fn verify(&self) -> Vec<Inconsistency> {
+        if self.rules.validate().is_err() {
+            return vec![Inconsistency::InvalidRules];
+        }
+        let mut game = TicTacToe::with_rules(self.rules);
+        for (index, &position) in self.moves.iter().enumerate() {
+            if game.is_game_over() {
+                return vec![Inconsistency::MoveAfterGameOver { index }];
+            }
+            if !game.make_move(position) {
+                return vec![Inconsistency::IllegalMove { index, position }];
+            }
+        }
+        match self.termination {
+            Some(recorded @ (Termination::Line | Termination::FullBoard)) if game.termination() != Some(recorded) => {
+                return vec![Inconsistency::WrongTermination { recorded, actual: game.termination() }];
+            }
+            Some(Termination::Line | Termination::FullBoard) | None => {}
+            Some(termination) if !game.terminate(termination) => {
+                return vec![Inconsistency::TerminationAfterGameOver];
+            }
+            Some(_) => {}
+        }
+        if game.get_winner() != self.winner {
+            return vec![Inconsistency::WrongWinner { recorded: self.winner, actual: game.get_winner() }];
+        }
+        Vec::new()
+    }
+}
+
+/// Несоответствие в записи партии, найденное при ее повторном проигрывании
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Inconsistency {
+    /// Ход вне поля или в занятую клетку
+    IllegalMove {
+        /// Номер хода в записи (с нуля)
+        index: usize,
+        /// Клетка
+        position: usize,
+    },
+    /// Ход после окончания партии
+    MoveAfterGameOver {
+        /// Номер хода в записи (с нуля)
+        index: usize,
+    },
+    /// Записанный результат не совпадает с результатом ходов
+    WrongWinner {
+        /// Победитель в записи
+        recorded: Option<Player>,
+        /// Победитель по ходам
+        actual: Option<Player>,
+    },
+    /// Причина окончания не совпадает с позицией на поле
+    WrongTermination {
+        /// Причина в записи
+        recorded: Termination,
+        /// Причина по ходам (`None` - партия по ходам не закончена)
+        actual: Option<Termination>,
+    },
+    /// Партия завершена сдачей, временем или соглашением уже после ее окончания
+    TerminationAfterGameOver,
+    /// Правила вне допустимых границ (см. [`Rules::try_new`])
+    InvalidRules,
+    /// Ход в журнале сервера не совпадает с ходом в записи
+    LogMismatch {
+        /// Номер хода (с нуля)
+        index: usize,
+        /// Ход в журнале (`None` - журнал короче записи)
+        logged: Option<usize>,
+        /// Ход в записи (`None` - запись короче журнала)
+        recorded: Option<usize>,
+    },
+}
+
+impl fmt::Display for Inconsistency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::IllegalMove { index, position } => write!(f, "ход {}: клетка {} недоступна", index + 1, position),
+            Self::MoveAfterGameOver { index } => write!(f, "ход {}: партия уже закончена", index + 1),
+            Self::WrongTermination { recorded, actual } => {
+                write!(f, "записана причина {:?}, по ходам {:?}", recorded, actual)
+            }
+            Self::TerminationAfterGameOver => write!(f, "партия завершена после ее окончания"),
+            Self::InvalidRules => write!(f, "недопустимые правила"),
+            Self::WrongWinner { recorded, actual } => {
+                write!(f, "записан победитель {:?}, по ходам {:?}", recorded, actual)
+            }
+            Self::LogMismatch { index, logged, recorded } => {
+                write!(f, "ход {}: в журнале {:?}, в записи {:?}", index + 1, logged, recorded)
+            }
+        }
+    }
+}
+
+/// Экранирует значение заголовка записи (как в PGN)
+fn escape_header(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Записывает ходы с номерами: `1. b2 a1 2. c3`
+pub fn format_moves(rules: Rules, moves: &[usize]) -> String {
+    moves
+        .chunks(2)
+        .enumerate()
+        .map(|(index, pair)| {
+            let pair: Vec<String> = pair.iter().map(|&position| format_position(rules, position)).collect();
+            format!("{}. {}", index + 1, pair.join(" "))
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Записывает клетку координатами (`a1`, `h8`, ...)
+///
+/// Столбец - одна буква, поэтому поле не шире [`Rules::MAX_SIZE`].
+pub fn format_position(rules: Rules, position: usize) -> String {
+    let column = (b'a' + (position % rules.size) as u8) as char;
+    format!("{}{}", column, position / rules.size + 1)
+}
+
+/// Разбирает координаты клетки
+///
+/// ## Возвращает
+/// - `Some(position)` - индекс клетки
+/// - `None` - если координаты некорректны или вне поля
+pub fn parse_position(rules: Rules, text: &str) -> Option<usize> {
+    let mut chars = text.chars();
+    let column = chars.next()?.to_ascii_lowercase();
+    if !column.is_ascii_lowercase() {
+        return None;
+    }
+    let column = (column as u8 - b'a') as usize;
+    let row: usize = chars.as_str().parse().ok()?;
+    if column >= rules.size || row == 0 || row > rules.size {
+        return None;
+    }
+    Some((row - 1) * rules.size + column)
+}
+
+// === ТЕСТЫ ===
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(moves: Vec<usize>, winner: Option<Player>) -> GameRecord {
+        GameRecord { rules: Rules::classic(), x: "A".into(), o: "B".into(), moves, winner, termination: None }
+    }
+
+    /// Тест: повторное проигрывание находит подделанные записи
+    #[test]
+    fn test_verify() {
+        assert!(record(vec![0, 3, 1, 4, 2], Some(Player::X)).verify().is_empty());
+        assert_eq!(
+            record(vec![0, 3, 1, 4, 2], Some(Player::O)).verify(),
+            vec![Inconsistency::WrongWinner { recorded: Some(Player::O), actual: Some(Player::X) }]
+        );
+        assert_eq!(
+            record(vec![0, 3, 1, 4, 2, 5], Some(Player::X)).verify(),
+            vec![Inconsistency::MoveAfterGameOver { index: 5 }]
+        );
+        assert_eq!(record(vec![9], None).verify(), vec![Inconsistency::IllegalMove { index: 0, position: 9 }]);
+
+        // Победа по времени и сдача
+        let timeout = GameRecord { termination: Some(Termination::Timeout(Player::O)), ..record(vec![4], Some(Player::X)) };
+        assert!(timeout.verify().is_empty());
+        let resigned = GameRecord { termination: Some(Termination::Resignation(Player::X)), ..record(vec![4], None) };
+        assert_eq!(
+            resigned.verify(),
+            vec![Inconsistency::WrongWinner { recorded: None, actual: Some(Player::O) }]
+        );
+        let late = GameRecord {
+            termination: Some(Termination::Agreement),
+            ..record(vec![0, 3, 1, 4, 2], Some(Player::X))
+        };
+        assert_eq!(late.verify(), vec![Inconsistency::TerminationAfterGameOver]);
+        let line = GameRecord { termination: Some(Termination::Line), ..record(vec![4], None) };
+        assert_eq!(
+            line.verify(),
+            vec![Inconsistency::WrongTermination { recorded: Termination::Line, actual: None }]
+        );
+        let huge = GameRecord { rules: Rules { size: usize::MAX, win_length: 5 }, ..record(vec![], None) };
+        assert_eq!(huge.verify(), vec![Inconsistency::InvalidRules]);
+    }
+
+    /// Тест: координаты на самом большом поле - от `a1` до `z26`
+    #[test]
+    fn test_positions_on_largest_board() {
+        let rules = Rules::new(Rules::MAX_SIZE, 5);
+        let last = rules.cell_count() - 1;
+        assert_eq!(format_position(rules, last), "z26");
+        assert_eq!(parse_position(rules, "z26"), Some(last));
+        assert_eq!(parse_position(rules, "Z1"), Some(Rules::MAX_SIZE - 1));
+        assert_eq!(parse_position(Rules::classic(), "d1"), None);
+    }
+
+    /// Тест: кавычка в имени не закрывает заголовок записи
+    #[test]
+    fn test_notation_escapes_names() {
+        let quoted = GameRecord { x: "Al\"] [Result \"0-1".into(), o: "C:\\".into(), ..record(vec![4], None) };
+        let notation = quoted.to_notation();
+        assert!(notation.contains("[X \"Al\\\"] [Result \\\"0-1\"]\n"), "{}", notation);
+        assert!(notation.contains("[O \"C:\\\\\"]\n"), "{}", notation);
+        assert_eq!(notation.lines().filter(|line| line.starts_with("[Result ")).count(), 1);
+    }
+}
@@ -0,0 +1,301 @@
+//! # Матч из нескольких партий
+//!
+//! Матч ведет общий счет двух участников между партиями и чередует, кто
+//! начинает: в первой партии за X играет первый участник, во второй - второй
+//! и так далее. Формат определяет, когда матч заканчивается:
+//! - «до N побед» - побеждает тот, кто первым выиграл N партий;
+//! - «лучший из N» - не больше N партий, побеждает набравший больше очков
+//!   (победа - 1 очко, ничья - ½); матч заканчивается досрочно, когда
+//!   отставшему уже не догнать;
+//! - открытый матч только ведет счет.
+//!
+//! Прерванные партии в счет не идут, и очередность при них не меняется.
+//!
+//! ## Использование
+//! ```rust
+//! use rust_tic_tac_toe::TicTacToe;
+//! use rust_tic_tac_toe::series::{Match, MatchFormat};
+//!
+//! let mut series = Match::new(MatchFormat::FirstTo(1), "Alice", "Bob");
+//! assert_eq!(series.x_index(), 0);
+//!
+//! let mut game = TicTacToe::new();
+//! for position in [0, 3, 1, 4, 2] {
+//!     game.make_move(position);
+//! }
+//! assert!(series.record(&game));
+//! assert_eq!(series.winner(), Some(0));
+//! assert_eq!(series.to_string(), "Alice 1 : 0 Bob");
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{Player, Termination, TicTacToe};
+
+// === ФОРМАТ ===
+
+/// Условие окончания матча
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum MatchFormat {
+    /// Матч без конца, только счет
+    #[default]
+    Open,
+    /// До N побед
+    FirstTo(u32),
+    /// Лучший из N партий
+    BestOf(u32),
+}
+
+impl FromStr for MatchFormat {
+    type Err = String;
+
+    /// Разбирает `bo3` (лучший из 3), `ft2` (до 2 побед) или пустую строку (открытый матч)
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let text = text.trim().to_lowercase();
+        let error = || format!("неверный формат матча: {} (ожидается bo3 или ft2)", text);
+        let count = |count: &str| match count.parse::<u32>() {
+            Ok(count) if count > 0 => Ok(count),
+            _ => Err(error()),
+        };
+        if text.is_empty() || text == "open" {
+            Ok(MatchFormat::Open)
+        } else if let Some(games) = text.strip_prefix("bo") {
+            count(games).map(MatchFormat::BestOf)
+        } else if let Some(wins) = text.strip_prefix("ft") {
+            count(wins).map(MatchFormat::FirstTo)
+        } else {
+            Err(error())
+        }
+    }
+}
+
+impl fmt::Display for MatchFormat {
+    /// Обратно к [`MatchFormat::from_str`]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MatchFormat::Open => write!(f, "open"),
+            MatchFormat::FirstTo(wins) => write!(f, "ft{}", wins),
+            MatchFormat::BestOf(games) => write!(f, "bo{}", games),
+        }
+    }
+}
+
+// === МАТЧ ===
+
+/// Счет матча двух участников
+#[derive(Clone, Debug, PartialEq)]
+pub struct Match {
+    format: MatchFormat,
+    players: [String; 2],
+    wins: [u32; 2],
+    draws: u32,
+}
+
+impl Match {
+    /// Начинает матч; `first` играет за X в первой партии
+    pub fn new(format: MatchFormat, first: &str, second: &str) -> Self {
+        Self {
+            format,
+            players: [first.to_string(), second.to_string()],
+            wins: [0, 0],
+            draws: 0,
+        }
+    }
+
+    /// Формат матча
+    pub fn format(&self) -> MatchFormat {
+        self.format
+    }
+
+    /// Имена участников
+    pub fn players(&self) -> &[String; 2] {
+        &self.players
+    }
+
+    /// Победы участника (0 - первый, 1 - второй)
+    pub fn wins(&self, index: usize) -> u32 {
+        self.wins[index]
+    }
+
+    /// Ничьи
+    pub fn draws(&self) -> u32 {
+        self.draws
+    }
+
+    /// Засчитанные партии
+    pub fn games(&self) -> u32 {
+        self.wins[0] + self.wins[1] + self.draws
+    }
+
+    /// Очки участника в половинах очка (победа - 2, ничья - 1)
+    fn half_points(&self, index: usize) -> u32 {
+        2 * self.wins[index] + self.draws
+    }
+
+    /// Очки участника (победа - 1, ничья - ½)
+    pub fn points(&self, index: usize) -> f64 {
+        f64::from(self.half_points(index)) / 2.0
+    }
+
+    /// Участник, который играет за X в следующей партии
+    pub fn x_index(&self) -> usize {
+        (self.games() % 2) as usize
+    }
+
+    /// Участник, который играет за сторону в следующей партии
+    pub fn player_for(&self, side: Player) -> usize {
+        match side {
+            Player::X => self.x_index(),
+            Player::O => 1 - self.x_index(),
+        }
+    }
+
+    /// Засчитывает законченную партию
+    ///
+    /// ## Возвращает
+    /// `true`, если партия засчитана; незаконченные и прерванные партии,
+    /// а также партии после окончания матча не засчитываются
+    pub fn record(&mut self, game: &TicTacToe) -> bool {
+        if !game.is_game_over() || game.termination() == Some(Termination::Aborted) || self.is_over() {
+            return false;
+        }
+        match game.get_winner() {
+            Some(side) => self.wins[self.player_for(side)] += 1,
+            None => self.draws += 1,
+        }
+        true
+    }
+
+    /// Закончен ли матч
+    pub fn is_over(&self) -> bool {
+        match self.format {
+            MatchFormat::Open => false,
+            MatchFormat::FirstTo(wins) => self.wins.iter().any(|&won| won >= wins),
+            MatchFormat::BestOf(games) => {
+                self.games() >= games || (0..2).any(|index| self.half_points(index) > games)
+            }
+        }
+    }
+
+    /// Победитель матча (`None` - матч не окончен или закончился вничью)
+    pub fn winner(&self) -> Option<usize> {
+        if !self.is_over() {
+            return None;
+        }
+        match self.half_points(0).cmp(&self.half_points(1)) {
+            std::cmp::Ordering::Greater => Some(0),
+            std::cmp::Ordering::Less => Some(1),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+
+    /// Начинает матч заново с теми же участниками и форматом
+    pub fn reset(&mut self) {
+        self.wins = [0, 0];
+        self.draws = 0;
+    }
+}
+
+/// Очки с половинками: `1½`, `½`, `2`
+pub fn format_points(points: f64) -> String {
+    let whole = points.trunc() as u32;
+    match (whole, points.fract() > 0.0) {
+        (0, true) => "½".to_string(),
+        (whole, true) => format!("{}½", whole),
+        (whole, false) => whole.to_string(),
+    }
+}
+
+impl fmt::Display for Match {
+    /// Формат: `Alice 1½ : ½ Bob`
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} : {} {}",
+            self.players[0],
+            format_points(self.points(0)),
+            format_points(self.points(1)),
+            self.players[1]
+        )
+    }
+}
+
+// === ТЕСТЫ ===
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn won_by(side: Player) -> TicTacToe {
+        let moves = match side {
+            Player::X => [0, 3, 1, 4, 2].as_slice(),
+            Player::O => [0, 3, 1, 4, 8, 5].as_slice(),
+        };
+        let mut game = TicTacToe::new();
+        for &position in moves {
+            game.make_move(position);
+        }
+        game
+    }
+
+    fn drawn() -> TicTacToe {
+        let mut game = TicTacToe::new();
+        game.offer_draw(Player::X);
+        game.accept_draw(Player::O);
+        game
+    }
+
+    /// Тест: разбор формата матча
+    #[test]
+    fn test_format_parse() {
+        assert_eq!("bo3".parse(), Ok(MatchFormat::BestOf(3)));
+        assert_eq!(" FT2 ".parse(), Ok(MatchFormat::FirstTo(2)));
+        assert_eq!("".parse(), Ok(MatchFormat::Open));
+        assert!("bo0".parse::<MatchFormat>().is_err());
+        assert!("best".parse::<MatchFormat>().is_err());
+        assert_eq!(MatchFormat::BestOf(5).to_string(), "bo5");
+    }
+
+    /// Тест: участники чередуются, победы засчитываются нужному участнику
+    #[test]
+    fn test_first_to() {
+        let mut series = Match::new(MatchFormat::FirstTo(2), "A", "B");
+        // A играет за X и выигрывает
+        assert!(series.record(&won_by(Player::X)));
+        assert_eq!(series.x_index(), 1);
+        // Теперь A играет за O и снова выигрывает
+        assert!(series.record(&won_by(Player::O)));
+        assert_eq!((series.wins(0), series.wins(1)), (2, 0));
+        assert_eq!(series.winner(), Some(0));
+        assert!(!series.record(&won_by(Player::X)), "после окончания матча партии не засчитываются");
+
+        series.reset();
+        assert_eq!(series.games(), 0);
+        assert!(!series.is_over());
+    }
+
+    /// Тест: лучший из N с ничьими, досрочным концом и прерванными партиями
+    #[test]
+    fn test_best_of() {
+        let mut series = Match::new(MatchFormat::BestOf(3), "A", "B");
+        let mut aborted = TicTacToe::new();
+        aborted.abort(Player::X);
+        assert!(!series.record(&aborted));
+        assert_eq!(series.x_index(), 0);
+
+        series.record(&drawn());
+        series.record(&won_by(Player::O)); // B играет за X и проигрывает
+        assert_eq!(series.to_string(), "A 1½ : ½ B");
+        assert!(!series.is_over(), "B еще может сравнять счет");
+        series.record(&won_by(Player::O)); // A играет за X и проигрывает
+        assert!(series.is_over());
+        assert_eq!(series.winner(), None);
+
+        let mut early = Match::new(MatchFormat::BestOf(3), "A", "B");
+        early.record(&won_by(Player::X));
+        early.record(&won_by(Player::O));
+        assert!(early.is_over(), "2 из 3 - отставшему не догнать");
+        assert_eq!(early.winner(), Some(0));
+    }
+}
@@ -0,0 +1,1175 @@
+//! # Игровой сервер для онлайн-игры
+//!
+//! Сервер хранит партии в комнатах с короткими кодами. Клиенты (браузерная
+//! версия или любые программы с WebSocket) обмениваются с ним сообщениями
+//! из модуля [`crate::online`].
+//!
+//! Сервер является единственным источником истины: каждый ход проверяется
+//! через [`TicTacToe::make_move`], а клиенты получают итоговое состояние
+//! комнаты. Место игрока закреплено за секретным ключом, поэтому после
+//! перезагрузки страницы можно вернуться в партию командой `rejoin`.
+//!
+//! Кроме комнат по коду сервер ведет лобби (модуль [`crate::lobby`]):
+//! партии из лобби рейтинговые, их результаты сохраняются в хранилище.
+//!
+//! Ходы принимаются только от подключения, занимающего место ходящей стороны,
+//! и с ожидаемым номером хода: ход за соперника, повторно отправленное или
+//! устаревшее сообщение отклоняется. Все принятые и отклоненные ходы попадают
+//! в журнал комнаты (модуль [`crate::audit`]); перед изменением рейтингов
+//! журнал сверяется с записью партии.
+//!
+//! Партии с контролем времени идут по часам (модуль [`crate::clock`]): сервер
+//! переключает их после каждого хода и периодически проверяет, не истекло ли
+//! время, поэтому игрок не может затянуть партию, просто не отвечая.
+//!
+//! Игрок может сдаться, предложить ничью или прервать партию до своего первого
+//! хода. Покинувший начатую партию игрок проигрывает; то же происходит, если
+//! он не вернулся в течение [`ABANDON_TIMEOUT`] после отключения.
+//!
+//! За любой партией можно наблюдать по коду комнаты командой `watch`:
+//! зритель получает текущую позицию и все последующие ходы, но ходить не может.
+//! Число зрителей рассылается игрокам в состоянии комнаты.
+//!
+//! ## Использование
+//! ```rust,no_run
+//! use rust_tic_tac_toe::server;
+//!
+//! server::run("0.0.0.0:9001")?;
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::collections::hash_map::RandomState;
+use std::collections::{HashMap, HashSet};
+use std::hash::{BuildHasher, Hasher};
+use std::io;
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+use tungstenite::Message;
+
+use crate::audit::{AuditEvent, AuditLog};
+use crate::clock::{Clock, RealTime, TimeSource};
+use crate::lobby::{Lobby, LobbyStore, MemoryStore, Pairing, SeekOutcome};
+use crate::online::{ClientMessage, RoomState, ServerMessage, TimeControl};
+use crate::record::GameRecord;
+use crate::{Player, Rules, Termination, TicTacToe};
+
+/// Идентификатор подключения
+pub type ConnectionId = u64;
+
+/// Сообщения, которые нужно отправить после обработки запроса
+pub type Outbox = Vec<(ConnectionId, ServerMessage)>;
+
+/// Символы кода комнаты (без похожих друг на друга 0/O и 1/I)
+const CODE_ALPHABET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+
+/// Длина кода комнаты
+const CODE_LEN: usize = 5;
+
+/// Как часто поток подключения проверяет исходящие сообщения
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Как часто сервер проверяет часы партий
+const CLOCK_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Сколько ждать отключившегося игрока, прежде чем засчитать ему уход из партии
+pub const ABANDON_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Место игрока в комнате
+#[derive(Clone, Debug)]
+struct Seat {
+    name: String,
+    token: String,
+    connection: Option<ConnectionId>,
+    /// Когда игрок отключился (по источнику времени сервера)
+    disconnected_at: Option<Duration>,
+}
+
+/// Комната с одной партией
+#[derive(Clone)]
+pub struct Room {
+    code: String,
+    game: TicTacToe,
+    seats: [Option<Seat>; 2],
+    /// Подключения зрителей
+    spectators: HashSet<ConnectionId>,
+    time_control: Option<TimeControl>,
+    /// Партия из лобби: результат меняет рейтинги
+    rated: bool,
+    /// Часы партии (`None` - без контроля времени)
+    clock: Option<Clock>,
+    audit: AuditLog,
+}
+
+impl Room {
+    fn new(
+        code: String,
+        rules: Rules,
+        time_control: Option<TimeControl>,
+        rated: bool,
+        time: &Arc<dyn TimeSource>,
+    ) -> Self {
+        let mut audit = AuditLog::new(&code);
+        audit.push(None, AuditEvent::Created { rules, time_control, rated });
+        Self {
+            code,
+            game: TicTacToe::with_rules(rules),
+            seats: [None, None],
+            spectators: HashSet::new(),
+            time_control,
+            rated,
+            clock: time_control.map(|control| Clock::new(control, Arc::clone(time))),
+            audit,
+        }
+    }
+
+    /// Код комнаты
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    /// Партия в комнате
+    pub fn game(&self) -> &TicTacToe {
+        &self.game
+    }
+
+    /// Состояние для рассылки клиентам
+    pub fn state(&self) -> RoomState {
+        RoomState {
+            code: self.code.clone(),
+            rules: self.game.rules(),
+            players: self.seats.clone().map(|seat| seat.map(|seat| seat.name)),
+            connected: self.seats.each_ref().map(|seat| seat.as_ref().is_some_and(|seat| seat.connection.is_some())),
+            moves: self.game.moves().to_vec(),
+            time_control: self.time_control,
+            spectators: self.spectators.len(),
+            clock: self.clock.as_ref().map(Clock::state),
+            termination: self.game.termination(),
+            draw_offer: self.game.draw_offer(),
+        }
+    }
+
+    /// Заняты ли оба места
+    fn is_full(&self) -> bool {
+        self.seats.iter().all(Option::is_some)
+    }
+
+    /// Сверяет часы с партией, когда оба игрока на местах
+    ///
+    /// ## Возвращает
+    /// `true`, если у игрока только что истекло время
+    fn tick_clock(&mut self) -> bool {
+        if !self.is_full() {
+            return false;
+        }
+        let Some(clock) = &mut self.clock else {
+            return false;
+        };
+        if !clock.tick(&mut self.game) {
+            return false;
+        }
+        if let Some(termination) = self.game.termination() {
+            self.audit.push(None, AuditEvent::Terminated { termination });
+        }
+        true
+    }
+
+    /// Засчитывает уход игроку, который не вернулся вовремя
+    ///
+    /// Если не вернулись оба, уход засчитывается тому, кто отключился первым.
+    ///
+    /// ## Возвращает
+    /// `true`, если партия только что закончилась
+    fn check_abandoned(&mut self, now: Duration) -> bool {
+        if self.game.is_game_over() || !self.is_full() {
+            return false;
+        }
+        let gone = [Player::X, Player::O]
+            .into_iter()
+            .filter_map(|player| {
+                let at = self.seats[seat_index(player)].as_ref()?.disconnected_at?;
+                (now.saturating_sub(at) >= ABANDON_TIMEOUT).then_some((at, player))
+            })
+            .min_by_key(|&(at, _)| at);
+        gone.is_some_and(|(_, player)| self.walk_out(None, player))
+    }
+
+    /// Можно ли удалить комнату: игроков нет, а партия закончена или не началась
+    fn is_idle(&self) -> bool {
+        self.connections().next().is_none() && (self.game.is_game_over() || !self.is_full())
+    }
+
+    /// Завершает партию, которую покинул игрок
+    ///
+    /// До своего первого хода игрок просто прерывает партию, после - проигрывает.
+    fn walk_out(&mut self, connection: Option<ConnectionId>, player: Player) -> bool {
+        let termination = if self.game.can_abort(player) {
+            Termination::Aborted
+        } else {
+            Termination::Abandonment(player)
+        };
+        self.terminate(connection, termination)
+    }
+
+    /// Завершает партию и записывает причину в журнал
+    fn terminate(&mut self, connection: Option<ConnectionId>, termination: Termination) -> bool {
+        if !self.game.terminate(termination) {
+            return false;
+        }
+        self.audit.push(connection, AuditEvent::Terminated { termination });
+        true
+    }
+
+    /// Число зрителей
+    pub fn spectator_count(&self) -> usize {
+        self.spectators.len()
+    }
+
+    /// Журнал событий комнаты
+    pub fn audit(&self) -> &AuditLog {
+        &self.audit
+    }
+
+    /// Каноническая запись партии
+    pub fn record(&self) -> GameRecord {
+        GameRecord::from_game(&self.game, self.name(Player::X), self.name(Player::O))
+    }
+
+    /// Проверяет ход игрока против состояния партии
+    ///
+    /// ## Возвращает
+    /// Причину отказа, если ход нельзя принять
+    fn check_move(&self, player: Player, position: usize, seq: usize) -> Result<(), String> {
+        if !self.is_full() {
+            return Err("ждем соперника".to_string());
+        }
+        if self.game.is_game_over() {
+            return Err("партия закончена".to_string());
+        }
+        if seq != self.game.moves().len() {
+            return Err(format!("ход номер {} уже сделан или устарел", seq + 1));
+        }
+        if self.game.current_player() != player {
+            return Err("сейчас ход соперника".to_string());
+        }
+        if !self.game.legal_moves().contains(&position) {
+            return Err(format!("ход {} невозможен", position));
+        }
+        Ok(())
+    }
+    /// Имя игрока на месте
+    fn name(&self, player: Player) -> &str {
+        self.seats[seat_index(player)].as_ref().map_or("", |seat| seat.name.as_str())
+    }
+
+    /// Подключения игроков комнаты
+    fn connections(&self) -> impl Iterator<Item = ConnectionId> + '_ {
+        self.seats.iter().flatten().filter_map(|seat| seat.connection)
+    }
+
+    /// Рассылает состояние всем подключенным игрокам и зрителям
+    fn broadcast(&self, outbox: &mut Outbox) {
+        let state = self.state();
+        for connection in self.connections().chain(self.spectators.iter().copied()) {
+            outbox.push((connection, ServerMessage::State(state.clone())));
+        }
+    }
+}
+
+/// Логика сервера без сетевого транспорта
+///
+/// Принимает разобранные сообщения клиентов и возвращает ответы,
+/// поэтому ее можно проверять без сокетов.
+pub struct GameServer {
+    rooms: HashMap<String, Room>,
+    /// Комната и сторона каждого подключения
+    sessions: HashMap<ConnectionId, (String, Player)>,
+    /// Комната, за которой наблюдает каждый зритель
+    watching: HashMap<ConnectionId, String>,
+    lobby: Lobby,
+    /// Источник времени для часов партий
+    time: Arc<dyn TimeSource>,
+    rng: u64,
+}
+
+impl GameServer {
+    /// Создает сервер со случайными кодами комнат
+    pub fn new() -> Self {
+        Self::with_seed(RandomState::new().build_hasher().finish())
+    }
+
+    /// Создает сервер с воспроизводимыми кодами комнат (для тестов)
+    ///
+    /// Ключи мест все равно случайные: они не зависят от зерна.
+    pub fn with_seed(seed: u64) -> Self {
+        Self {
+            rooms: HashMap::new(),
+            sessions: HashMap::new(),
+            watching: HashMap::new(),
+            lobby: Lobby::new(Box::new(MemoryStore::default())),
+            time: Arc::new(RealTime::default()),
+            rng: seed,
+        }
+    }
+
+    /// Заменяет источник времени для часов (для тестов)
+    pub fn with_time_source(mut self, time: Arc<dyn TimeSource>) -> Self {
+        self.time = time;
+        self
+    }
+
+    /// Заменяет хранилище рейтингов и партий
+    pub fn with_store(mut self, store: Box<dyn LobbyStore>) -> Self {
+        self.lobby = Lobby::new(store);
+        self
+    }
+
+    /// Лобби сервера
+    pub fn lobby(&self) -> &Lobby {
+        &self.lobby
+    }
+
+    /// Комната по коду
+    pub fn room(&self, code: &str) -> Option<&Room> {
+        self.rooms.get(code)
+    }
+
+    /// Количество комнат
+    pub fn room_count(&self) -> usize {
+        self.rooms.len()
+    }
+
+    /// Обрабатывает сообщение клиента
+    ///
+    /// ## Возвращает
+    /// Сообщения для отправки (ответ клиенту и рассылку соперникам)
+    pub fn handle(&mut self, connection: ConnectionId, message: ClientMessage) -> Outbox {
+        let mut outbox = Vec::new();
+        let result = match message {
+            ClientMessage::Create { name, rules, time_control } => {
+                self.create(connection, name, rules, time_control, &mut outbox)
+            }
+            ClientMessage::Join { code, name } => self.join(connection, &code, name, &mut outbox),
+            ClientMessage::Rejoin { code, token } => self.rejoin(connection, &code, &token, &mut outbox),
+            ClientMessage::Move { position, seq } => self.make_move(connection, position, seq, &mut outbox),
+            ClientMessage::Resign => self.game_action(connection, TicTacToe::resign, &mut outbox),
+            ClientMessage::OfferDraw => self.game_action(connection, TicTacToe::offer_draw, &mut outbox),
+            ClientMessage::AcceptDraw => self.game_action(connection, TicTacToe::accept_draw, &mut outbox),
+            ClientMessage::DeclineDraw => self.game_action(connection, TicTacToe::decline_draw, &mut outbox),
+            ClientMessage::Abort => self.game_action(connection, TicTacToe::abort, &mut outbox),
+            ClientMessage::Leave => {
+                self.leave(connection, &mut outbox);
+                outbox.extend(self.disconnect(connection));
+                Ok(())
+            }
+            ClientMessage::Lobby => {
+                self.lobby.subscribe(connection);
+                outbox.push((connection, ServerMessage::Lobby { seeks: self.lobby.info() }));
+                Ok(())
+            }
+            ClientMessage::Seek { name, rules, time_control } => self.seek(connection, name, rules, time_control, &mut outbox),
+            ClientMessage::CancelSeek => {
+                self.leave_lobby_queue(connection, &mut outbox);
+                Ok(())
+            }
+            ClientMessage::Accept { id, name } => self.accept(connection, id, name, &mut outbox),
+            ClientMessage::Watch { code } => self.watch(connection, &code, &mut outbox),
+        };
+        if let Err(reason) = result {
+            outbox.push((connection, ServerMessage::Error { reason }));
+        }
+        outbox
+    }
+
+    /// Проверяет часы всех партий и отключившихся игроков
+    ///
+    /// Транспорт вызывает этот метод периодически, чтобы партия заканчивалась
+    /// по времени или уходу, даже если игрок не отвечает.
+    pub fn tick(&mut self) -> Outbox {
+        let mut outbox = Vec::new();
+        let now = self.time.now();
+        let mut finished = Vec::new();
+        for (code, room) in self.rooms.iter_mut() {
+            if room.tick_clock() || room.check_abandoned(now) {
+                room.broadcast(&mut outbox);
+                finish_game(&mut self.lobby, room);
+                finished.push(code.clone());
+            }
+        }
+        // Партия, которую оба игрока покинули, больше никому не нужна
+        for code in finished {
+            self.remove_if_idle(&code);
+        }
+        outbox
+    }
+
+    /// Отмечает, что клиент отключился
+    ///
+    /// Место остается за игроком до конца партии, чтобы он мог вернуться.
+    pub fn disconnect(&mut self, connection: ConnectionId) -> Outbox {
+        let mut outbox = Vec::new();
+        if self.lobby.remove_connection(connection) {
+            self.broadcast_lobby(&mut outbox);
+        }
+        if let Some(code) = self.watching.remove(&connection) {
+            if let Some(room) = self.rooms.get_mut(&code) {
+                room.spectators.remove(&connection);
+                room.broadcast(&mut outbox);
+            }
+        }
+        let Some((code, player)) = self.sessions.remove(&connection) else {
+            return outbox;
+        };
+        let Some(room) = self.rooms.get_mut(&code) else {
+            return outbox;
+        };
+        if let Some(seat) = &mut room.seats[seat_index(player)] {
+            seat.connection = None;
+            seat.disconnected_at = Some(self.time.now());
+        }
+        room.audit.push(Some(connection), AuditEvent::Disconnected { player });
+        if !self.remove_if_idle(&code) {
+            if let Some(room) = self.rooms.get(&code) {
+                room.broadcast(&mut outbox);
+            }
+        }
+        outbox
+    }
+
+    /// Удаляет комнату без игроков вместе с записями ее зрителей
+    ///
+    /// ## Возвращает
+    /// `true`, если комната удалена
+    fn remove_if_idle(&mut self, code: &str) -> bool {
+        if !self.rooms.get(code).is_some_and(Room::is_idle) {
+            return false;
+        }
+        if let Some(room) = self.rooms.remove(code) {
+            for spectator in &room.spectators {
+                self.watching.remove(spectator);
+            }
+        }
+        true
+    }
+
+    fn create(
+        &mut self,
+        connection: ConnectionId,
+        name: String,
+        rules: Rules,
+        time_control: Option<TimeControl>,
+        outbox: &mut Outbox,
+    ) -> Result<(), String> {
+        self.ensure_free(connection)?;
+        let rules = rules.validate()?;
+        self.leave_lobby_queue(connection, outbox);
+        self.start_room(rules, time_control, false, &[(connection, name)], outbox);
+        Ok(())
+    }
+
+    fn seek(
+        &mut self,
+        connection: ConnectionId,
+        name: String,
+        rules: Rules,
+        time_control: Option<TimeControl>,
+        outbox: &mut Outbox,
+    ) -> Result<(), String> {
+        self.ensure_free(connection)?;
+        let rules = rules.validate()?;
+        match self.lobby.add_seek(connection, &name, rules, time_control)? {
+            SeekOutcome::Posted(id) => outbox.push((connection, ServerMessage::Seeking { id })),
+            SeekOutcome::Paired(pairing) => self.start_pairing(pairing, outbox),
+        }
+        self.broadcast_lobby(outbox);
+        Ok(())
+    }
+
+    fn accept(&mut self, connection: ConnectionId, id: u64, name: String, outbox: &mut Outbox) -> Result<(), String> {
+        self.ensure_free(connection)?;
+        let pairing = self.lobby.accept(id, connection, &name)?;
+        self.start_pairing(pairing, outbox);
+        self.broadcast_lobby(outbox);
+        Ok(())
+    }
+
+    /// Подключает зрителя к комнате и сразу отправляет ему текущую позицию
+    fn watch(&mut self, connection: ConnectionId, code: &str, outbox: &mut Outbox) -> Result<(), String> {
+        self.ensure_free(connection)?;
+        let code = code.trim().to_ascii_uppercase();
+        let room = self.rooms.get_mut(&code).ok_or("комната не найдена")?;
+        room.spectators.insert(connection);
+        self.watching.insert(connection, code.clone());
+        outbox.push((connection, ServerMessage::Watching { code }));
+        room.broadcast(outbox);
+        Ok(())
+    }
+
+    /// Начинает рейтинговую партию для пары из лобби
+    fn start_pairing(&mut self, pairing: Pairing, outbox: &mut Outbox) {
+        let Pairing { x, o } = pairing;
+        let players = [(x.connection, x.name), (o.connection, o.name)];
+        self.start_room(x.rules, x.time_control, true, &players, outbox);
+    }
+
+    /// Создает комнату и рассаживает игроков по порядку: X, затем O
+    fn start_room(
+        &mut self,
+        rules: Rules,
+        time_control: Option<TimeControl>,
+        rated: bool,
+        players: &[(ConnectionId, String)],
+        outbox: &mut Outbox,
+    ) {
+        let code = loop {
+            let code = self.next_code();
+            if !self.rooms.contains_key(&code) {
+                break code;
+            }
+        };
+        let mut room = Room::new(code.clone(), rules, time_control, rated, &self.time);
+        for ((connection, name), player) in players.iter().cloned().zip([Player::X, Player::O]) {
+            let token = new_token();
+            room.audit.push(Some(connection), AuditEvent::Seated { player, name: name.clone() });
+            let seat = Seat { name, token: token.clone(), connection: Some(connection), disconnected_at: None };
+            room.seats[seat_index(player)] = Some(seat);
+            self.sessions.insert(connection, (code.clone(), player));
+            outbox.push((connection, ServerMessage::Welcome { code: code.clone(), token, you_play: player }));
+        }
+        room.tick_clock();
+        room.broadcast(outbox);
+        self.rooms.insert(code, room);
+    }
+
+    fn join(&mut self, connection: ConnectionId, code: &str, name: String, outbox: &mut Outbox) -> Result<(), String> {
+        self.ensure_free(connection)?;
+        let code = code.trim().to_ascii_uppercase();
+        let token = new_token();
+        let room = self.rooms.get_mut(&code).ok_or("комната не найдена")?;
+        if room.seats[1].is_some() {
+            return Err("комната заполнена".to_string());
+        }
+        room.audit.push(Some(connection), AuditEvent::Seated { player: Player::O, name: name.clone() });
+        room.seats[1] = Some(Seat { name, token: token.clone(), connection: Some(connection), disconnected_at: None });
+        self.sessions.insert(connection, (code.clone(), Player::O));
+        outbox.push((connection, ServerMessage::Welcome { code, token, you_play: Player::O }));
+        room.tick_clock();
+        room.broadcast(outbox);
+        self.leave_lobby_queue(connection, outbox);
+        Ok(())
+    }
+
+    fn rejoin(&mut self, connection: ConnectionId, code: &str, token: &str, outbox: &mut Outbox) -> Result<(), String> {
+        self.ensure_free(connection)?;
+        let code = code.trim().to_ascii_uppercase();
+        let room = self.rooms.get_mut(&code).ok_or("комната не найдена")?;
+        let player = [Player::X, Player::O]
+            .into_iter()
+            .find(|&player| room.seats[seat_index(player)].as_ref().is_some_and(|seat| seat.token == token))
+            .ok_or("неверный ключ")?;
+
+        // Старое подключение (например, вкладка до перезагрузки) больше не управляет местом
+        let seat = room.seats[seat_index(player)].as_mut().unwrap();
+        if let Some(old) = seat.connection.replace(connection) {
+            self.sessions.remove(&old);
+        }
+        seat.disconnected_at = None;
+        self.sessions.insert(connection, (code.clone(), player));
+        room.audit.push(Some(connection), AuditEvent::Rejoined { player });
+        outbox.push((connection, ServerMessage::Welcome { code, token: token.to_string(), you_play: player }));
+        room.broadcast(outbox);
+        Ok(())
+    }
+
+    fn make_move(
+        &mut self,
+        connection: ConnectionId,
+        position: usize,
+        seq: usize,
+        outbox: &mut Outbox,
+    ) -> Result<(), String> {
+        let (code, player) = self.sessions.get(&connection).ok_or("вы не в комнате")?;
+        let player = *player;
+        let room = self.rooms.get_mut(code).ok_or("комната не найдена")?;
+        // Ход, пришедший после истечения времени, уже не принимается
+        if room.tick_clock() {
+            room.broadcast(outbox);
+            finish_game(&mut self.lobby, room);
+        }
+        if let Err(reason) = room.check_move(player, position, seq) {
+            let event = AuditEvent::Rejected { player, seq, position, reason: reason.clone() };
+            room.audit.push(Some(connection), event);
+            return Err(reason);
+        }
+        room.game.make_move(position);
+        room.audit.push(Some(connection), AuditEvent::Move { player, seq, position });
+        room.tick_clock();
+        room.broadcast(outbox);
+
+        if room.game.is_game_over() {
+            finish_game(&mut self.lobby, room);
+        }
+        Ok(())
+    }
+
+    /// Сдача, ничья или прерывание партии от имени игрока подключения
+    ///
+    /// ## Параметры
+    /// - `action`: действие над партией, например [`TicTacToe::resign`];
+    ///   возвращает `false`, если оно сейчас невозможно
+    fn game_action(
+        &mut self,
+        connection: ConnectionId,
+        action: fn(&mut TicTacToe, Player) -> bool,
+        outbox: &mut Outbox,
+    ) -> Result<(), String> {
+        let (code, player) = self.sessions.get(&connection).ok_or("вы не в комнате")?;
+        let player = *player;
+        let room = self.rooms.get_mut(code).ok_or("комната не найдена")?;
+        if room.tick_clock() {
+            room.broadcast(outbox);
+            finish_game(&mut self.lobby, room);
+        }
+        if !room.is_full() {
+            return Err("ждем соперника".to_string());
+        }
+        let offer = room.game.draw_offer();
+        if !action(&mut room.game, player) {
+            return Err("сейчас это невозможно".to_string());
+        }
+
+        // Действие либо закончило партию, либо изменило предложение ничьей
+        if let Some(termination) = room.game.termination() {
+            room.audit.push(Some(connection), AuditEvent::Terminated { termination });
+        } else if room.game.draw_offer().is_some() {
+            room.audit.push(Some(connection), AuditEvent::DrawOffered { player });
+        } else if offer.is_some() {
+            room.audit.push(Some(connection), AuditEvent::DrawDeclined { player });
+        }
+        room.broadcast(outbox);
+        if room.game.is_game_over() {
+            finish_game(&mut self.lobby, room);
+        }
+        Ok(())
+    }
+
+    /// Засчитывает уход игроку, который покидает начатую партию
+    fn leave(&mut self, connection: ConnectionId, outbox: &mut Outbox) {
+        let Some((code, player)) = self.sessions.get(&connection) else {
+            return;
+        };
+        let player = *player;
+        let Some(room) = self.rooms.get_mut(code) else {
+            return;
+        };
+        if room.is_full() && room.walk_out(Some(connection), player) {
+            room.broadcast(outbox);
+            finish_game(&mut self.lobby, room);
+        }
+    }
+
+    /// Снимает заявку игрока, который сел за партию другим способом
+    fn leave_lobby_queue(&mut self, connection: ConnectionId, outbox: &mut Outbox) {
+        if self.lobby.cancel(connection) {
+            self.broadcast_lobby(outbox);
+        }
+    }
+
+    /// Рассылает список заявок подписчикам лобби
+    fn broadcast_lobby(&self, outbox: &mut Outbox) {
+        let seeks = self.lobby.info();
+        for connection in self.lobby.subscribers() {
+            outbox.push((connection, ServerMessage::Lobby { seeks: seeks.clone() }));
+        }
+    }
+
+    /// Проверяет, что подключение еще не занимает место в комнате и не наблюдает
+    fn ensure_free(&self, connection: ConnectionId) -> Result<(), String> {
+        if self.sessions.contains_key(&connection) || self.watching.contains_key(&connection) {
+            return Err("вы уже в комнате".to_string());
+        }
+        Ok(())
+    }
+
+    /// Следующее псевдослучайное число (SplitMix64)
+    fn next_random(&mut self) -> u64 {
+        self.rng = self.rng.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.rng;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn next_code(&mut self) -> String {
+        let mut value = self.next_random();
+        (0..CODE_LEN)
+            .map(|_| {
+                let c = CODE_ALPHABET[(value % CODE_ALPHABET.len() as u64) as usize] as char;
+                value /= CODE_ALPHABET.len() as u64;
+                c
+            })
+            .collect()
+    }
+
+}
+
+/// Секретный ключ места из криптографического генератора системы
+///
+/// Коды комнат идут из SplitMix64, но его состояние можно восстановить по
+/// выходу, поэтому ключи от него не зависят: по своему ключу нельзя
+/// вычислить ключ соперника.
+fn new_token() -> String {
+    let mut bytes = [0u8; 16];
+    getrandom::getrandom(&mut bytes).expect("системный генератор случайных чисел недоступен");
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+impl Default for GameServer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Записывает конец партии в журнал и сохраняет результат рейтинговой партии
+///
+/// Перед изменением рейтингов журнал сверяется с записью партии;
+/// прерванная партия на рейтинги не влияет.
+fn finish_game(lobby: &mut Lobby, room: &mut Room) {
+    room.audit.push(None, AuditEvent::Finished { winner: room.game.get_winner() });
+    if !room.rated || room.game.termination() == Some(Termination::Aborted) {
+        return;
+    }
+    let record = room.record();
+    let problems = room.audit.verify(&record);
+    let saved = if problems.is_empty() {
+        lobby.record_result(&record).and_then(|_| lobby.save_audit(&room.audit))
+    } else {
+        Err(io::Error::new(io::ErrorKind::InvalidData, format!("{:?}", problems)))
+    };
+    if let Err(e) = saved {
+        eprintln!("Не удалось сохранить партию {}: {}", room.code, e);
+    }
+}
+
+/// Индекс места в комнате
+fn seat_index(player: Player) -> usize {
+    match player {
+        Player::X => 0,
+        Player::O => 1,
+    }
+}
+
+// === СЕТЕВОЙ ТРАНСПОРТ ===
+
+/// Общее состояние потоков подключений
+struct Hub {
+    server: GameServer,
+    clients: HashMap<ConnectionId, Sender<ServerMessage>>,
+    next_id: ConnectionId,
+}
+
+impl Hub {
+    fn dispatch(&self, outbox: Outbox) {
+        for (connection, message) in outbox {
+            if let Some(client) = self.clients.get(&connection) {
+                let _ = client.send(message);
+            }
+        }
+    }
+}
+
+/// Захватывает общее состояние, даже если другой поток упал, держа его
+///
+/// Одно сломанное подключение не должно останавливать весь сервер.
+fn lock(hub: &Mutex<Hub>) -> MutexGuard<'_, Hub> {
+    hub.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Запускает сервер на указанном адресе (блокирует поток)
+pub fn run(addr: impl ToSocketAddrs) -> io::Result<()> {
+    serve(TcpListener::bind(addr)?, GameServer::new())
+}
+
+/// Обслуживает WebSocket-подключения с уже открытого порта
+///
+/// Каждое подключение обрабатывается в своем потоке.
+pub fn serve(listener: TcpListener, server: GameServer) -> io::Result<()> {
+    let hub = Arc::new(Mutex::new(Hub { server, clients: HashMap::new(), next_id: 1 }));
+    let ticker = Arc::clone(&hub);
+    std::thread::spawn(move || loop {
+        std::thread::sleep(CLOCK_INTERVAL);
+        let mut hub = lock(&ticker);
+        let outbox = hub.server.tick();
+        hub.dispatch(outbox);
+    });
+    for stream in listener.incoming() {
+        let stream = stream?;
+        let hub = Arc::clone(&hub);
+        std::thread::spawn(move || handle_connection(stream, hub));
+    }
+    Ok(())
+}
+
+/// Обслуживает одно подключение до его закрытия
+fn handle_connection(stream: TcpStream, hub: Arc<Mutex<Hub>>) {
+    let Ok(mut socket) = tungstenite::accept(stream) else {
+        return;
+    };
+    if socket.get_ref().set_read_timeout(Some(POLL_INTERVAL)).is_err() {
+        return;
+    }
+
+    let (sender, outgoing) = mpsc::channel();
+    let id = {
+        let mut hub = lock(&hub);
+        let id = hub.next_id;
+        hub.next_id += 1;
+        hub.clients.insert(id, sender);
+        id
+    };
+
+    loop {
+        match socket.read() {
+            Ok(Message::Text(text)) => {
+                let mut hub = lock(&hub);
+                let outbox = match serde_json::from_str(&text) {
+                    Ok(message) => hub.server.handle(id, message),
+                    Err(e) => vec![(id, ServerMessage::Error { reason: format!("некорректное сообщение: {}", e) })],
+                };
+                hub.dispatch(outbox);
+            }
+            Ok(Message::Close(_)) => break,
+            Ok(_) => {}
+            Err(tungstenite::Error::Io(e))
+                if matches!(e.kind(), io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut) => {}
+            Err(_) => break,
+        }
+
+        let mut failed = false;
+        while let Ok(message) = outgoing.try_recv() {
+            let text = serde_json::to_string(&message).expect("сообщение сериализуется");
+            failed |= socket.send(Message::Text(text)).is_err();
+        }
+        if failed {
+            break;
+        }
+    }
+
+    let mut hub = lock(&hub);
+    hub.clients.remove(&id);
+    let outbox = hub.server.disconnect(id);
+    hub.dispatch(outbox);
+}
+
+// === ТЕСТЫ ===
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::ManualTime;
+
+    /// Ход с номером для текущей позиции комнаты
+    fn numbered(server: &GameServer, code: &str, position: usize) -> ClientMessage {
+        let seq = server.room(code).unwrap().game().moves().len();
+        ClientMessage::Move { position, seq }
+    }
+
+    fn create(time_control: Option<TimeControl>) -> ClientMessage {
+        ClientMessage::Create { name: "A".into(), rules: Rules::classic(), time_control }
+    }
+
+    fn welcome(outbox: &Outbox) -> (String, String) {
+        outbox
+            .iter()
+            .find_map(|(_, message)| match message {
+                ServerMessage::Welcome { code, token, .. } => Some((code.clone(), token.clone())),
+                _ => None,
+            })
+            .expect("нет приветствия")
+    }
+
+    /// Тест: создание комнаты, вход по коду и проверка ходов
+    #[test]
+    fn test_room_and_moves() {
+        let mut server = GameServer::with_seed(7);
+        let (code, _) = welcome(&server.handle(1, create(None)));
+        assert_eq!(code.len(), CODE_LEN);
+
+        // Пока нет соперника, ходить нельзя
+        let outbox = server.handle(1, numbered(&server, &code, 4));
+        assert!(matches!(outbox[0].1, ServerMessage::Error { .. }));
+
+        server.handle(2, ClientMessage::Join { code: code.to_lowercase(), name: "B".into() });
+        let outbox = server.handle(2, numbered(&server, &code, 4));
+        assert!(matches!(outbox[0].1, ServerMessage::Error { .. }), "ход не в свою очередь");
+
+        let outbox = server.handle(1, numbered(&server, &code, 4));
+        assert_eq!(outbox.len(), 2, "состояние получают оба игрока");
+        let outbox = server.handle(2, numbered(&server, &code, 4));
+        assert!(matches!(outbox[0].1, ServerMessage::Error { .. }), "занятая клетка");
+        assert_eq!(server.room(&code).unwrap().game().moves(), &[4]);
+
+        let outbox = server.handle(3, ClientMessage::Join { code, name: "C".into() });
+        assert!(matches!(outbox[0].1, ServerMessage::Error { .. }), "комната заполнена");
+    }
+
+    /// Тест: ключ места не выводится из состояния генератора кодов
+    #[test]
+    fn test_tokens_do_not_follow_code_generator() {
+        // Два сервера с одним зерном выдают одинаковые коды, но разные ключи:
+        // знание своего ключа и зерна не дает ключа соперника
+        let mut first = GameServer::with_seed(7);
+        let mut second = GameServer::with_seed(7);
+        let (code, alice) = welcome(&first.handle(1, create(None)));
+        let (same_code, other) = welcome(&second.handle(1, create(None)));
+        assert_eq!(code, same_code);
+        assert_ne!(alice, other);
+
+        let (_, bob) = welcome(&first.handle(2, ClientMessage::Join { code: code.clone(), name: "B".into() }));
+        let (_, guess) = welcome(&second.handle(2, ClientMessage::Join { code, name: "B".into() }));
+        assert_ne!(bob, guess);
+        assert_eq!(bob.len(), 32);
+    }
+
+    /// Тест: комната и заявка с недопустимыми правилами не создаются
+    #[test]
+    fn test_rejects_invalid_rules() {
+        let mut server = GameServer::with_seed(7);
+        let message = serde_json::from_str(r#"{"type":"create","name":"A","rules":{"size":4294967296,"win_length":5}}"#).unwrap();
+        let outbox = server.handle(1, message);
+        assert!(matches!(outbox[0].1, ServerMessage::Error { .. }));
+        assert_eq!(server.room_count(), 0);
+
+        let rules = Rules { size: 3, win_length: 4 };
+        let outbox = server.handle(1, ClientMessage::Create { name: "A".into(), rules, time_control: None });
+        assert!(matches!(outbox[0].1, ServerMessage::Error { .. }));
+        assert_eq!(server.room_count(), 0);
+
+        let outbox = server.handle(1, ClientMessage::Seek { name: "A".into(), rules, time_control: None });
+        assert!(matches!(outbox[0].1, ServerMessage::Error { .. }));
+        assert!(server.lobby().info().is_empty(), "заявка не попала в лобби");
+    }
+
+    /// Тест: сервер отклоняет ходы за соперника, повторные и запоздавшие ходы и пишет их в журнал
+    #[test]
+    fn test_rejects_forged_and_replayed_moves() {
+        let mut server = GameServer::with_seed(7);
+        let (code, _) = welcome(&server.handle(1, create(None)));
+        server.handle(2, ClientMessage::Join { code: code.clone(), name: "B".into() });
+
+        // Ход за соперника и ход от подключения вне комнаты
+        let forged = numbered(&server, &code, 0);
+        assert!(matches!(server.handle(2, forged.clone())[0].1, ServerMessage::Error { .. }));
+        assert!(matches!(server.handle(3, forged)[0].1, ServerMessage::Error { .. }));
+
+        let first = numbered(&server, &code, 0);
+        server.handle(1, first.clone());
+        // Повтор того же сообщения
+        assert!(matches!(server.handle(1, first)[0].1, ServerMessage::Error { .. }));
+
+        for (connection, position) in [(2, 3), (1, 1), (2, 4), (1, 2)] {
+            let message = numbered(&server, &code, position);
+            server.handle(connection, message);
+        }
+        let late = numbered(&server, &code, 5);
+        assert!(matches!(server.handle(2, late)[0].1, ServerMessage::Error { .. }), "партия закончена");
+
+        let room = server.room(&code).unwrap();
+        assert_eq!(room.game().get_winner(), Some(Player::X));
+        assert_eq!(room.audit().moves(), vec![0, 3, 1, 4, 2]);
+        assert_eq!(room.audit().rejected_count(), 3);
+        assert!(room.audit().verify(&room.record()).is_empty());
+    }
+
+    /// Тест: сервер ведет часы и засчитывает поражение по времени
+    #[test]
+    fn test_clock_timeout() {
+        let time = ManualTime::default();
+        let mut server = GameServer::with_seed(7).with_time_source(Arc::new(time.clone()));
+        let (code, _) = welcome(&server.handle(1, create(Some(TimeControl::fischer(10, 2)))));
+        server.handle(2, ClientMessage::Join { code: code.clone(), name: "B".into() });
+
+        time.advance(Duration::from_secs(3));
+        server.handle(1, numbered(&server, &code, 4));
+        let clock = server.room(&code).unwrap().state().clock.unwrap();
+        assert_eq!(clock.remaining_ms, [9000, 10000]);
+        assert_eq!(clock.running, Some(Player::O));
+
+        time.advance(Duration::from_secs(10));
+        assert_eq!(server.tick().len(), 2, "оба игрока узнают о конце партии");
+        let late = numbered(&server, &code, 0);
+        assert!(matches!(server.handle(2, late)[0].1, ServerMessage::Error { .. }));
+
+        let room = server.room(&code).unwrap();
+        assert_eq!(room.game().termination(), Some(Termination::Timeout(Player::O)));
+        assert_eq!(room.game().get_winner(), Some(Player::X));
+        assert!(room.audit().verify(&room.record()).is_empty());
+    }
+
+    /// Тест: ничья по соглашению, сдача и уход из партии
+    #[test]
+    fn test_draw_resign_and_abandonment() {
+        let time = ManualTime::default();
+        let mut server = GameServer::with_seed(7).with_time_source(Arc::new(time.clone()));
+        let (code, _) = welcome(&server.handle(1, create(None)));
+        server.handle(2, ClientMessage::Join { code: code.clone(), name: "B".into() });
+
+        assert!(matches!(server.handle(2, ClientMessage::AcceptDraw)[0].1, ServerMessage::Error { .. }));
+        server.handle(1, ClientMessage::OfferDraw);
+        server.handle(2, ClientMessage::DeclineDraw);
+        server.handle(1, ClientMessage::OfferDraw);
+        let outbox = server.handle(2, ClientMessage::AcceptDraw);
+        match &outbox[0].1 {
+            ServerMessage::State(state) => assert_eq!(state.termination, Some(Termination::Agreement)),
+            other => panic!("ожидалось состояние, получено {:?}", other),
+        }
+        let room = server.room(&code).unwrap();
+        assert!(room.audit().entries.iter().any(|entry| entry.event == AuditEvent::DrawDeclined { player: Player::O }));
+        assert!(room.audit().verify(&room.record()).is_empty());
+
+        // Сдача и уход засчитываются только после начала партии
+        let (code, _) = welcome(&server.handle(3, create(None)));
+        server.handle(4, ClientMessage::Join { code: code.clone(), name: "B".into() });
+        server.handle(3, numbered(&server, &code, 4));
+        assert!(matches!(server.handle(3, ClientMessage::Abort)[0].1, ServerMessage::Error { .. }));
+        server.handle(3, ClientMessage::Resign);
+        assert_eq!(server.room(&code).unwrap().game().get_winner(), Some(Player::O));
+
+        let (code, _) = welcome(&server.handle(5, create(None)));
+        server.handle(6, ClientMessage::Join { code: code.clone(), name: "B".into() });
+        server.handle(5, numbered(&server, &code, 4));
+        server.disconnect(5);
+        time.advance(ABANDON_TIMEOUT);
+        assert!(!server.tick().is_empty());
+        let room = server.room(&code).unwrap();
+        assert_eq!(room.game().termination(), Some(Termination::Abandonment(Player::X)));
+        assert_eq!(room.game().get_winner(), Some(Player::O));
+    }
+
+    /// Тест: покинутая обоими партия засчитывается первому ушедшему, а комната удаляется
+    #[test]
+    fn test_both_players_leave() {
+        let time = ManualTime::default();
+        let mut server = GameServer::with_seed(7).with_time_source(Arc::new(time.clone()));
+        let (code, _) = welcome(&server.handle(1, create(None)));
+        server.handle(2, ClientMessage::Join { code: code.clone(), name: "B".into() });
+        server.handle(3, ClientMessage::Watch { code: code.clone() });
+        server.handle(1, numbered(&server, &code, 4));
+        server.handle(2, numbered(&server, &code, 0));
+
+        server.disconnect(2);
+        time.advance(Duration::from_secs(1));
+        server.disconnect(1);
+        time.advance(ABANDON_TIMEOUT);
+        let outbox = server.tick();
+        match &outbox[0] {
+            (3, ServerMessage::State(state)) => {
+                assert_eq!(state.termination, Some(Termination::Abandonment(Player::O)));
+            }
+            other => panic!("ожидалось состояние для зрителя, получено {:?}", other),
+        }
+        assert_eq!(server.room_count(), 0);
+        assert!(matches!(server.handle(3, create(None))[0].1, ServerMessage::Welcome { .. }), "зритель свободен");
+    }
+
+    /// Тест: возвращение в партию по ключу после отключения
+    #[test]
+    fn test_rejoin_after_disconnect() {
+        let mut server = GameServer::with_seed(7);
+        let (code, alice) = welcome(&server.handle(1, create(None)));
+        server.handle(2, ClientMessage::Join { code: code.clone(), name: "B".into() });
+        server.handle(1, numbered(&server, &code, 0));
+
+        let outbox = server.disconnect(1);
+        match &outbox[0].1 {
+            ServerMessage::State(state) => assert_eq!(state.connected, [false, true]),
+            other => panic!("ожидалось состояние, получено {:?}", other),
+        }
+
+        let outbox = server.handle(5, ClientMessage::Rejoin { code: code.clone(), token: "bad".into() });
+        assert!(matches!(outbox[0].1, ServerMessage::Error { .. }));
+
+        let outbox = server.handle(5, ClientMessage::Rejoin { code: format!(" {} ", code.to_lowercase()), token: alice.clone() });
+        assert!(matches!(outbox[0].1, ServerMessage::Welcome { you_play: Player::X, .. }));
+        server.handle(2, numbered(&server, &code, 4));
+        let outbox = server.handle(5, numbered(&server, &code, 8));
+        match &outbox[0].1 {
+            ServerMessage::State(state) => assert_eq!(state.moves, vec![0, 4, 8]),
+            other => panic!("ожидалось состояние, получено {:?}", other),
+        }
+    }
+
+    /// Тест: зритель получает позицию и ходы, но не может ходить
+    #[test]
+    fn test_spectator() {
+        let mut server = GameServer::with_seed(7);
+        let (code, _) = welcome(&server.handle(1, create(None)));
+        server.handle(2, ClientMessage::Join { code: code.clone(), name: "B".into() });
+        server.handle(1, numbered(&server, &code, 4));
+
+        let outbox = server.handle(9, ClientMessage::Watch { code: code.clone() });
+        assert!(matches!(outbox[0], (9, ServerMessage::Watching { .. })));
+        // Позиция зрителю и новое число зрителей игрокам
+        let states: Vec<_> = outbox
+            .iter()
+            .filter_map(|(connection, message)| match message {
+                ServerMessage::State(state) => Some((*connection, state.spectators, state.moves.clone())),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(states.len(), 3);
+        assert!(states.iter().all(|(_, spectators, moves)| *spectators == 1 && moves == &vec![4]));
+
+        let outbox = server.handle(9, numbered(&server, &code, 0));
+        assert!(matches!(outbox[0].1, ServerMessage::Error { .. }));
+
+        let outbox = server.handle(2, numbered(&server, &code, 0));
+        assert!(outbox.iter().any(|(connection, _)| *connection == 9), "ход виден зрителю");
+
+        let outbox = server.disconnect(9);
+        assert_eq!(outbox.len(), 2);
+        assert_eq!(server.room(&code).unwrap().spectator_count(), 0);
+    }
+
+    /// Тест: два клиента играют через настоящий WebSocket
+    #[test]
+    fn test_websocket_roundtrip() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || serve(listener, GameServer::with_seed(1)));
+
+        let url = format!("ws://{}", addr);
+        let send = |socket: &mut tungstenite::WebSocket<_>, message: &ClientMessage| {
+            socket.send(Message::Text(serde_json::to_string(message).unwrap())).unwrap();
+        };
+        let receive = |socket: &mut tungstenite::WebSocket<_>| -> ServerMessage {
+            loop {
+                if let Message::Text(text) = socket.read().unwrap() {
+                    return serde_json::from_str(&text).unwrap();
+                }
+            }
+        };
+
+        let (mut alice, _) = tungstenite::connect(url.as_str()).unwrap();
+        send(&mut alice, &ClientMessage::Create { name: "Alice".into(), rules: Rules::classic(), time_control: None });
+        let ServerMessage::Welcome { code, .. } = receive(&mut alice) else { panic!("нет приветствия") };
+        receive(&mut alice);
+
+        let (mut bob, _) = tungstenite::connect(url.as_str()).unwrap();
+        send(&mut bob, &ClientMessage::Join { code: code.clone(), name: "Bob".into() });
+        assert!(matches!(receive(&mut bob), ServerMessage::Welcome { you_play: Player::O, .. }));
+        receive(&mut bob);
+        receive(&mut alice);
+
+        send(&mut alice, &ClientMessage::Move { position: 4, seq: 0 });
+        match receive(&mut bob) {
+            ServerMessage::State(state) => {
+                assert_eq!(state.moves, vec![4]);
+                assert_eq!(state.players, [Some("Alice".to_string()), Some("Bob".to_string())]);
+            }
+            other => panic!("ожидалось состояние, получено {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,395 @@
+//! # Статистика сыгранных партий
+//!
+//! Каждая законченная партия сохраняется вместе с видами игроков (человек
+//! или движок ИИ) и временем начала и конца. По сохраненным партиям
+//! считаются победы, ничьи и поражения игрока: всего, против каждого
+//! соперника, по вариантам правил и по видам ИИ.
+//!
+//! Партии хранятся через [`StatsStore`]: в памяти ([`MemoryStatsStore`]) или
+//! в JSON-файле ([`JsonStatsStore`]), который переживает перезапуск программы.
+//! Прерванные партии сохраняются, но в счет не идут. По тем же партиям
+//! считаются рейтинги (см. [`crate::rating`]).
+//!
+//! ## Использование
+//! ```rust
+//! use rust_tic_tac_toe::TicTacToe;
+//! use rust_tic_tac_toe::agent::AgentKind;
+//! use rust_tic_tac_toe::stats::{MemoryStatsStore, Participant, Stats};
+//!
+//! let mut game = TicTacToe::new();
+//! for position in [0, 3, 1, 4, 2] {
+//!     game.make_move(position);
+//! }
+//!
+//! let mut stats = Stats::new(Box::new(MemoryStatsStore::default()));
+//! let alice = Participant::human("Alice");
+//! stats.record(&game, &alice, &Participant::ai(AgentKind::Mcts), 0)?;
+//! assert_eq!(stats.score("Alice").wins, 1);
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::agent::AgentKind;
+use crate::rating::{RatingSystem, Ratings};
+use crate::record::GameRecord;
+use crate::{Localization, MessageKey, Player, Rules, Termination, TicTacToe};
+
+/// Имя файла статистики в домашнем каталоге
+const STATS_FILE: &str = ".rust_tic_tac_toe_stats.json";
+
+/// Участник партии
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Participant {
+    /// Имя игрока или движка
+    pub name: String,
+    /// Вид агента (`None` - удаленный игрок или внешний движок)
+    pub kind: Option<AgentKind>,
+}
+
+impl Participant {
+    /// Локальный игрок
+    pub fn human(name: &str) -> Self {
+        Self { name: name.to_string(), kind: Some(AgentKind::Human) }
+    }
+
+    /// Встроенный ИИ; имя совпадает с названием движка в командной строке
+    pub fn ai(kind: AgentKind) -> Self {
+        Self { name: kind.id().to_string(), kind: Some(kind) }
+    }
+
+    /// Удаленный игрок или внешний движок
+    pub fn other(name: &str) -> Self {
+        Self { name: name.to_string(), kind: None }
+    }
+}
+
+/// Сохраненная партия
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StoredGame {
+    /// Запись партии: правила, имена, ходы и результат
+    pub record: GameRecord,
+    /// Виды агентов сторон X и O
+    pub kinds: [Option<AgentKind>; 2],
+    /// Время начала в миллисекундах от начала эпохи Unix
+    pub started_ms: u64,
+    /// Время окончания в миллисекундах от начала эпохи Unix
+    pub finished_ms: u64,
+}
+
+impl StoredGame {
+    /// За какую сторону играл игрок с этим именем
+    ///
+    /// ## Возвращает
+    /// `None`, если игрок не участвовал или играл сам с собой
+    pub fn side_of(&self, name: &str) -> Option<Player> {
+        match (self.record.x == name, self.record.o == name) {
+            (true, false) => Some(Player::X),
+            (false, true) => Some(Player::O),
+            _ => None,
+        }
+    }
+
+    /// Прервана ли партия (такие партии в счет не идут)
+    pub fn is_aborted(&self) -> bool {
+        self.record.termination == Some(Termination::Aborted)
+    }
+}
+
+/// Победы, ничьи и поражения
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Score {
+    /// Победы
+    pub wins: u32,
+    /// Ничьи
+    pub draws: u32,
+    /// Поражения
+    pub losses: u32,
+}
+
+impl Score {
+    /// Число партий
+    pub fn total(&self) -> u32 {
+        self.wins + self.draws + self.losses
+    }
+
+    /// Добавляет результат партии с точки зрения игрока
+    fn add(&mut self, winner: Option<Player>, side: Player) {
+        match winner {
+            Some(player) if player == side => self.wins += 1,
+            Some(_) => self.losses += 1,
+            None => self.draws += 1,
+        }
+    }
+}
+
+impl fmt::Display for Score {
+    /// Формат `+3 =1 -2`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "+{} ={} -{}", self.wins, self.draws, self.losses)
+    }
+}
+
+// === ХРАНИЛИЩА ===
+
+/// Хранилище сыгранных партий
+pub trait StatsStore: Send {
+    /// Сохраняет партию
+    fn save(&mut self, game: &StoredGame) -> io::Result<()>;
+
+    /// Все сохраненные партии в порядке их окончания
+    fn games(&self) -> &[StoredGame];
+}
+
+/// Хранилище в памяти; статистика живет до закрытия программы
+#[derive(Default)]
+pub struct MemoryStatsStore {
+    games: Vec<StoredGame>,
+}
+
+impl StatsStore for MemoryStatsStore {
+    fn save(&mut self, game: &StoredGame) -> io::Result<()> {
+        self.games.push(game.clone());
+        Ok(())
+    }
+
+    fn games(&self) -> &[StoredGame] {
+        &self.games
+    }
+}
+
+/// Хранилище в JSON-файле, который перезаписывается после каждой партии
+pub struct JsonStatsStore {
+    path: PathBuf,
+    games: Vec<StoredGame>,
+}
+
+impl JsonStatsStore {
+    /// Открывает файл статистики или начинает новый, если файла нет
+    pub fn open(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let games = match std::fs::read_to_string(&path) {
+            Ok(text) => serde_json::from_str(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e),
+        };
+        Ok(Self { path, games })
+    }
+
+    /// Открывает файл статистики в домашнем каталоге пользователя
+    ///
+    /// Если домашний каталог неизвестен, файл создается в текущем каталоге.
+    pub fn open_default() -> io::Result<Self> {
+        let home = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"));
+        Self::open(home.map(PathBuf::from).unwrap_or_default().join(STATS_FILE))
+    }
+}
+
+impl StatsStore for JsonStatsStore {
+    fn save(&mut self, game: &StoredGame) -> io::Result<()> {
+        self.games.push(game.clone());
+        let text = serde_json::to_string_pretty(&self.games).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        std::fs::write(&self.path, text)
+    }
+
+    fn games(&self) -> &[StoredGame] {
+        &self.games
+    }
+}
+
+// === ЗАПРОСЫ ===
+
+/// Статистика поверх хранилища партий
+pub struct Stats {
+    store: Box<dyn StatsStore>,
+}
+
+impl Stats {
+    /// Создает статистику с указанным хранилищем
+    pub fn new(store: Box<dyn StatsStore>) -> Self {
+        Self { store }
+    }
+
+    /// Все сохраненные партии
+    pub fn games(&self) -> &[StoredGame] {
+        self.store.games()
+    }
+
+    /// Сохраняет законченную партию
+    ///
+    /// ## Параметры
+    /// - `game`: законченная партия (незаконченная не сохраняется)
+    /// - `x`, `o`: участники за стороны X и O
+    /// - `started_ms`: время начала партии, см. [`unix_time_ms`]
+    pub fn record(&mut self, game: &TicTacToe, x: &Participant, o: &Participant, started_ms: u64) -> io::Result<()> {
+        if !game.is_game_over() {
+            return Ok(());
+        }
+        self.store.save(&StoredGame {
+            record: GameRecord::from_game(game, &x.name, &o.name),
+            kinds: [x.kind, o.kind],
+            started_ms,
+            finished_ms: unix_time_ms(),
+        })
+    }
+
+    /// Общий счет игрока
+    pub fn score(&self, name: &str) -> Score {
+        self.score_by(name, |_, _| Some(())).remove(&()).unwrap_or_default()
+    }
+
+    /// Счет игрока против каждого соперника
+    pub fn by_opponent(&self, name: &str) -> BTreeMap<String, Score> {
+        self.score_by(name, |game, side| {
+            Some(match side {
+                Player::X => game.record.o.clone(),
+                Player::O => game.record.x.clone(),
+            })
+        })
+    }
+
+    /// Счет игрока по вариантам правил
+    pub fn by_variant(&self, name: &str) -> BTreeMap<Rules, Score> {
+        self.score_by(name, |game, _| Some(game.record.rules))
+    }
+
+    /// Счет игрока против каждого вида ИИ
+    pub fn by_ai(&self, name: &str) -> BTreeMap<AgentKind, Score> {
+        self.score_by(name, |game, side| {
+            game.kinds[opponent_index(side)].filter(|&kind| kind != AgentKind::Human)
+        })
+    }
+
+    /// Счет игрока, сгруппированный по ключу партии
+    ///
+    /// ## Параметры
+    /// - `key`: ключ группы по партии и стороне игрока (`None` - партия не учитывается)
+    pub fn score_by<K: Ord>(&self, name: &str, key: impl Fn(&StoredGame, Player) -> Option<K>) -> BTreeMap<K, Score> {
+        let mut scores = BTreeMap::new();
+        for game in self.games().iter().filter(|game| !game.is_aborted()) {
+            let Some(side) = game.side_of(name) else { continue };
+            if let Some(key) = key(game, side) {
+                scores.entry(key).or_insert_with(Score::default).add(game.record.winner, side);
+            }
+        }
+        scores
+    }
+
+    /// Рейтинги по всем сохраненным партиям; встроенные движки ИИ - якоря
+    pub fn ratings(&self, system: RatingSystem) -> Ratings {
+        Ratings::new(system).with_ai_anchors().from_records(self.games().iter().map(|game| &game.record))
+    }
+
+    /// Текстовый отчет для консоли на языке `localization`
+    ///
+    /// ## Пример вывода
+    /// ```text
+    /// Всего: +3 =1 -2
+    /// Рейтинг: 1532 ±120
+    /// Соперники:
+    ///   mcts: +1 =1 -2
+    /// Варианты:
+    ///   3x3/3: +3 =1 -2
+    /// ```
+    pub fn report(&self, name: &str, localization: &Localization) -> String {
+        let score = self.score(name).to_string();
+        let rating = self.ratings(RatingSystem::default()).rating(name).to_string();
+        let mut text = format!("{}\n", localization.format(MessageKey::StatsTotal, &[("score", score.into())]));
+        text.push_str(&format!("{}\n", localization.format(MessageKey::StatsRating, &[("rating", rating.into())])));
+        let sections = [
+            (MessageKey::StatsOpponents, self.by_opponent(name).into_iter().collect::<Vec<_>>()),
+            (MessageKey::StatsVariants, self.by_variant(name).into_iter().map(|(key, score)| (key.to_string(), score)).collect()),
+            (MessageKey::StatsAi, self.by_ai(name).into_iter().map(|(key, score)| (key.id().to_string(), score)).collect()),
+        ];
+        for (title, rows) in sections.iter().filter(|(_, rows)| !rows.is_empty()) {
+            text.push_str(&format!("{}:\n", localization.get_text(*title)));
+            for (key, score) in rows {
+                text.push_str(&format!("  {}: {}\n", key, score));
+            }
+        }
+        text
+    }
+}
+
+/// Текущее время в миллисекундах от начала эпохи Unix
+pub fn unix_time_ms() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |time| time.as_millis() as u64)
+}
+
+fn opponent_index(side: Player) -> usize {
+    match side {
+        Player::X => 1,
+        Player::O => 0,
+    }
+}
+
+// === ТЕСТЫ ===
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Language;
+
+    fn played(moves: &[usize], rules: Rules) -> TicTacToe {
+        let mut game = TicTacToe::with_rules(rules);
+        for &position in moves {
+            game.make_move(position);
+        }
+        game
+    }
+
+    /// Тест: счет по соперникам, вариантам и видам ИИ
+    #[test]
+    fn test_queries() {
+        let mut stats = Stats::new(Box::new(MemoryStatsStore::default()));
+        let alice = Participant::human("Alice");
+        let mcts = Participant::ai(AgentKind::Mcts);
+
+        stats.record(&played(&[0, 3, 1, 4, 2], Rules::classic()), &alice, &mcts, 0).unwrap();
+        stats.record(&played(&[0, 3, 1, 4, 8, 5], Rules::classic()), &alice, &Participant::other("Bob"), 0).unwrap();
+        let mut agreed = played(&[4], Rules::new(4, 3));
+        agreed.offer_draw(Player::X);
+        agreed.accept_draw(Player::O);
+        stats.record(&agreed, &mcts, &alice, 0).unwrap();
+        let mut aborted = TicTacToe::new();
+        aborted.abort(Player::X);
+        stats.record(&aborted, &alice, &mcts, 0).unwrap();
+        // Незаконченная партия не сохраняется
+        stats.record(&played(&[4], Rules::classic()), &alice, &mcts, 0).unwrap();
+
+        assert_eq!(stats.games().len(), 4);
+        assert_eq!(stats.score("Alice"), Score { wins: 1, draws: 1, losses: 1 });
+        assert_eq!(stats.by_opponent("Alice")["Bob"], Score { wins: 0, draws: 0, losses: 1 });
+        assert_eq!(stats.by_variant("Alice")[&Rules::new(4, 3)].draws, 1);
+        assert_eq!(stats.by_ai("Alice")[&AgentKind::Mcts], Score { wins: 1, draws: 1, losses: 0 });
+        assert!(!stats.by_ai("Alice").contains_key(&AgentKind::Human));
+        let report = stats.report("Alice", &Localization::new(Language::English));
+        assert!(report.contains("mcts: +1 =1 -0"));
+        assert!(report.starts_with("Total: +1 =1 -1"));
+    }
+
+    /// Тест: партии сохраняются в файл и читаются после перезапуска
+    #[test]
+    fn test_persist_in_file() {
+        let path = std::env::temp_dir().join(format!("ttt_stats_{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let mut stats = Stats::new(Box::new(JsonStatsStore::open(&path).unwrap()));
+        let game = played(&[0, 3, 1, 4, 2], Rules::classic());
+        stats.record(&game, &Participant::human("Alice"), &Participant::ai(AgentKind::ThreatSpace), 5).unwrap();
+
+        let reopened = Stats::new(Box::new(JsonStatsStore::open(&path).unwrap()));
+        assert_eq!(reopened.games().len(), 1);
+        assert_eq!(reopened.games()[0].started_ms, 5);
+        assert!(reopened.games()[0].finished_ms >= 5);
+        assert_eq!(reopened.games()[0].record.moves, vec![0, 3, 1, 4, 2]);
+        assert_eq!(reopened.score("threat").losses, 1);
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,422 @@
+//! # Темы оформления
+//!
+//! Тема - это цвета поля и интерфейса, светлая или темная основа egui и
+//! узоры, по которым X и O различаются без цвета. Встроенные темы:
+//! - `dark` - темная (по умолчанию);
+//! - `light` - светлая;
+//! - `color-blind` - цвета Окабэ-Ито, различимые при дейтеранопии и протанопии;
+//! - `high-contrast` - черный фон и яркие символы.
+//!
+//! Вместо конкретной темы можно выбрать [`SYSTEM_THEME`]: тогда светлая или
+//! темная тема берется из настроек системы или браузера и меняется вместе
+//! с ними.
+//!
+//! ## Свои темы
+//! Файлы `*.toml` из каталога [`THEMES_ENV`] добавляются к встроенным темам;
+//! имя файла - имя темы. Тема наследует встроенную (`base`, по умолчанию
+//! одноименную или `dark`) и меняет только указанные цвета:
+//! ```toml
+//! base = "light"
+//! patterns = true
+//!
+//! [colors]
+//! x = "#0072b2"
+//! o = "#d55e00"
+//! ```
+
+use std::io;
+use std::path::Path;
+
+use eframe::egui;
+use serde::Deserialize;
+
+use crate::{Localization, MessageKey};
+
+/// Переменная окружения с каталогом своих тем `*.toml`
+pub const THEMES_ENV: &str = "TIC_TAC_TOE_THEMES";
+
+/// Ключ, под которым графические версии хранят выбранную тему
+pub const THEME_STORAGE_KEY: &str = "tic_tac_toe_theme";
+
+/// Выбор темы, которая следует за светлой или темной темой системы
+pub const SYSTEM_THEME: &str = "system";
+
+// === ЦВЕТА ===
+
+/// Цветовая схема интерфейса
+///
+/// По умолчанию - темная тема в стиле популярных редакторов кода с яркими
+/// акцентами для игровых элементов.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct GameColors {
+    /// Цвет фона игрового поля
+    pub background: egui::Color32,
+    /// Цвет сетки игрового поля
+    pub grid: egui::Color32,
+    /// Цвет символа X
+    pub x_color: egui::Color32,
+    /// Цвет символа O
+    pub o_color: egui::Color32,
+    /// Цвет выделения и заголовков
+    pub highlight: egui::Color32,
+    /// Цвет основного текста
+    pub text: egui::Color32,
+}
+
+impl Default for GameColors {
+    /// Создает цветовую схему по умолчанию
+    ///
+    /// Использует темную тему с яркими акцентами:
+    /// - Темно-серый фон
+    /// - Светло-серые линии сетки
+    /// - Красный для X
+    /// - Зеленый для O
+    /// - Оранжевый для выделения
+    fn default() -> Self {
+        Self {
+            background: egui::Color32::from_rgb(40, 44, 52),    // Темно-серый
+            grid: egui::Color32::from_rgb(68, 71, 90),          // Светло-серый
+            x_color: egui::Color32::from_rgb(255, 85, 85),      // Красный
+            o_color: egui::Color32::from_rgb(80, 250, 123),     // Зеленый
+            highlight: egui::Color32::from_rgb(255, 184, 108),  // Оранжевый
+            text: egui::Color32::from_rgb(248, 248, 242),       // Белый
+        }
+    }
+}
+
+// === ТЕМА ===
+
+/// Тема оформления
+#[derive(Clone, PartialEq, Debug)]
+pub struct Theme {
+    /// Имя темы: `dark`, `light` или имя файла своей темы
+    pub name: String,
+    /// Темная основа egui для кнопок и панелей
+    pub dark: bool,
+    /// Цвета поля и интерфейса
+    pub colors: GameColors,
+    /// Узоры на клетках: X и O различаются не только цветом
+    pub patterns: bool,
+}
+
+impl Theme {
+    /// Темная тема по умолчанию
+    pub fn dark() -> Self {
+        Self { name: "dark".to_string(), dark: true, colors: GameColors::default(), patterns: false }
+    }
+
+    /// Светлая тема
+    pub fn light() -> Self {
+        Self {
+            name: "light".to_string(),
+            dark: false,
+            colors: GameColors {
+                background: egui::Color32::from_rgb(250, 250, 247),
+                grid: egui::Color32::from_rgb(190, 192, 204),
+                x_color: egui::Color32::from_rgb(200, 40, 40),
+                o_color: egui::Color32::from_rgb(20, 140, 70),
+                highlight: egui::Color32::from_rgb(200, 100, 0),
+                text: egui::Color32::from_rgb(30, 30, 36),
+            },
+            patterns: false,
+        }
+    }
+
+    /// Тема для дальтоников: синий и оранжевый из палитры Окабэ-Ито
+    ///
+    /// Пары красный-зеленый при дейтеранопии и протанопии сливаются, а
+    /// голубой и оранжевый остаются разными. Узоры включены.
+    pub fn color_blind() -> Self {
+        Self {
+            name: "color-blind".to_string(),
+            dark: true,
+            colors: GameColors {
+                x_color: egui::Color32::from_rgb(86, 180, 233),    // Голубой
+                o_color: egui::Color32::from_rgb(230, 159, 0),     // Оранжевый
+                highlight: egui::Color32::from_rgb(240, 228, 66),  // Желтый
+                ..GameColors::default()
+            },
+            patterns: true,
+        }
+    }
+
+    /// Высококонтрастная тема: черный фон, белая сетка, желтый X и голубой O
+    pub fn high_contrast() -> Self {
+        Self {
+            name: "high-contrast".to_string(),
+            dark: true,
+            colors: GameColors {
+                background: egui::Color32::BLACK,
+                grid: egui::Color32::WHITE,
+                x_color: egui::Color32::from_rgb(255, 255, 0),
+                o_color: egui::Color32::from_rgb(0, 255, 255),
+                highlight: egui::Color32::from_rgb(255, 128, 255),
+                text: egui::Color32::WHITE,
+            },
+            patterns: true,
+        }
+    }
+
+    /// Встроенные темы в порядке показа
+    pub fn builtin() -> Vec<Theme> {
+        vec![Self::dark(), Self::light(), Self::color_blind(), Self::high_contrast()]
+    }
+
+    /// Разбирает свою тему из TOML
+    ///
+    /// ## Параметры
+    /// - `name`: имя темы
+    /// - `source`: текст файла; неизвестные ключи считаются ошибкой
+    ///
+    /// ## Возвращает
+    /// Тему или описание ошибки
+    ///
+    /// ## Пример
+    /// ```rust
+    /// use rust_tic_tac_toe::theme::Theme;
+    ///
+    /// let theme = Theme::from_toml("paper", "base = \"light\"\n[colors]\nx = \"#0072b2\"").unwrap();
+    /// assert!(!theme.dark);
+    /// assert_eq!(theme.colors.x_color.to_hex(), "#0072b2ff");
+    /// assert_eq!(theme.colors.o_color, Theme::light().colors.o_color);
+    /// ```
+    pub fn from_toml(name: &str, source: &str) -> Result<Theme, String> {
+        let file: ThemeFile = toml::from_str(source).map_err(|e| format!("{}: {}", name, e))?;
+        let builtin = Self::builtin();
+        // Файл с именем встроенной темы по умолчанию меняет ее саму
+        let default_base = if builtin.iter().any(|theme| theme.name == name) { name } else { "dark" };
+        let base = file.base.as_deref().unwrap_or(default_base);
+        let mut theme = builtin
+            .into_iter()
+            .find(|theme| theme.name == base)
+            .ok_or_else(|| format!("{}: неизвестная тема base = \"{}\"", name, base))?;
+        theme.name = name.to_string();
+        theme.dark = file.dark.unwrap_or(theme.dark);
+        theme.patterns = file.patterns.unwrap_or(theme.patterns);
+
+        let colors = &mut theme.colors;
+        let fields = [
+            (&file.colors.background, &mut colors.background),
+            (&file.colors.grid, &mut colors.grid),
+            (&file.colors.x, &mut colors.x_color),
+            (&file.colors.o, &mut colors.o_color),
+            (&file.colors.highlight, &mut colors.highlight),
+            (&file.colors.text, &mut colors.text),
+        ];
+        for (hex, color) in fields {
+            if let Some(hex) = hex {
+                *color = egui::Color32::from_hex(hex).map_err(|_| format!("{}: неверный цвет \"{}\"", name, hex))?;
+            }
+        }
+        Ok(theme)
+    }
+
+    /// Оформление egui для кнопок, полей и панелей этой темы
+    pub fn visuals(&self) -> egui::Visuals {
+        let mut visuals = if self.dark { egui::Visuals::dark() } else { egui::Visuals::light() };
+        visuals.override_text_color = Some(self.colors.text);
+        visuals.selection.stroke.color = self.colors.highlight;
+        visuals
+    }
+}
+
+/// Файл своей темы
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ThemeFile {
+    /// Встроенная тема, от которой берутся неуказанные цвета
+    base: Option<String>,
+    dark: Option<bool>,
+    patterns: Option<bool>,
+    #[serde(default)]
+    colors: ColorsFile,
+}
+
+/// Цвета своей темы в виде `#rrggbb` или `#rrggbbaa`
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+struct ColorsFile {
+    background: Option<String>,
+    grid: Option<String>,
+    x: Option<String>,
+    o: Option<String>,
+    highlight: Option<String>,
+    text: Option<String>,
+}
+
+// === НАБОР ТЕМ ===
+
+/// Встроенные и свои темы и выбранная среди них
+///
+/// ## Пример
+/// ```rust
+/// use rust_tic_tac_toe::theme::{Themes, SYSTEM_THEME};
+///
+/// let mut themes = Themes::new();
+/// assert_eq!(themes.current().name, "dark");
+/// assert!(themes.select(SYSTEM_THEME));
+/// themes.set_system_dark(false);
+/// assert_eq!(themes.current().name, "light");
+/// ```
+#[derive(Clone, Debug)]
+pub struct Themes {
+    /// Доступные темы: сначала встроенные, затем свои
+    themes: Vec<Theme>,
+    /// Имя выбранной темы или [`SYSTEM_THEME`]
+    selected: String,
+    /// Система сейчас в темной теме
+    system_dark: bool,
+}
+
+impl Default for Themes {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Themes {
+    /// Создает набор встроенных тем; выбрана темная
+    pub fn new() -> Self {
+        Self { themes: Theme::builtin(), selected: "dark".to_string(), system_dark: true }
+    }
+
+    /// Добавляет тему или заменяет тему с тем же именем
+    pub fn add(&mut self, theme: Theme) {
+        match self.themes.iter_mut().find(|known| known.name == theme.name) {
+            Some(known) => *known = theme,
+            None => self.themes.push(theme),
+        }
+    }
+
+    /// Загружает все файлы `*.toml` каталога; имя файла - имя темы
+    ///
+    /// ## Возвращает
+    /// Имена загруженных тем
+    pub fn load_dir(&mut self, dir: impl AsRef<Path>) -> io::Result<Vec<String>> {
+        let mut loaded = Vec::new();
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else { continue };
+            if path.extension().is_some_and(|extension| extension == "toml") {
+                let source = std::fs::read_to_string(&path)?;
+                let theme = Theme::from_toml(name, &source).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                self.add(theme);
+                loaded.push(name.to_string());
+            }
+        }
+        loaded.sort();
+        Ok(loaded)
+    }
+
+    /// Загружает темы из [`THEMES_ENV`], если переменная задана
+    pub fn load_env_dir(&mut self) -> io::Result<Vec<String>> {
+        match std::env::var(THEMES_ENV) {
+            Ok(dir) => self.load_dir(dir),
+            Err(_) => Ok(Vec::new()),
+        }
+    }
+
+    /// Варианты выбора: [`SYSTEM_THEME`] и имена всех тем
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(SYSTEM_THEME).chain(self.themes.iter().map(|theme| theme.name.as_str()))
+    }
+
+    /// Имя выбранной темы или [`SYSTEM_THEME`]
+    pub fn selected(&self) -> &str {
+        &self.selected
+    }
+
+    /// Выбирает тему по имени
+    ///
+    /// ## Возвращает
+    /// `false`, если такой темы нет; выбор тогда не меняется
+    pub fn select(&mut self, name: &str) -> bool {
+        let known = name == SYSTEM_THEME || self.themes.iter().any(|theme| theme.name == name);
+        if known {
+            self.selected = name.to_string();
+        }
+        known
+    }
+
+    /// Запоминает, темная ли сейчас тема системы
+    pub fn set_system_dark(&mut self, dark: bool) {
+        self.system_dark = dark;
+    }
+
+    /// Тема, которой сейчас рисуется интерфейс
+    pub fn current(&self) -> &Theme {
+        let name = match self.selected.as_str() {
+            SYSTEM_THEME if self.system_dark => "dark",
+            SYSTEM_THEME => "light",
+            name => name,
+        };
+        self.themes.iter().find(|theme| theme.name == name).unwrap_or(&self.themes[0])
+    }
+
+    /// Название варианта выбора для меню: встроенные темы переводятся
+    pub fn label(name: &str, localization: &Localization) -> String {
+        let key = match name {
+            SYSTEM_THEME => MessageKey::ThemeSystem,
+            "dark" => MessageKey::ThemeDark,
+            "light" => MessageKey::ThemeLight,
+            "color-blind" => MessageKey::ThemeColorBlind,
+            "high-contrast" => MessageKey::ThemeHighContrast,
+            name => return name.to_string(),
+        };
+        localization.get_text(key)
+    }
+}
+
+// === ТЕСТЫ ===
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Тест: своя тема наследует встроенную, а ошибки в файле не пропускаются
+    #[test]
+    fn test_from_toml() {
+        let theme = Theme::from_toml("mine", "patterns = true\n[colors]\nbackground = \"#102030\"").unwrap();
+        assert!(theme.dark && theme.patterns);
+        assert_eq!(theme.colors.background, egui::Color32::from_rgb(0x10, 0x20, 0x30));
+        assert_eq!(theme.colors.x_color, GameColors::default().x_color);
+
+        assert!(Theme::from_toml("bad", "[colors]\nx = \"red\"").is_err());
+        assert!(Theme::from_toml("bad", "base = \"sepia\"").is_err());
+        assert!(Theme::from_toml("bad", "[colors]\ncross = \"#ffffff\"").is_err(), "опечатка в ключе");
+    }
+
+    /// Тест: выбор темы, в том числе неизвестной и следующей за системой
+    #[test]
+    fn test_select() {
+        let mut themes = Themes::new();
+        assert!(!themes.select("sepia"));
+        assert_eq!(themes.selected(), "dark");
+        assert!(themes.select("high-contrast"));
+        themes.set_system_dark(false);
+        assert_eq!(themes.current().name, "high-contrast", "явный выбор не зависит от системы");
+
+        themes.select(SYSTEM_THEME);
+        assert_eq!(themes.current().name, "light");
+        themes.set_system_dark(true);
+        assert_eq!(themes.current().name, "dark");
+    }
+
+    /// Тест: темы загружаются из файлов и заменяют темы с тем же именем
+    #[test]
+    fn test_load_dir() {
+        let dir = std::env::temp_dir().join(format!("ttt_themes_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("paper.toml"), "base = \"light\"").unwrap();
+        std::fs::write(dir.join("light.toml"), "[colors]\ntext = \"#000000\"").unwrap();
+        std::fs::write(dir.join("readme.txt"), "не тема").unwrap();
+
+        let mut themes = Themes::new();
+        assert_eq!(themes.load_dir(&dir).unwrap(), vec!["light".to_string(), "paper".to_string()]);
+        assert_eq!(themes.names().count(), 6);
+        assert!(themes.select("light"));
+        assert_eq!(themes.current().colors.text, egui::Color32::BLACK);
+        assert!(!themes.current().dark, "файл `light.toml` меняет встроенную светлую тему");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,767 @@
+//! # Поиск по пространству угроз
+//!
+//! Движок для больших полей (например, 15x15 "пять в ряд"), где полный
+//! перебор невозможен. Работает в три этапа:
+//! 1. Распознавание угроз: пятерка, открытая четверка, четверка, открытая тройка
+//! 2. Поиск форсированного выигрыша: VCF (только четверками) и VCT (четверками и тройками)
+//! 3. Эвристический альфа-бета перебор с итеративным углублением и ограничением по времени
+//!
+//! ## Использование
+//! ```rust
+//! use rust_tic_tac_toe::{Rules, TicTacToe};
+//! use rust_tic_tac_toe::engine::Engine;
+//! use rust_tic_tac_toe::threat::ThreatSpaceEngine;
+//!
+//! let mut game = TicTacToe::with_rules(Rules::gomoku());
+//! let mut ai = ThreatSpaceEngine::default();
+//! let position = ai.choose_move(&game).unwrap();
+//! assert!(game.make_move(position));
+//! ```
+
+use std::time::Duration;
+use web_time::Instant;
+
+use crate::engine::{Engine, EngineInfo};
+use crate::{Player, TicTacToe, DIRECTIONS};
+
+/// Оценка выигранной позиции
+const WIN_SCORE: i64 = 1_000_000_000;
+
+/// Вид угрозы, которую создает ход
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Threat {
+    /// Ход завершает выигрышную линию
+    Five,
+    /// После хода есть две и более точки выигрыша - защититься нельзя
+    OpenFour,
+    /// После хода есть ровно одна точка выигрыша
+    Four,
+    /// Следующим ходом можно создать открытую четверку
+    OpenThree,
+}
+
+/// Настройки поиска
+#[derive(Clone, Debug)]
+pub struct ThreatSpaceConfig {
+    /// Максимальное число ходов атакующего в поиске VCF
+    pub vcf_depth: usize,
+    /// Максимальное число ходов атакующего в поиске VCT
+    pub vct_depth: usize,
+    /// Максимальная глубина альфа-бета перебора (в полуходах)
+    pub max_depth: usize,
+    /// Сколько лучших кандидатов рассматривать в каждом узле перебора
+    pub candidate_limit: usize,
+    /// Время на обдумывание одного хода
+    pub time_budget: Duration,
+}
+
+impl Default for ThreatSpaceConfig {
+    fn default() -> Self {
+        Self {
+            vcf_depth: 12,
+            vct_depth: 4,
+            max_depth: 10,
+            candidate_limit: 12,
+            time_budget: Duration::from_millis(1000),
+        }
+    }
+}
+
+/// Сведения о последнем поиске
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SearchInfo {
+    /// Глубина последней полностью просчитанной итерации
+    pub depth: usize,
+    /// Количество просмотренных узлов
+    pub nodes: u64,
+    /// Оценка выбранного хода с точки зрения ходящего
+    pub score: i64,
+    /// Найден ли форсированный выигрыш
+    pub forced_win: bool,
+}
+
+/// Движок с поиском по пространству угроз
+#[derive(Clone, Debug, Default)]
+pub struct ThreatSpaceEngine {
+    /// Настройки поиска
+    pub config: ThreatSpaceConfig,
+    /// Сведения о последнем поиске
+    info: SearchInfo,
+}
+
+impl ThreatSpaceEngine {
+    /// Создает движок с указанными настройками
+    pub fn new(config: ThreatSpaceConfig) -> Self {
+        Self { config, info: SearchInfo::default() }
+    }
+
+    /// Возвращает сведения о последнем поиске
+    pub fn last_search(&self) -> &SearchInfo {
+        &self.info
+    }
+
+    /// Ищет форсированный выигрыш сплошными четверками (VCF)
+    ///
+    /// Возвращает первый ход выигрывающей последовательности для текущего игрока.
+    pub fn find_vcf(&self, game: &TicTacToe) -> Option<usize> {
+        if game.is_game_over() {
+            return None;
+        }
+        let mut search = Search::new(game, &self.config);
+        search.vcf(game.current_player(), self.config.vcf_depth)
+    }
+
+    /// Ищет форсированный выигрыш четверками и открытыми тройками (VCT)
+    pub fn find_vct(&self, game: &TicTacToe) -> Option<usize> {
+        if game.is_game_over() {
+            return None;
+        }
+        let mut search = Search::new(game, &self.config);
+        search.vct(game.current_player(), self.config.vct_depth)
+    }
+}
+
+impl Engine for ThreatSpaceEngine {
+    fn name(&self) -> String {
+        "Threat-space".to_string()
+    }
+
+    fn set_move_time(&mut self, time: Duration) {
+        self.config.time_budget = time;
+    }
+
+    fn info(&self) -> Option<EngineInfo> {
+        Some(EngineInfo {
+            depth: self.info.depth,
+            nodes: self.info.nodes,
+            score: self.info.score,
+        })
+    }
+
+    fn choose_move(&mut self, game: &TicTacToe) -> Option<usize> {
+        if game.is_game_over() {
+            return None;
+        }
+
+        let me = game.current_player();
+        let mut search = Search::new(game, &self.config);
+        self.info = SearchInfo::default();
+
+        // Первый ход - в центр
+        if search.pos.cells.iter().all(|cell| cell.is_none()) {
+            return Some(search.pos.center());
+        }
+
+        // Выигрываем сразу
+        if let Some(&win) = search.pos.winning_points(me).first() {
+            self.info.forced_win = true;
+            self.info.score = WIN_SCORE;
+            return Some(win);
+        }
+
+        // Закрываем выигрыш соперника: любой другой ход, даже четверка, проигрывает
+        if let Some(&block) = search.pos.winning_points(me.opponent()).first() {
+            return Some(block);
+        }
+
+        // Форсированный выигрыш
+        let forced = search
+            .vcf(me, self.config.vcf_depth)
+            .or_else(|| search.vct(me, self.config.vct_depth));
+        if let Some(position) = forced {
+            self.info.forced_win = true;
+            self.info.score = WIN_SCORE;
+            self.info.nodes = search.nodes;
+            return Some(position);
+        }
+
+        // Эвристический перебор с итеративным углублением
+        let mut best = search.candidates(me).first().copied();
+        for depth in 1..=self.config.max_depth {
+            search.timed_out = false;
+            match search.root(me, depth) {
+                Some((position, score)) if !search.timed_out => {
+                    best = Some(position);
+                    self.info.depth = depth;
+                    self.info.score = score;
+                    if score.abs() >= WIN_SCORE - self.config.max_depth as i64 {
+                        break;
+                    }
+                }
+                _ => break,
+            }
+            if search.pos.empty_count() <= depth {
+                break;
+            }
+        }
+        self.info.nodes = search.nodes;
+
+        best
+    }
+}
+
+/// Определяет, какую угрозу создает ход текущего игрока в указанную клетку
+///
+/// ## Возвращает
+/// - `Some(threat)` - вид угрозы
+/// - `None` - ход не создает угроз или клетка занята
+pub fn classify_move(game: &TicTacToe, position: usize) -> Option<Threat> {
+    let mut pos = Position::from_game(game);
+    let player = game.current_player();
+    if position >= pos.cells.len() || pos.cells[position].is_some() {
+        return None;
+    }
+    if pos.completes_line(position, player) {
+        return Some(Threat::Five);
+    }
+
+    pos.cells[position] = Some(player);
+    let threat = match pos.winning_points_near(position, player).len() {
+        0 if !pos.open_four_points(position, player).is_empty() => Some(Threat::OpenThree),
+        0 => None,
+        1 => Some(Threat::Four),
+        _ => Some(Threat::OpenFour),
+    };
+    pos.cells[position] = None;
+
+    threat
+}
+
+/// Рабочая копия поля для поиска
+///
+/// Ходы ставятся и снимаются без проверки очередности,
+/// что позволяет свободно моделировать ответы атакующего и защитника.
+#[derive(Clone)]
+struct Position {
+    size: usize,
+    win_length: usize,
+    cells: Vec<Option<Player>>,
+}
+
+impl Position {
+    fn from_game(game: &TicTacToe) -> Self {
+        let rules = game.rules();
+        Self {
+            size: rules.size,
+            win_length: rules.win_length,
+            cells: game.get_board().to_vec(),
+        }
+    }
+
+    fn center(&self) -> usize {
+        (self.size / 2) * self.size + self.size / 2
+    }
+
+    fn empty_count(&self) -> usize {
+        self.cells.iter().filter(|cell| cell.is_none()).count()
+    }
+
+    /// Клетка, сдвинутая на `steps` шагов в направлении `(dr, dc)`
+    fn offset(&self, position: usize, (dr, dc): (isize, isize), steps: isize) -> Option<usize> {
+        let size = self.size as isize;
+        let row = (position / self.size) as isize + dr * steps;
+        let col = (position % self.size) as isize + dc * steps;
+        if row >= 0 && row < size && col >= 0 && col < size {
+            Some((row * size + col) as usize)
+        } else {
+            None
+        }
+    }
+
+    /// Длина линии игрока через клетку, если бы в ней стоял его символ
+    fn line_length(&self, position: usize, player: Player, direction: (isize, isize)) -> usize {
+        let mut length = 1;
+        for sign in [1, -1] {
+            let mut steps = 1;
+            while let Some(next) = self.offset(position, direction, sign * steps) {
+                if self.cells[next] != Some(player) {
+                    break;
+                }
+                length += 1;
+                steps += 1;
+            }
+        }
+        length
+    }
+
+    /// Завершает ли ход игрока в клетку выигрышную линию
+    fn completes_line(&self, position: usize, player: Player) -> bool {
+        DIRECTIONS
+            .iter()
+            .any(|&direction| self.line_length(position, player, direction) >= self.win_length)
+    }
+
+    /// Образовал ли символ в клетке выигрышную линию
+    fn completes_line_at(&self, position: usize, player: Player) -> bool {
+        self.cells[position] == Some(player) && self.completes_line(position, player)
+    }
+
+    /// Все точки выигрыша игрока на поле
+    fn winning_points(&self, player: Player) -> Vec<usize> {
+        (0..self.cells.len())
+            .filter(|&i| self.cells[i].is_none() && self.completes_line(i, player))
+            .collect()
+    }
+
+    /// Пустые клетки на линиях через `position` в пределах длины линии
+    fn line_cells(&self, position: usize) -> Vec<usize> {
+        let reach = self.win_length as isize - 1;
+        let mut cells = Vec::new();
+        for &direction in &DIRECTIONS {
+            for steps in -reach..=reach {
+                if let Some(cell) = self.offset(position, direction, steps) {
+                    if self.cells[cell].is_none() && !cells.contains(&cell) {
+                        cells.push(cell);
+                    }
+                }
+            }
+        }
+        cells
+    }
+
+    /// Точки выигрыша игрока на линиях через `position`
+    fn winning_points_near(&self, position: usize, player: Player) -> Vec<usize> {
+        self.line_cells(position)
+            .into_iter()
+            .filter(|&cell| self.completes_line(cell, player))
+            .collect()
+    }
+
+    /// Клетки на линиях через `position`, ход в которые дает открытую четверку
+    fn open_four_points(&mut self, position: usize, player: Player) -> Vec<usize> {
+        let mut points = Vec::new();
+        for cell in self.line_cells(position) {
+            self.cells[cell] = Some(player);
+            if self.winning_points_near(cell, player).len() >= 2 {
+                points.push(cell);
+            }
+            self.cells[cell] = None;
+        }
+        points
+    }
+
+    /// Есть ли свой символ на расстоянии не больше `distance` по одной из линий
+    fn has_neighbor(&self, position: usize, player: Option<Player>, distance: isize) -> bool {
+        DIRECTIONS.iter().any(|&direction| {
+            (1..=distance).any(|steps| {
+                [steps, -steps].iter().any(|&s| {
+                    self.offset(position, direction, s).is_some_and(|cell| match player {
+                        Some(p) => self.cells[cell] == Some(p),
+                        None => self.cells[cell].is_some(),
+                    })
+                })
+            })
+        })
+    }
+
+    /// Ходы игрока, создающие четверку (одну или несколько точек выигрыша)
+    fn four_moves(&mut self, player: Player) -> Vec<usize> {
+        let mut moves = Vec::new();
+        for cell in 0..self.cells.len() {
+            if self.cells[cell].is_some() || !self.has_neighbor(cell, Some(player), 2) {
+                continue;
+            }
+            self.cells[cell] = Some(player);
+            if !self.winning_points_near(cell, player).is_empty() {
+                moves.push(cell);
+            }
+            self.cells[cell] = None;
+        }
+        moves
+    }
+
+    /// Ходы игрока, создающие четверку или открытую тройку (четверки идут первыми)
+    fn threat_moves(&mut self, player: Player) -> Vec<usize> {
+        let mut fours = Vec::new();
+        let mut threes = Vec::new();
+        for cell in 0..self.cells.len() {
+            if self.cells[cell].is_some() || !self.has_neighbor(cell, Some(player), 2) {
+                continue;
+            }
+            self.cells[cell] = Some(player);
+            if !self.winning_points_near(cell, player).is_empty() {
+                fours.push(cell);
+            } else if !self.open_four_points(cell, player).is_empty() {
+                threes.push(cell);
+            }
+            self.cells[cell] = None;
+        }
+        fours.extend(threes);
+        fours
+    }
+
+    /// Вес окна с `count` символами одного игрока
+    fn window_weight(&self, count: usize) -> i64 {
+        if count == 0 {
+            0
+        } else if count >= self.win_length {
+            WIN_SCORE
+        } else {
+            1i64 << (3 * count)
+        }
+    }
+
+    /// Статическая оценка позиции с точки зрения игрока
+    ///
+    /// Суммирует все окна длиной в выигрышную линию, в которых
+    /// есть символы только одного игрока.
+    fn evaluate(&self, player: Player) -> i64 {
+        let mut score = 0;
+        for start in 0..self.cells.len() {
+            for &direction in &DIRECTIONS {
+                let Some(_) = self.offset(start, direction, self.win_length as isize - 1) else {
+                    continue;
+                };
+                let (mut mine, mut theirs) = (0, 0);
+                for steps in 0..self.win_length as isize {
+                    match self.offset(start, direction, steps).and_then(|cell| self.cells[cell]) {
+                        Some(p) if p == player => mine += 1,
+                        Some(_) => theirs += 1,
+                        None => {}
+                    }
+                }
+                if theirs == 0 {
+                    score += self.window_weight(mine);
+                } else if mine == 0 {
+                    score -= self.window_weight(theirs);
+                }
+            }
+        }
+        score
+    }
+
+    /// Оценка клетки для упорядочивания ходов: атака плюс защита
+    fn cell_priority(&self, position: usize, player: Player) -> i64 {
+        let reach = self.win_length as isize - 1;
+        let mut priority = 0;
+        for &direction in &DIRECTIONS {
+            for shift in -reach..=0 {
+                let mut counts = [0usize; 2];
+                let mut complete = true;
+                for steps in shift..=shift + reach {
+                    match self.offset(position, direction, steps) {
+                        None => {
+                            complete = false;
+                            break;
+                        }
+                        Some(cell) => match self.cells[cell] {
+                            Some(p) if p == player => counts[0] += 1,
+                            Some(_) => counts[1] += 1,
+                            None => {}
+                        },
+                    }
+                }
+                if !complete {
+                    continue;
+                }
+                if counts[1] == 0 {
+                    priority += self.window_weight(counts[0] + 1);
+                }
+                if counts[0] == 0 {
+                    priority += self.window_weight(counts[1] + 1);
+                }
+            }
+        }
+        priority
+    }
+}
+
+/// Состояние одного поиска
+struct Search<'a> {
+    pos: Position,
+    config: &'a ThreatSpaceConfig,
+    deadline: Instant,
+    nodes: u64,
+    timed_out: bool,
+}
+
+impl<'a> Search<'a> {
+    fn new(game: &TicTacToe, config: &'a ThreatSpaceConfig) -> Self {
+        Self {
+            pos: Position::from_game(game),
+            config,
+            deadline: Instant::now() + config.time_budget,
+            nodes: 0,
+            timed_out: false,
+        }
+    }
+
+    /// Учитывает очередной узел и проверяет, не истекло ли время
+    fn tick(&mut self) -> bool {
+        self.nodes += 1;
+        if self.nodes.is_multiple_of(256) && Instant::now() >= self.deadline {
+            self.timed_out = true;
+        }
+        self.timed_out
+    }
+
+    /// Поиск выигрыша сплошными четверками
+    fn vcf(&mut self, attacker: Player, depth: usize) -> Option<usize> {
+        if self.tick() {
+            return None;
+        }
+        if let Some(&win) = self.pos.winning_points(attacker).first() {
+            return Some(win);
+        }
+        if depth == 0 {
+            return None;
+        }
+
+        let defender = attacker.opponent();
+        let defender_wins = self.pos.winning_points(defender);
+        let candidates = match defender_wins.len() {
+            0 => self.pos.four_moves(attacker),
+            1 => defender_wins,
+            _ => return None,
+        };
+
+        for position in candidates {
+            self.pos.cells[position] = Some(attacker);
+            let wins = self.pos.winning_points_near(position, attacker);
+            let result = match wins.len() {
+                0 => false,
+                1 => {
+                    let block = wins[0];
+                    self.pos.cells[block] = Some(defender);
+                    let result = !self.pos.completes_line_at(block, defender)
+                        && self.vcf(attacker, depth - 1).is_some();
+                    self.pos.cells[block] = None;
+                    result
+                }
+                _ => true,
+            };
+            self.pos.cells[position] = None;
+            if result {
+                return Some(position);
+            }
+            if self.timed_out {
+                return None;
+            }
+        }
+
+        None
+    }
+
+    /// Поиск выигрыша четверками и открытыми тройками
+    fn vct(&mut self, attacker: Player, depth: usize) -> Option<usize> {
+        if self.tick() {
+            return None;
+        }
+        if let Some(&win) = self.pos.winning_points(attacker).first() {
+            return Some(win);
+        }
+        if depth == 0 {
+            return None;
+        }
+
+        let defender = attacker.opponent();
+        let defender_wins = self.pos.winning_points(defender);
+        let candidates = match defender_wins.len() {
+            0 => self.pos.threat_moves(attacker),
+            1 => defender_wins,
+            _ => return None,
+        };
+
+        for position in candidates {
+            self.pos.cells[position] = Some(attacker);
+            let wins = self.pos.winning_points_near(position, attacker);
+            let replies = match wins.len() {
+                0 => self.three_defenses(position, attacker),
+                1 => Some(wins),
+                _ => Some(Vec::new()),
+            };
+
+            let result = match replies {
+                None => false,
+                Some(replies) => replies.into_iter().all(|reply| {
+                    self.pos.cells[reply] = Some(defender);
+                    let result = !self.pos.completes_line_at(reply, defender)
+                        && self.vct(attacker, depth - 1).is_some();
+                    self.pos.cells[reply] = None;
+                    result
+                }),
+            };
+            self.pos.cells[position] = None;
+            if result {
+                return Some(position);
+            }
+            if self.timed_out {
+                return None;
+            }
+        }
+
+        None
+    }
+
+    /// Ответы защитника на открытую тройку
+    ///
+    /// Это клетки, после занятия которых атакующий больше не может
+    /// построить открытую четверку, и контратакующие четверки защитника.
+    /// Возвращает `None`, если ход не создал открытую тройку.
+    fn three_defenses(&mut self, position: usize, attacker: Player) -> Option<Vec<usize>> {
+        if self.pos.open_four_points(position, attacker).is_empty() {
+            return None;
+        }
+
+        let defender = attacker.opponent();
+        let mut replies = Vec::new();
+        for cell in self.pos.line_cells(position) {
+            self.pos.cells[cell] = Some(defender);
+            if self.pos.open_four_points(position, attacker).is_empty() {
+                replies.push(cell);
+            }
+            self.pos.cells[cell] = None;
+        }
+        for cell in self.pos.four_moves(defender) {
+            if !replies.contains(&cell) {
+                replies.push(cell);
+            }
+        }
+
+        Some(replies)
+    }
+
+    /// Кандидаты для перебора, отсортированные по приоритету
+    fn candidates(&mut self, player: Player) -> Vec<usize> {
+        // Свой выигрыш, затем обязательная защита
+        if let Some(&win) = self.pos.winning_points(player).first() {
+            return vec![win];
+        }
+        let blocks = self.pos.winning_points(player.opponent());
+        if !blocks.is_empty() {
+            return blocks;
+        }
+
+        let mut moves: Vec<(i64, usize)> = (0..self.pos.cells.len())
+            .filter(|&cell| self.pos.cells[cell].is_none() && self.pos.has_neighbor(cell, None, 2))
+            .map(|cell| (self.pos.cell_priority(cell, player), cell))
+            .collect();
+        if moves.is_empty() {
+            return self.pos.cells.iter().position(|cell| cell.is_none()).into_iter().collect();
+        }
+        moves.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+        moves.truncate(self.config.candidate_limit);
+        moves.into_iter().map(|(_, cell)| cell).collect()
+    }
+
+    /// Корень перебора на заданную глубину
+    fn root(&mut self, player: Player, depth: usize) -> Option<(usize, i64)> {
+        let mut best: Option<(usize, i64)> = None;
+        let mut alpha = -WIN_SCORE - 1;
+        for position in self.candidates(player) {
+            let score = -self.negamax(position, player, depth - 1, 1, -WIN_SCORE - 1, -alpha);
+            if self.timed_out {
+                return best;
+            }
+            if best.is_none_or(|(_, s)| score > s) {
+                best = Some((position, score));
+                alpha = alpha.max(score);
+            }
+        }
+        best
+    }
+
+    /// Делает ход `position` за `player` и оценивает позицию за соперника
+    fn negamax(&mut self, position: usize, player: Player, depth: usize, ply: usize, mut alpha: i64, beta: i64) -> i64 {
+        self.tick();
+        self.pos.cells[position] = Some(player);
+        let opponent = player.opponent();
+
+        let score = if self.pos.completes_line_at(position, player) {
+            -(WIN_SCORE - ply as i64)
+        } else if self.pos.empty_count() == 0 {
+            0
+        } else if depth == 0 || self.timed_out {
+            self.pos.evaluate(opponent)
+        } else {
+            let mut best = -WIN_SCORE - 1;
+            for reply in self.candidates(opponent) {
+                let score = -self.negamax(reply, opponent, depth - 1, ply + 1, -beta, -alpha);
+                best = best.max(score);
+                alpha = alpha.max(score);
+                if alpha >= beta || self.timed_out {
+                    break;
+                }
+            }
+            best
+        };
+
+        self.pos.cells[position] = None;
+        score
+    }
+}
+
+// === ТЕСТЫ ===
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Rules;
+
+    /// Расставляет ходы поочередно за X и O
+    fn play(rules: Rules, moves: &[usize]) -> TicTacToe {
+        let mut game = TicTacToe::with_rules(rules);
+        for &position in moves {
+            assert!(game.make_move(position));
+        }
+        game
+    }
+
+    /// Тест: распознавание четверок и открытых троек на поле 15x15
+    #[test]
+    fn test_classify_threats() {
+        // X: 7*15+5, 7*15+6, 7*15+7; O далеко
+        let game = play(Rules::gomoku(), &[110, 0, 111, 2, 112, 4]);
+        assert_eq!(classify_move(&game, 113), Some(Threat::OpenFour));
+
+        let game = play(Rules::gomoku(), &[110, 0, 111, 2]);
+        assert_eq!(classify_move(&game, 112), Some(Threat::OpenThree));
+
+        let game = play(Rules::gomoku(), &[110, 0, 111, 2, 112, 109]);
+        assert_eq!(classify_move(&game, 113), Some(Threat::Four));
+    }
+
+    /// Тест: VCF находит выигрыш двумя четверками
+    #[test]
+    fn test_vcf_finds_forced_win() {
+        // Горизонтальная и вертикальная тройки X, закрытые с одной стороны,
+        // пересекаются в пустой клетке 6*15+9 = 99
+        let x = [96, 97, 98, 114, 129, 144];
+        let o = [95, 200, 159, 0, 2, 4];
+        let mut moves = Vec::new();
+        for i in 0..x.len() {
+            moves.push(x[i]);
+            moves.push(o[i]);
+        }
+        let game = play(Rules::gomoku(), &moves);
+
+        let engine = ThreatSpaceEngine::default();
+        assert!(engine.find_vcf(&game).is_some());
+    }
+
+    /// Тест: движок выигрывает одним ходом и блокирует выигрыш соперника
+    #[test]
+    fn test_engine_wins_and_blocks() {
+        let mut engine = ThreatSpaceEngine::default();
+
+        // X может выиграть в позиции 2
+        let game = play(Rules::classic(), &[0, 3, 1, 4]);
+        assert_eq!(engine.choose_move(&game), Some(2));
+
+        // O должен закрыть позицию 2
+        let game = play(Rules::classic(), &[0, 4, 1]);
+        assert_eq!(engine.choose_move(&game), Some(2));
+    }
+
+    /// Тест: партия движка против самого себя на поле 3x3 заканчивается ничьей
+    #[test]
+    fn test_classic_self_play_is_draw() {
+        let mut engine = ThreatSpaceEngine::default();
+        let mut game = TicTacToe::new();
+        while !game.is_game_over() {
+            let position = engine.choose_move(&game).unwrap();
+            assert!(game.make_move(position));
+        }
+        assert_eq!(game.get_winner(), None);
+    }
+}
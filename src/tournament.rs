@@ -0,0 +1,571 @@
+//! # Турниры между движками
+//!
+//! Разыгрывает матчи между агентами по круговой системе или в формате
+//! "гонтлет" (один претендент против всех остальных), чередуя первый ход,
+//! в нескольких потоках. Для каждой пары считает победы, ничьи и поражения,
+//! оценку разницы Эло с доверительным интервалом и может досрочно
+//! останавливать пару по критерию SPRT.
+//!
+//! ## Использование
+//! ```rust
+//! use rust_tic_tac_toe::Rules;
+//! use rust_tic_tac_toe::agent::{Agent, ScriptedAgent};
+//! use rust_tic_tac_toe::tournament::{Participant, Tournament, TournamentConfig};
+//!
+//! let participants = vec![
+//!     Participant::new("first", |_rules, _seed| Box::new(ScriptedAgent::new(vec![0, 1, 2, 3, 4, 5, 6, 7, 8])) as Box<dyn Agent + Send>),
+//!     Participant::new("second", |_rules, _seed| Box::new(ScriptedAgent::new(vec![8, 7, 6, 5, 4, 3, 2, 1, 0])) as Box<dyn Agent + Send>),
+//! ];
+//! let config = TournamentConfig { games_per_pair: 4, ..Default::default() };
+//! let report = Tournament::new(participants, config)?.run();
+//! assert_eq!(report.records.len(), 4);
+//! println!("{}", report.to_json());
+//! # Ok::<(), String>(())
+//! ```
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+
+use crate::agent::Agent;
+use crate::record::GameRecord;
+use crate::{Localization, MessageKey, Player, Rules, TicTacToe};
+
+/// Фабрика агентов: создает нового агента для партии
+///
+/// Получает правила и зерно, чтобы случайные агенты играли разные партии.
+pub type AgentFactory = Arc<dyn Fn(Rules, u64) -> Box<dyn Agent + Send> + Send + Sync>;
+
+/// Участник турнира
+#[derive(Clone)]
+pub struct Participant {
+    /// Имя участника
+    pub name: String,
+    /// Фабрика агентов
+    pub factory: AgentFactory,
+}
+
+impl Participant {
+    /// Создает участника с указанным именем и фабрикой
+    pub fn new<F>(name: impl Into<String>, factory: F) -> Self
+    where
+        F: Fn(Rules, u64) -> Box<dyn Agent + Send> + Send + Sync + 'static,
+    {
+        Self { name: name.into(), factory: Arc::new(factory) }
+    }
+}
+
+/// Формат турнира
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize)]
+pub enum Format {
+    /// Каждый играет с каждым
+    RoundRobin,
+    /// Участник с указанным номером играет со всеми остальными
+    Gauntlet {
+        /// Номер претендента в списке участников
+        challenger: usize,
+    },
+}
+
+/// Параметры последовательного теста отношения правдоподобия (SPRT)
+#[derive(Clone, Copy, Debug, Serialize)]
+pub struct SprtConfig {
+    /// Нулевая гипотеза: разница Эло
+    pub elo0: f64,
+    /// Альтернативная гипотеза: разница Эло
+    pub elo1: f64,
+    /// Вероятность ошибки первого рода
+    pub alpha: f64,
+    /// Вероятность ошибки второго рода
+    pub beta: f64,
+}
+
+impl Default for SprtConfig {
+    fn default() -> Self {
+        Self { elo0: 0.0, elo1: 10.0, alpha: 0.05, beta: 0.05 }
+    }
+}
+
+/// Настройки турнира
+#[derive(Clone, Debug)]
+pub struct TournamentConfig {
+    /// Правила всех партий
+    pub rules: Rules,
+    /// Формат турнира
+    pub format: Format,
+    /// Сколько партий играет каждая пара (первый ход чередуется)
+    pub games_per_pair: usize,
+    /// Количество потоков
+    pub threads: usize,
+    /// Досрочная остановка пар по SPRT
+    pub sprt: Option<SprtConfig>,
+    /// Начальное зерно для агентов
+    pub seed: u64,
+}
+
+impl Default for TournamentConfig {
+    fn default() -> Self {
+        Self {
+            rules: Rules::classic(),
+            format: Format::RoundRobin,
+            games_per_pair: 10,
+            threads: 1,
+            sprt: None,
+            seed: 1,
+        }
+    }
+}
+
+/// Оценка разницы Эло с 95% доверительным интервалом
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct EloEstimate {
+    /// Оценка разницы
+    pub elo: f64,
+    /// Нижняя граница интервала
+    pub lower: f64,
+    /// Верхняя граница интервала
+    pub upper: f64,
+}
+
+/// Состояние SPRT для пары
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize)]
+pub enum SprtStatus {
+    /// Данных пока недостаточно
+    Continue,
+    /// Принята нулевая гипотеза (первый участник не сильнее на `elo1`)
+    AcceptH0,
+    /// Принята альтернативная гипотеза (первый участник сильнее)
+    AcceptH1,
+}
+
+/// Результаты одной пары с точки зрения первого участника
+#[derive(Clone, Debug, Serialize)]
+pub struct PairResult {
+    /// Первый участник
+    pub first: String,
+    /// Второй участник
+    pub second: String,
+    /// Победы первого
+    pub wins: u32,
+    /// Ничьи
+    pub draws: u32,
+    /// Поражения первого
+    pub losses: u32,
+    /// Разница Эло первого относительно второго
+    pub elo: EloEstimate,
+    /// Логарифм отношения правдоподобия SPRT
+    pub llr: Option<f64>,
+    /// Решение SPRT
+    pub sprt: Option<SprtStatus>,
+}
+
+/// Итоги участника по всем парам
+#[derive(Clone, Debug, Serialize)]
+pub struct Standing {
+    /// Имя участника
+    pub name: String,
+    /// Победы
+    pub wins: u32,
+    /// Ничьи
+    pub draws: u32,
+    /// Поражения
+    pub losses: u32,
+    /// Очки: победа - 1, ничья - 0.5
+    pub points: f64,
+}
+
+/// Отчет о турнире
+#[derive(Clone, Debug, Serialize)]
+pub struct TournamentReport {
+    /// Правила партий
+    pub rules: Rules,
+    /// Формат турнира
+    pub format: Format,
+    /// Таблица участников, отсортированная по очкам
+    pub standings: Vec<Standing>,
+    /// Результаты пар
+    pub pairs: Vec<PairResult>,
+    /// Записи всех сыгранных партий
+    pub records: Vec<GameRecord>,
+}
+
+impl TournamentReport {
+    /// Отчет в формате JSON
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    /// Все партии в текстовой записи, разделенные пустой строкой
+    pub fn records_notation(&self) -> String {
+        self.records
+            .iter()
+            .map(GameRecord::to_notation)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Таблица результатов для вывода в консоль на языке `localization`
+    pub fn summary(&self, localization: &Localization) -> String {
+        let rules = localization.format(MessageKey::TournamentRules, &[("rules", self.rules.to_string().into())]);
+        let elo = localization.get_text(MessageKey::TournamentElo);
+        let mut text = format!("{}\n\n", rules);
+        for (place, standing) in self.standings.iter().enumerate() {
+            text += &format!(
+                "{}. {:<16} {:>6.1}  +{} ={} -{}\n",
+                place + 1,
+                standing.name,
+                standing.points,
+                standing.wins,
+                standing.draws,
+                standing.losses,
+            );
+        }
+        text += "\n";
+        for pair in &self.pairs {
+            text += &format!(
+                "{} - {}: +{} ={} -{}  {} {:+.1} [{:+.1}, {:+.1}]",
+                pair.first, pair.second, pair.wins, pair.draws, pair.losses, elo,
+                pair.elo.elo, pair.elo.lower, pair.elo.upper,
+            );
+            if let (Some(llr), Some(status)) = (pair.llr, pair.sprt) {
+                text += &format!("  LLR {:.2} {:?}", llr, status);
+            }
+            text += "\n";
+        }
+        text
+    }
+}
+
+/// Турнир между участниками
+pub struct Tournament {
+    participants: Vec<Participant>,
+    config: TournamentConfig,
+}
+
+/// Одна партия в очереди
+#[derive(Clone, Copy)]
+struct Job {
+    pair: usize,
+    game: usize,
+}
+
+impl Tournament {
+    /// Создает турнир
+    ///
+    /// ## Возвращает
+    /// Турнир или описание ошибки: недопустимые правила или номер
+    /// претендента в "гонтлете" вне списка участников
+    pub fn new(participants: Vec<Participant>, config: TournamentConfig) -> Result<Self, String> {
+        config.rules.validate()?;
+        if let Format::Gauntlet { challenger } = config.format {
+            if challenger >= participants.len() {
+                return Err(format!(
+                    "нет претендента с номером {}: участников {}",
+                    challenger,
+                    participants.len()
+                ));
+            }
+        }
+        Ok(Self { participants, config })
+    }
+
+    /// Пары участников согласно формату
+    fn pairings(&self) -> Vec<(usize, usize)> {
+        let count = self.participants.len();
+        match self.config.format {
+            Format::RoundRobin => (0..count)
+                .flat_map(|a| (a + 1..count).map(move |b| (a, b)))
+                .collect(),
+            Format::Gauntlet { challenger } => (0..count)
+                .filter(|&other| other != challenger)
+                .map(|other| (challenger, other))
+                .collect(),
+        }
+    }
+
+    /// Играет все партии и возвращает отчет
+    pub fn run(&self) -> TournamentReport {
+        let pairings = self.pairings();
+        let jobs: VecDeque<Job> = (0..self.config.games_per_pair)
+            .flat_map(|game| (0..pairings.len()).map(move |pair| Job { pair, game }))
+            .collect();
+        let total = jobs.len();
+
+        let queue = Mutex::new(jobs);
+        let pairs: Vec<PairResult> = pairings
+            .iter()
+            .map(|&(a, b)| PairResult {
+                first: self.participants[a].name.clone(),
+                second: self.participants[b].name.clone(),
+                wins: 0,
+                draws: 0,
+                losses: 0,
+                elo: elo_estimate(0, 0, 0),
+                llr: self.config.sprt.map(|_| 0.0),
+                sprt: self.config.sprt.map(|_| SprtStatus::Continue),
+            })
+            .collect();
+        let results = Mutex::new((pairs, Vec::with_capacity(total)));
+
+        std::thread::scope(|scope| {
+            for _ in 0..self.config.threads.max(1) {
+                let (queue, results, pairings) = (&queue, &results, &pairings);
+                scope.spawn(move || {
+                    while let Some(job) = queue.lock().ok().and_then(|mut queue| queue.pop_front()) {
+                        // Пара уже остановлена по SPRT
+                        let stopped = results.lock().map_or(true, |results| {
+                            results.0[job.pair].sprt.is_some_and(|status| status != SprtStatus::Continue)
+                        });
+                        if stopped {
+                            continue;
+                        }
+
+                        let record = self.play(pairings[job.pair], job);
+                        let Ok(mut results) = results.lock() else {
+                            break;
+                        };
+                        let (pairs, records) = &mut *results;
+                        self.update_pair(&mut pairs[job.pair], job, &record);
+                        records.push(record);
+                    }
+                });
+            }
+        });
+
+        let (pairs, records) = results.into_inner().unwrap_or_default();
+        TournamentReport {
+            rules: self.config.rules,
+            format: self.config.format,
+            standings: self.standings(&pairings, &pairs),
+            pairs,
+            records,
+        }
+    }
+
+    /// Учитывает результат партии в статистике пары
+    fn update_pair(&self, pair: &mut PairResult, job: Job, record: &GameRecord) {
+        let first_player = if job.game.is_multiple_of(2) { Player::X } else { Player::O };
+        match record.winner {
+            Some(winner) if winner == first_player => pair.wins += 1,
+            Some(_) => pair.losses += 1,
+            None => pair.draws += 1,
+        }
+        pair.elo = elo_estimate(pair.wins, pair.draws, pair.losses);
+
+        if let Some(sprt) = &self.config.sprt {
+            let llr = sprt_llr(pair.wins, pair.draws, pair.losses, sprt);
+            pair.llr = Some(llr);
+            if pair.sprt == Some(SprtStatus::Continue) {
+                pair.sprt = Some(sprt_status(llr, sprt));
+            }
+        }
+    }
+
+    /// Играет одну партию пары; в четных партиях первым ходит первый участник
+    fn play(&self, (a, b): (usize, usize), job: Job) -> GameRecord {
+        let (x, o) = if job.game.is_multiple_of(2) { (a, b) } else { (b, a) };
+        let seed = self.config.seed.wrapping_add((job.pair * self.config.games_per_pair + job.game) as u64);
+        let mut agents = [
+            (self.participants[x].factory)(self.config.rules, seed),
+            (self.participants[o].factory)(self.config.rules, seed.wrapping_mul(31)),
+        ];
+        play_game(
+            self.config.rules,
+            &mut agents,
+            &self.participants[x].name,
+            &self.participants[o].name,
+        )
+    }
+
+    /// Итоговая таблица участников
+    fn standings(&self, pairings: &[(usize, usize)], pairs: &[PairResult]) -> Vec<Standing> {
+        let mut standings: Vec<Standing> = self
+            .participants
+            .iter()
+            .map(|participant| Standing {
+                name: participant.name.clone(),
+                wins: 0,
+                draws: 0,
+                losses: 0,
+                points: 0.0,
+            })
+            .collect();
+        for (&(a, b), pair) in pairings.iter().zip(pairs) {
+            for (index, wins, losses) in [(a, pair.wins, pair.losses), (b, pair.losses, pair.wins)] {
+                standings[index].wins += wins;
+                standings[index].draws += pair.draws;
+                standings[index].losses += losses;
+                standings[index].points += wins as f64 + pair.draws as f64 / 2.0;
+            }
+        }
+        standings.sort_by(|a, b| b.points.total_cmp(&a.points));
+        standings
+    }
+}
+
+/// Играет одну партию между двумя агентами
+///
+/// Агент, который не может сделать ход или делает некорректный ход,
+/// проигрывает партию.
+pub fn play_game(rules: Rules, agents: &mut [Box<dyn Agent + Send>; 2], x: &str, o: &str) -> GameRecord {
+    let mut game = TicTacToe::with_rules(rules);
+    for agent in agents.iter_mut() {
+        agent.new_game(&game);
+    }
+
+    while !game.is_game_over() {
+        let current = match game.current_player() {
+            Player::X => 0,
+            Player::O => 1,
+        };
+        let legal = agents[current]
+            .choose_move(&game)
+            .is_some_and(|position| game.make_move(position));
+        if !legal {
+            // Ход не получен - техническое поражение
+            game.adjudicate(Some(game.current_player().opponent()));
+            return GameRecord::from_game(&game, x, o);
+        }
+        let position = *game.moves().last().unwrap();
+        agents[1 - current].observe_move(&game, position);
+    }
+
+    GameRecord::from_game(&game, x, o)
+}
+
+/// Ожидаемый результат при разнице Эло
+fn expected_score(elo: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf(-elo / 400.0))
+}
+
+/// Разница Эло для среднего результата
+fn score_to_elo(score: f64) -> f64 {
+    let score = score.clamp(1e-6, 1.0 - 1e-6);
+    -400.0 * (1.0 / score - 1.0).log10()
+}
+
+/// Средний результат и дисперсия результата одной партии
+fn score_stats(wins: u32, draws: u32, losses: u32) -> (f64, f64, f64) {
+    let n = (wins + draws + losses) as f64;
+    if n == 0.0 {
+        return (0.0, 0.5, 0.0);
+    }
+    let score = (wins as f64 + draws as f64 / 2.0) / n;
+    let variance = (wins as f64 * (1.0 - score).powi(2)
+        + draws as f64 * (0.5 - score).powi(2)
+        + losses as f64 * score.powi(2))
+        / n;
+    (n, score, variance)
+}
+
+/// Оценка разницы Эло с 95% доверительным интервалом
+pub fn elo_estimate(wins: u32, draws: u32, losses: u32) -> EloEstimate {
+    let (n, score, variance) = score_stats(wins, draws, losses);
+    if n == 0.0 {
+        return EloEstimate { elo: 0.0, lower: f64::NEG_INFINITY, upper: f64::INFINITY };
+    }
+    let margin = 1.96 * (variance / n).sqrt();
+    EloEstimate {
+        elo: score_to_elo(score),
+        lower: score_to_elo(score - margin),
+        upper: score_to_elo(score + margin),
+    }
+}
+
+/// Логарифм отношения правдоподобия SPRT (нормальное приближение)
+///
+/// К результатам добавляются одна виртуальная победа и одно поражение,
+/// чтобы дисперсия не была нулевой, когда все партии закончились одинаково.
+pub fn sprt_llr(wins: u32, draws: u32, losses: u32, config: &SprtConfig) -> f64 {
+    let (n, score, variance) = score_stats(wins + 1, draws, losses + 1);
+    if n == 0.0 || variance <= 0.0 {
+        return 0.0;
+    }
+    let s0 = expected_score(config.elo0);
+    let s1 = expected_score(config.elo1);
+    n * (s1 - s0) * (2.0 * score - s0 - s1) / (2.0 * variance)
+}
+
+/// Решение SPRT по значению LLR
+pub fn sprt_status(llr: f64, config: &SprtConfig) -> SprtStatus {
+    let lower = (config.beta / (1.0 - config.alpha)).ln();
+    let upper = ((1.0 - config.beta) / config.alpha).ln();
+    if llr >= upper {
+        SprtStatus::AcceptH1
+    } else if llr <= lower {
+        SprtStatus::AcceptH0
+    } else {
+        SprtStatus::Continue
+    }
+}
+
+// === ТЕСТЫ ===
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::ScriptedAgent;
+
+    /// Агент, который всегда ходит в первую свободную клетку
+    fn first_free() -> Participant {
+        Participant::new("first-free", |_rules, _seed| {
+            Box::new(ScriptedAgent::new((0..9).collect())) as Box<dyn Agent + Send>
+        })
+    }
+
+    /// Агент, который сразу сдается
+    fn resigner() -> Participant {
+        Participant::new("resigner", |_rules, _seed| Box::new(ScriptedAgent::new(Vec::new())) as Box<dyn Agent + Send>)
+    }
+
+    /// Тест: оценка Эло и решение SPRT
+    #[test]
+    fn test_elo_and_sprt() {
+        let even = elo_estimate(10, 0, 10);
+        assert!(even.elo.abs() < 1e-9);
+        assert!(even.lower < 0.0 && even.upper > 0.0);
+        assert!(elo_estimate(30, 5, 5).elo > 100.0);
+
+        let config = SprtConfig::default();
+        assert_eq!(sprt_status(sprt_llr(200, 10, 20, &config), &config), SprtStatus::AcceptH1);
+        assert_eq!(sprt_status(sprt_llr(20, 10, 200, &config), &config), SprtStatus::AcceptH0);
+        assert_eq!(sprt_status(sprt_llr(1, 0, 1, &config), &config), SprtStatus::Continue);
+    }
+
+    /// Тест: круговой турнир в нескольких потоках с чередованием первого хода
+    #[test]
+    fn test_round_robin_parallel() {
+        let config = TournamentConfig { games_per_pair: 6, threads: 3, ..Default::default() };
+        let report = Tournament::new(vec![first_free(), resigner()], config).unwrap().run();
+
+        assert_eq!(report.records.len(), 6);
+        assert_eq!(report.records.iter().filter(|r| r.x == "first-free").count(), 3);
+        assert_eq!(report.pairs[0].wins, 6);
+        assert_eq!(report.standings[0].name, "first-free");
+        assert!(report.to_json().contains("\"standings\""));
+    }
+
+    /// Тест: SPRT досрочно останавливает явно неравную пару
+    #[test]
+    fn test_sprt_stops_early() {
+        let config = TournamentConfig {
+            games_per_pair: 1000,
+            sprt: Some(SprtConfig { elo0: 0.0, elo1: 50.0, ..Default::default() }),
+            format: Format::Gauntlet { challenger: 1 },
+            ..Default::default()
+        };
+        let report = Tournament::new(vec![resigner(), first_free()], config).unwrap().run();
+
+        assert!(report.records.len() < 1000);
+        assert_eq!(report.pairs[0].first, "first-free");
+        assert_eq!(report.pairs[0].sprt, Some(SprtStatus::AcceptH1));
+    }
+
+    /// Тест: претендент вне списка участников - ошибка, а не паника
+    #[test]
+    fn test_gauntlet_challenger_out_of_range() {
+        let config = TournamentConfig { format: Format::Gauntlet { challenger: 2 }, ..Default::default() };
+        assert!(Tournament::new(vec![resigner(), first_free()], config).is_err());
+    }
+}
@@ -1,11 +1,36 @@
 //! # Веб-версия графического интерфейса
 //! 
-//! Этот модуль содержит веб-версию GUI для игры "Крестики-нолики".
-//! Работает в браузере через WebAssembly.
+//! Этот модуль содержит веб-оболочку общего приложения [`crate::app::App`]
+//! для игры "Крестики-нолики". Работает в браузере через WebAssembly.
+//! Поле, статус, матч, часы, выбор сторон и языка те же, что в настольной
+//! версии.
+//! 
+//! Кроме игры за одним устройством поддерживает онлайн-игру через игровой
+//! сервер (`cargo run --bin server`): одна вкладка создает комнату, другая
+//! входит в нее по коду. Код комнаты и ключ места хранятся в `localStorage`,
+//! поэтому после перезагрузки страницы клиент возвращается в партию.
+//!
+//! Язык интерфейса берется из `navigator.language`, а выбранный вручную
+//! запоминается в `localStorage`, как и тема оформления. Заголовок вкладки
+//! переводится вместе с интерфейсом.
+//!
+//! Запасные шрифты для китайского, японского и арабского скачиваются с того
+//! же сервера, что и страница (см. [`crate::fonts::WEB_FONTS`]); пока их нет,
+//! эти языки показываются квадратами.
 
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
 use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
 use eframe::{egui, WebRunner};
-use crate::TicTacToe;
+use crate::{bidi, fonts, Language, Localization, MessageKey, Player, PlayerNames};
+use crate::app::{self, App, Remote, Session, Shell};
+use crate::i18n::LANGUAGE_STORAGE_KEY;
+use crate::online::{ClientMessage, ServerMessage, OnlineState, DEFAULT_PORT};
+
+/// Ключ `localStorage` для возвращения в онлайн-партию
+const ONLINE_STORAGE_KEY: &str = "tic_tac_toe_online";
 
 /// Веб-версия GUI для игры "Крестики-нолики"
 #[wasm_bindgen]
@@ -25,7 +50,8 @@ impl TicTacToeWeb {
     /// Запускает веб-интерфейс
     pub async fn start(&mut self) -> Result<(), JsValue> {
         let options = eframe::WebOptions {
-            follow_system_theme: false,
+            // Тема "как в системе" узнает о светлой или темной теме браузера
+            follow_system_theme: true,
             default_theme: eframe::Theme::Dark,
             ..Default::default()
         };
@@ -33,7 +59,19 @@ impl TicTacToeWeb {
         self.runner.start(
             "game-canvas",
             options,
-            Box::new(|_cc| Box::new(TicTacToeWebApp::new())),
+            Box::new(|cc| {
+                load_web_fonts(cc.egui_ctx.clone());
+                // Сохраненный выбор, затем язык браузера, иначе английский
+                let mut localization = Localization::new(Language::English);
+                let browser = web_sys::window().and_then(|window| window.navigator().language());
+                localization.select_preferred(browser.iter().map(String::as_str));
+                let mut session = Session::new(localization);
+                session.restore_language(cc.storage);
+                session.restore_appearance(cc.storage);
+                let mut shell = WebShell::new();
+                shell.restore_online(&cc.egui_ctx);
+                Box::new(App::new(session, shell))
+            }),
         ).await?;
 
         Ok(())
@@ -45,227 +83,393 @@ impl TicTacToeWeb {
     }
 }
 
-/// Внутреннее веб-приложение
-struct TicTacToeWebApp {
-    game: TicTacToe,
-    cell_size: f32,
-    colors: WebGameColors,
-    localization: Localization,
+/// Внутреннее веб-приложение: онлайн-игра через браузерный WebSocket
+struct WebShell {
+    /// Подключение к игровому серверу
+    online: Option<OnlineClient>,
+    /// Адрес игрового сервера
+    server_url: String,
+    /// Вводимый код комнаты
+    room_code: String,
+    /// Ошибка подключения к серверу
+    online_error: Option<String>,
 }
 
-struct WebGameColors {
-    background: egui::Color32,
-    grid: egui::Color32,
-    x_color: egui::Color32,
-    o_color: egui::Color32,
-    highlight: egui::Color32,
-    text: egui::Color32,
+/// Сохраненное место в онлайн-партии
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SavedSeat {
+    url: String,
+    code: String,
+    token: String,
 }
 
-impl Default for WebGameColors {
-    fn default() -> Self {
-        Self {
-            background: egui::Color32::from_rgb(40, 44, 52),    // Темно-серый
-            grid: egui::Color32::from_rgb(68, 71, 90),          // Светло-серый
-            x_color: egui::Color32::from_rgb(255, 85, 85),      // Красный
-            o_color: egui::Color32::from_rgb(80, 250, 123),     // Зеленый
-            highlight: egui::Color32::from_rgb(255, 184, 108),  // Оранжевый
-            text: egui::Color32::from_rgb(248, 248, 242),       // Белый
+/// Клиент игрового сервера поверх браузерного WebSocket
+struct OnlineClient {
+    socket: web_sys::WebSocket,
+    url: String,
+    /// Сообщения сервера, еще не обработанные интерфейсом
+    inbox: Rc<RefCell<VecDeque<ServerMessage>>>,
+    /// Состояние лобби и партии
+    state: OnlineState,
+    /// Клиент вошел через лобби, а не по коду комнаты
+    in_lobby: bool,
+    _on_open: Closure<dyn FnMut()>,
+    _on_message: Closure<dyn FnMut(web_sys::MessageEvent)>,
+}
+
+impl OnlineClient {
+    /// Подключается к серверу и отправляет первое сообщение после открытия соединения
+    fn connect(url: &str, first: &ClientMessage, ctx: egui::Context) -> Result<Self, JsValue> {
+        let socket = web_sys::WebSocket::new(url)?;
+        let inbox = Rc::new(RefCell::new(VecDeque::new()));
+        
+        let on_open = {
+            let socket = socket.clone();
+            let text = serde_json::to_string(first).unwrap_or_default();
+            Closure::wrap(Box::new(move || {
+                let _ = socket.send_with_str(&text);
+            }) as Box<dyn FnMut()>)
+        };
+        socket.set_onopen(Some(on_open.as_ref().unchecked_ref()));
+        
+        let on_message = {
+            let inbox = Rc::clone(&inbox);
+            Closure::wrap(Box::new(move |event: web_sys::MessageEvent| {
+                let Some(text) = event.data().as_string() else { return };
+                if let Ok(message) = serde_json::from_str(&text) {
+                    inbox.borrow_mut().push_back(message);
+                    ctx.request_repaint();
+                }
+            }) as Box<dyn FnMut(web_sys::MessageEvent)>)
+        };
+        socket.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+        
+        Ok(Self {
+            socket,
+            url: url.to_string(),
+            inbox,
+            state: OnlineState::default(),
+            in_lobby: matches!(first, ClientMessage::Lobby),
+            _on_open: on_open,
+            _on_message: on_message,
+        })
+    }
+    
+    fn send(&self, message: &ClientMessage) {
+        if let Ok(text) = serde_json::to_string(message) {
+            let _ = self.socket.send_with_str(&text);
         }
     }
 }
 
-impl TicTacToeWebApp {
-    fn new() -> Self {
-        Self {
-            game: TicTacToe::new(),
-            cell_size: 80.0,
-            colors: WebGameColors::default(),
-            localization: Localization::new(Language::English), // По умолчанию английский
-        }
+impl Drop for OnlineClient {
+    fn drop(&mut self) {
+        let _ = self.socket.close();
     }
+}
 
-    fn draw_board(&mut self, ui: &mut egui::Ui) {
-        let board_size = self.cell_size * 3.0;
-        
-        let (response, painter) = ui.allocate_painter(
-            egui::vec2(board_size, board_size),
-            egui::Sense::click()
-        );
-
-        // Фон
-        painter.rect_filled(
-            egui::Rect::from_min_size(
-                response.rect.min,
-                egui::vec2(board_size, board_size)
-            ),
-            0.0,
-            self.colors.background,
-        );
-
-        // Сетка
-        self.draw_grid(&painter, board_size, response.rect);
-
-        // Символы
-        self.draw_symbols(&painter, response.rect);
-
-        // Клики
-        if response.clicked() {
-            self.handle_click(&response);
-        }
+/// Хранилище браузера
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+/// Место в онлайн-партии, сохраненное до перезагрузки страницы
+fn load_saved_seat() -> Option<SavedSeat> {
+    let text = local_storage()?.get_item(ONLINE_STORAGE_KEY).ok()??;
+    serde_json::from_str(&text).ok()
+}
+
+/// Запоминает выбранный язык до следующего открытия страницы
+fn save_language(locale: &str) {
+    if let Some(storage) = local_storage() {
+        let _ = storage.set_item(LANGUAGE_STORAGE_KEY, locale);
     }
+}
 
-    fn draw_grid(&self, painter: &egui::Painter, board_size: f32, rect: egui::Rect) {
-        let stroke = egui::Stroke::new(3.0, self.colors.grid);
-        
-        // Вертикальные линии
-        for i in 1..3 {
-            let x = rect.min.x + i as f32 * self.cell_size;
-            painter.line_segment(
-                [egui::pos2(x, rect.min.y), egui::pos2(x, rect.min.y + board_size)],
-                stroke,
-            );
+/// Меняет заголовок вкладки браузера
+fn set_document_title(title: &str) {
+    if let Some(document) = web_sys::window().and_then(|window| window.document()) {
+        document.set_title(title);
+    }
+}
+
+/// Адрес игрового сервера по умолчанию - тот же хост, что и у страницы
+fn default_server_url() -> String {
+    let host = web_sys::window()
+        .and_then(|window| window.location().hostname().ok())
+        .filter(|host| !host.is_empty())
+        .unwrap_or_else(|| "localhost".to_string());
+    format!("ws://{}:{}", host, DEFAULT_PORT)
+}
+
+/// Скачивает запасные шрифты и подключает те, что нашлись
+fn load_web_fonts(ctx: egui::Context) {
+    wasm_bindgen_futures::spawn_local(async move {
+        let mut loaded = Vec::new();
+        for url in fonts::WEB_FONTS {
+            match fetch_bytes(url).await {
+                Ok(data) => loaded.push((url.to_string(), data)),
+                Err(e) => web_sys::console::warn_1(&e),
+            }
         }
-        
-        // Горизонтальные линии
-        for i in 1..3 {
-            let y = rect.min.y + i as f32 * self.cell_size;
-            painter.line_segment(
-                [egui::pos2(rect.min.x, y), egui::pos2(rect.min.x + board_size, y)],
-                stroke,
-            );
+        if !loaded.is_empty() {
+            ctx.set_fonts(fonts::definitions(loaded));
+            ctx.request_repaint();
         }
+    });
+}
+
+/// Скачивает файл с сервера страницы
+async fn fetch_bytes(url: &str) -> Result<Vec<u8>, JsValue> {
+    let window = web_sys::window().ok_or_else(|| JsValue::from_str("нет окна браузера"))?;
+    let response: web_sys::Response = JsFuture::from(window.fetch_with_str(url)).await?.dyn_into()?;
+    if !response.ok() {
+        return Err(JsValue::from_str(&format!("{}: HTTP {}", url, response.status())));
     }
+    let buffer = JsFuture::from(response.array_buffer()?).await?;
+    Ok(js_sys::Uint8Array::new(&buffer).to_vec())
+}
 
-    fn draw_symbols(&self, painter: &egui::Painter, rect: egui::Rect) {
-        for (i, cell) in self.game.get_board().iter().enumerate() {
-            if let Some(player) = cell {
-                let (row, col) = (i / 3, i % 3);
-                let center = egui::pos2(
-                    rect.min.x + col as f32 * self.cell_size + self.cell_size / 2.0,
-                    rect.min.y + row as f32 * self.cell_size + self.cell_size / 2.0,
-                );
-                
-                match player {
-                    crate::Player::X => self.draw_x(painter, center),
-                    crate::Player::O => self.draw_o(painter, center),
-                }
-            }
+impl WebShell {
+    /// Создает оболочку без подключения к серверу
+    fn new() -> Self {
+        Self {
+            online: None,
+            server_url: default_server_url(),
+            room_code: String::new(),
+            online_error: None,
         }
     }
 
-    fn draw_x(&self, painter: &egui::Painter, center: egui::Pos2) {
-        let size = self.cell_size * 0.3;
-        let stroke = egui::Stroke::new(4.0, self.colors.x_color);
-        
-        painter.line_segment(
-            [egui::pos2(center.x - size, center.y - size), egui::pos2(center.x + size, center.y + size)],
-            stroke,
-        );
-        painter.line_segment(
-            [egui::pos2(center.x + size, center.y - size), egui::pos2(center.x - size, center.y + size)],
-            stroke,
-        );
+    /// Возвращается в онлайн-партию, если место сохранено до перезагрузки
+    fn restore_online(&mut self, ctx: &egui::Context) {
+        if let Some(saved) = load_saved_seat() {
+            self.server_url = saved.url.clone();
+            let message = ClientMessage::Rejoin { code: saved.code, token: saved.token };
+            self.connect_online(&message, ctx);
+        }
     }
 
-    fn draw_o(&self, painter: &egui::Painter, center: egui::Pos2) {
-        let radius = self.cell_size * 0.25;
-        let stroke = egui::Stroke::new(4.0, self.colors.o_color);
-        
-        painter.circle_stroke(center, radius, stroke);
+    /// Подключается к серверу с первым сообщением (создать, войти или вернуться)
+    fn connect_online(&mut self, first: &ClientMessage, ctx: &egui::Context) {
+        self.online = None;
+        match OnlineClient::connect(&self.server_url, first, ctx.clone()) {
+            Ok(client) => {
+                self.online = Some(client);
+                self.online_error = None;
+            }
+            Err(e) => self.online_error = Some(format!("{:?}", e)),
+        }
     }
 
-    fn handle_click(&mut self, response: &egui::Response) {
-        if self.game.is_game_over() {
-            return;
+    /// Выходит из онлайн-партии и забывает сохраненное место
+    fn leave_online(&mut self, session: &mut Session) {
+        if let Some(client) = &self.online {
+            client.send(&ClientMessage::Leave);
         }
-        
-        let click_pos = response.hover_pos().unwrap();
-        let board_pos = click_pos - response.rect.min;
-        
-        let col = (board_pos.x / self.cell_size) as usize;
-        let row = (board_pos.y / self.cell_size) as usize;
-        
-        if row < 3 && col < 3 {
-            let position = row * 3 + col;
-            self.game.make_move(position);
+        self.online = None;
+        if let Some(storage) = local_storage() {
+            let _ = storage.remove_item(ONLINE_STORAGE_KEY);
         }
+        session.reset_game();
     }
 
-    fn draw_status(&self, ui: &mut egui::Ui) {
-        ui.add_space(20.0);
-        
-        if self.game.is_game_over() {
-            match self.game.get_winner() {
-                Some(player) => {
-                    let text = self.localization.get_text("winner").replace("{}", player.symbol());
-                    ui.heading(egui::RichText::new(text)
-                        .color(self.colors.highlight)
-                        .size(24.0));
+    /// Обрабатывает сообщения сервера
+    fn poll_online(&mut self, session: &mut Session) {
+        let Some(client) = &mut self.online else { return };
+        let messages: Vec<ServerMessage> = client.inbox.borrow_mut().drain(..).collect();
+        for message in messages {
+            match &message {
+                ServerMessage::Welcome { code, token, .. } => {
+                    let saved = SavedSeat { url: client.url.clone(), code: code.clone(), token: token.clone() };
+                    if let (Some(storage), Ok(text)) = (local_storage(), serde_json::to_string(&saved)) {
+                        let _ = storage.set_item(ONLINE_STORAGE_KEY, &text);
+                    }
+                    self.room_code = code.clone();
                 }
-                None => {
-                    ui.heading(egui::RichText::new(self.localization.get_text("draw"))
-                        .color(self.colors.highlight)
-                        .size(24.0));
+                // Сохраненное место больше не действительно
+                ServerMessage::Error { .. } if client.state.seat.is_none() => {
+                    if let Some(storage) = local_storage() {
+                        let _ = storage.remove_item(ONLINE_STORAGE_KEY);
+                    }
                 }
+                _ => {}
             }
-        } else {
-            let text = self.localization.get_text("current_player_turn").replace("{}", self.game.current_player_symbol());
-            ui.heading(egui::RichText::new(text)
-                .color(self.colors.text)
-                .size(20.0));
+            client.state.apply(message);
+        }
+        if let Some(game) = client.state.game() {
+            session.set_game(game);
         }
     }
 
-    fn draw_controls(&mut self, ui: &mut egui::Ui) {
-        ui.add_space(20.0);
-        
-        // Переключатель языка
-        ui.horizontal(|ui| {
-            ui.label(egui::RichText::new(self.localization.get_text("language_switch"))
-                .color(self.colors.text)
+    /// Отрисовывает панель онлайн-игры: комнаты по коду и лобби
+    fn draw_online(&mut self, ui: &mut egui::Ui, session: &mut Session) {
+        let rtl = session.localization().is_rtl();
+        let colors = *session.colors();
+        bidi::horizontal(ui, rtl, |ui| {
+            ui.label(egui::RichText::new(session.localization().get_text(MessageKey::Network))
+                .color(colors.text)
                 .size(16.0));
-            
-            if ui.button(egui::RichText::new(self.localization.get_text("russian"))
-                .color(if self.localization.language == Language::Russian { self.colors.highlight } else { self.colors.text })
-                .size(14.0))
-                .clicked() {
-                self.localization.language = Language::Russian;
-            }
-            
-            if ui.button(egui::RichText::new(self.localization.get_text("english"))
-                .color(if self.localization.language == Language::English { self.colors.highlight } else { self.colors.text })
-                .size(14.0))
-                .clicked() {
-                self.localization.language = Language::English;
-            }
+            ui.add(egui::TextEdit::singleline(&mut self.server_url).desired_width(160.0));
         });
         
-        ui.add_space(10.0);
+        match &self.online {
+            None => {
+                let ctx = ui.ctx().clone();
+                bidi::horizontal(ui, rtl, |ui| {
+                    if ui.button(session.localization().get_text(MessageKey::HostGame)).clicked() {
+                        match session.time_control() {
+                            Ok(time_control) => {
+                                let message = ClientMessage::Create { name: session.player_name().to_string(), rules: session.game().rules(), time_control };
+                                self.connect_online(&message, &ctx);
+                            }
+                            Err(e) => self.online_error = Some(e),
+                        }
+                    }
+                    ui.add(egui::TextEdit::singleline(&mut self.room_code).desired_width(70.0));
+                    if ui.button(session.localization().get_text(MessageKey::JoinGame)).clicked() {
+                        let message = ClientMessage::Join { code: self.room_code.clone(), name: session.player_name().to_string() };
+                        self.connect_online(&message, &ctx);
+                    }
+                    if ui.button(session.localization().get_text(MessageKey::Watch)).clicked() {
+                        let message = ClientMessage::Watch { code: self.room_code.clone() };
+                        self.connect_online(&message, &ctx);
+                    }
+                    if ui.button(session.localization().get_text(MessageKey::Lobby)).clicked() {
+                        self.connect_online(&ClientMessage::Lobby, &ctx);
+                    }
+                });
+            }
+            Some(client) if client.in_lobby && client.state.seat.is_none() => {
+                // Лобби: список заявок и своя заявка
+                if client.state.seeks.is_empty() {
+                    ui.label(egui::RichText::new(session.localization().get_text(MessageKey::NoSeeks)).color(colors.text));
+                }
+                for seek in &client.state.seeks {
+                    bidi::horizontal(ui, rtl, |ui| {
+                        let time_control = seek.time_control.map(|tc| tc.to_string()).unwrap_or_default();
+                        ui.label(egui::RichText::new(format!("{} ({}) {} {}", seek.name, seek.rating, seek.rules, time_control))
+                            .color(colors.text));
+                        if client.state.my_seek != Some(seek.id) && ui.button(session.localization().get_text(MessageKey::Accept)).clicked() {
+                            client.send(&ClientMessage::Accept { id: seek.id, name: session.player_name().to_string() });
+                        }
+                    });
+                }
+                let time_control = session.time_control();
+                let rules = session.game().rules();
+                let mut leave = false;
+                bidi::horizontal(ui, rtl, |ui| {
+                    ui.label(session.localization().get_text(MessageKey::PlayerName));
+                    ui.add(egui::TextEdit::singleline(session.player_name_mut()).desired_width(70.0));
+                    if client.state.my_seek.is_some() {
+                        if ui.button(session.localization().get_text(MessageKey::CancelSeek)).clicked() {
+                            client.send(&ClientMessage::CancelSeek);
+                        }
+                    } else if ui.button(session.localization().get_text(MessageKey::Seek)).clicked() {
+                        time_control
+                        .clone()
+                        .map(|time_control| client.send(&ClientMessage::Seek {
+                            name: session.player_name().to_string(),
+                            rules,
+                            time_control,
+                        }))
+                        .unwrap_or_else(|e| self.online_error = Some(e));
+                    }
+                    leave = ui.button(session.localization().get_text(MessageKey::Exit)).clicked();
+                });
+                if leave {
+                    self.leave_online(session);
+                }
+            }
+            Some(client) => {
+                let localization = session.localization();
+                let status = match (&client.state.seat, &client.state.watching, &client.state.room) {
+                    (Some((code, player)), _, Some(room)) => {
+                        let side = localization.format(MessageKey::NetYouPlay, &[("player", player.mark(localization).into())]);
+                        let waiting = if room.is_full() { "" } else { " …" };
+                        format!("{} · {}{}", code, side, waiting)
+                    }
+                    // Режим зрителя: доска обновляется сервером, клики отключены
+                    (None, Some(code), _) => localization.format(MessageKey::Watching, &[("code", code.as_str().into())]),
+                    _ => localization.get_text(MessageKey::Connecting),
+                };
+                ui.label(egui::RichText::new(status).color(colors.text));
+                // Действие применится, когда сервер пришлет новое состояние
+                let seated = client.state.room.as_ref().filter(|room| room.is_full()).and(client.state.seat.as_ref());
+                let player = seated.map(|(_, player)| *player);
+                let names = room_names(&client.state);
+                if let Some(action) = app::game_action_buttons(ui, localization, &colors, &names, session.game(), player) {
+                    client.send(&action.message());
+                }
+                if let Some(room) = &client.state.room {
+                    let mut info = localization.format(MessageKey::Spectators, &[("count", room.spectators.into())]);
+                    if let Some(time_control) = room.time_control {
+                        info.push_str(&format!(" · ⏱ {}", time_control));
+                    }
+                    ui.label(egui::RichText::new(info).color(colors.text).size(12.0));
+                    if !room.moves.is_empty() {
+                        ui.label(egui::RichText::new(localization.format(MessageKey::History, &[("moves", client.state.history().as_str().into())]))
+                            .color(colors.text)
+                            .size(12.0));
+                    }
+                }
+                if ui.button(localization.get_text(MessageKey::Exit)).clicked() {
+                    self.leave_online(session);
+                }
+            }
+        }
         
-        if ui.button(egui::RichText::new(self.localization.get_text("new_game"))
-            .color(self.colors.text)
-            .size(16.0))
-            .clicked() {
-            self.game.reset();
+        let server_error = self.online.as_ref().and_then(|client| client.state.error.as_ref());
+        if let Some(error) = self.online_error.as_ref().or(server_error) {
+            ui.label(egui::RichText::new(session.localization().format(MessageKey::NetError, &[("error", error.as_str().into())]))
+                .color(colors.x_color));
         }
     }
 }
 
-impl eframe::App for TicTacToeWebApp {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        egui::CentralPanel::default().show(ctx, |ui| {
-            ui.vertical_centered(|ui| {
-                ui.heading(egui::RichText::new(self.localization.get_text("game_title"))
-                    .color(self.colors.highlight)
-                    .size(28.0));
-                
-                ui.add_space(20.0);
-                
-                self.draw_board(ui);
-                self.draw_status(ui);
-                self.draw_controls(ui);
-            });
-        });
+/// Имена игроков онлайн-комнаты
+fn room_names(state: &OnlineState) -> PlayerNames {
+    let name = |index: usize| state.room.as_ref().and_then(|room| room.players[index].clone()).unwrap_or_default();
+    PlayerNames::new(&name(0), &name(1))
+}
+
+impl Shell for WebShell {
+    fn poll(&mut self, session: &mut Session, _ctx: &egui::Context) {
+        self.poll_online(session);
+    }
+
+    /// Пока есть подключение к серверу, партия удаленная
+    fn remote(&self, _session: &Session) -> Option<Remote> {
+        let client = self.online.as_ref()?;
+        let state = &client.state;
+        let remaining = [Player::X, Player::O].map(|player| state.clock_remaining(player));
+        Some(Remote {
+            names: room_names(state),
+            local_turn: state.is_local_turn(),
+            remaining: remaining[0].zip(remaining[1]).map(|(x, o)| [x, o]),
+            running: state.room.as_ref().and_then(|room| room.clock).and_then(|clock| clock.running),
+        })
+    }
+
+    /// Ход применится, когда сервер пришлет новое состояние
+    fn send_move(&mut self, _session: &mut Session, position: usize) {
+        if let Some(client) = &self.online {
+            if let Some(message) = client.state.move_message(position) {
+                client.send(&message);
+            }
+        }
+    }
+
+    fn draw_panels(&mut self, ui: &mut egui::Ui, session: &mut Session) {
+        self.draw_online(ui, session);
+    }
+
+    fn language_selected(&mut self, locale: &str) {
+        save_language(locale);
+    }
+
+    /// Меняет заголовок вкладки вместо заголовка окна
+    fn set_title(&mut self, _ctx: &egui::Context, title: &str) {
+        set_document_title(title);
     }
 }
@@ -0,0 +1,120 @@
+//! Интеграционные тесты лобби: несколько клиентов подключаются
+//! к настоящему серверу через WebSocket.
+
+use std::net::TcpListener;
+use std::time::Duration;
+
+use rust_tic_tac_toe::online::{ClientMessage, Connection, SeekInfo, ServerMessage, TimeControl};
+use rust_tic_tac_toe::server::{self, GameServer};
+use rust_tic_tac_toe::{Player, Rules};
+
+/// Запускает сервер на свободном порту и возвращает его адрес
+fn start_server() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("ws://{}", listener.local_addr().unwrap());
+    std::thread::spawn(move || server::serve(listener, GameServer::with_seed(42)));
+    url
+}
+
+/// Ждет первое сообщение, для которого `select` возвращает значение
+fn expect<T>(client: &Connection, mut select: impl FnMut(ServerMessage) -> Option<T>) -> T {
+    loop {
+        let message = client.recv_timeout(Duration::from_secs(5)).expect("сервер не ответил");
+        if let Some(value) = select(message) {
+            return value;
+        }
+    }
+}
+
+/// Ждет приветствие и возвращает код комнаты, ключ места и сторону
+fn welcome(client: &Connection) -> (String, String, Player) {
+    expect(client, |message| match message {
+        ServerMessage::Welcome { code, token, you_play } => Some((code, token, you_play)),
+        _ => None,
+    })
+}
+
+fn lobby(client: &Connection) -> Vec<SeekInfo> {
+    expect(client, |message| match message {
+        ServerMessage::Lobby { seeks } => Some(seeks),
+        _ => None,
+    })
+}
+
+fn seek(name: &str, rules: Rules, time_control: Option<TimeControl>) -> ClientMessage {
+    ClientMessage::Seek { name: name.to_string(), rules, time_control }
+}
+
+/// Тест: заявки видны подписчикам, одинаковые заявки сводятся в партию,
+/// а чужую заявку можно принять из списка
+#[test]
+fn test_seek_pairing_and_accept() {
+    let url = start_server();
+    let clients: Vec<Connection> = (0..4).map(|_| Connection::connect(&url).unwrap()).collect();
+    let [alice, bob, carol, dave] = &clients[..] else { unreachable!() };
+    let blitz = Some("3+2".parse().unwrap());
+
+    bob.send(ClientMessage::Lobby);
+    assert!(lobby(bob).is_empty());
+
+    alice.send(seek("alice", Rules::classic(), blitz));
+    expect(alice, |message| matches!(message, ServerMessage::Seeking { .. }).then_some(()));
+    let seeks = lobby(bob);
+    assert_eq!(seeks.len(), 1);
+    assert_eq!((seeks[0].name.as_str(), seeks[0].rating, seeks[0].time_control), ("alice", 1500, blitz));
+
+    carol.send(seek("carol", Rules::gomoku(), None));
+    assert_eq!(lobby(bob).len(), 2);
+
+    // Те же условия - партия начинается сразу, автор первой заявки играет за X
+    dave.send(seek("dave", Rules::classic(), blitz));
+    let (code_a, _, side_a) = welcome(alice);
+    let (code_d, _, side_d) = welcome(dave);
+    assert_eq!(code_a, code_d);
+    assert_eq!((side_a, side_d), (Player::X, Player::O));
+    let seeks = lobby(bob);
+    assert_eq!(seeks.len(), 1);
+    assert_eq!(seeks[0].name, "carol");
+
+    bob.send(ClientMessage::Accept { id: seeks[0].id, name: "bob".to_string() });
+    let (code_c, ..) = welcome(carol);
+    let (code_b, _, side_b) = welcome(bob);
+    assert_eq!(code_b, code_c);
+    assert_eq!(side_b, Player::O);
+    assert!(lobby(bob).is_empty());
+}
+
+/// Тест: результат партии из лобби меняет рейтинги
+#[test]
+fn test_rated_game_updates_ratings() {
+    let url = start_server();
+    let alice = Connection::connect(&url).unwrap();
+    let bob = Connection::connect(&url).unwrap();
+
+    alice.send(seek("alice", Rules::classic(), None));
+    expect(&alice, |message| matches!(message, ServerMessage::Seeking { .. }).then_some(()));
+    bob.send(seek("bob", Rules::classic(), None));
+    let (_, _, side) = welcome(&alice);
+    assert_eq!(side, Player::X);
+    welcome(&bob);
+
+    // X выигрывает по верхней строке
+    let (alice, bob) = (&alice, &bob);
+    for (seq, (client, position)) in [(alice, 0), (bob, 3), (alice, 1), (bob, 4), (alice, 2)].into_iter().enumerate() {
+        client.send(ClientMessage::Move { position, seq });
+        expect(client, |message| match message {
+            ServerMessage::State(state) if state.moves.last() == Some(&position) => Some(()),
+            ServerMessage::Error { reason } => panic!("ход отклонен: {}", reason),
+            _ => None,
+        });
+    }
+
+    // Новый клиент видит обновленный рейтинг в заявке
+    let watcher = Connection::connect(&url).unwrap();
+    watcher.send(ClientMessage::Lobby);
+    lobby(&watcher);
+    let alice_again = Connection::connect(&url).unwrap();
+    alice_again.send(seek("alice", Rules::gomoku(), None));
+    let seeks = lobby(&watcher);
+    assert_eq!(seeks[0].rating, 1516);
+}